@@ -0,0 +1,106 @@
+use anchor_lang::AnchorDeserialize;
+use anyhow::Result;
+use gor_race_client::state::PlatformVault;
+use gor_race_client::{instructions, pda, FeeReconciliation};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+
+use super::sent;
+use crate::context::Context;
+
+pub fn initialize(ctx: &Context, fee_bps: u16) -> Result<Value> {
+    let signature = ctx.send(&[instructions::initialize(ctx.wallet(), fee_bps)])?;
+    Ok(sent(signature, json!({
+        "platform_vault": pda::platform_vault().0.to_string(),
+        "authority": ctx.wallet().to_string(),
+        "platform_fee_bps": fee_bps,
+    })))
+}
+
+pub fn migrate(ctx: &Context) -> Result<Value> {
+    let signature = ctx.send(&[instructions::migrate_platform_vault(ctx.wallet())])?;
+    let platform_vault = ctx.fetch::<PlatformVault>(&pda::platform_vault().0)?;
+    Ok(sent(signature, json!({
+        "platform_vault": pda::platform_vault().0.to_string(),
+        "platform_fee_bps": platform_vault.platform_fee_bps,
+        "total_fees_collected": platform_vault.total_fees_collected,
+    })))
+}
+
+pub fn set_fee(ctx: &Context, fee_bps: u16) -> Result<Value> {
+    let previous = ctx.fetch::<PlatformVault>(&pda::platform_vault().0)?.platform_fee_bps;
+    let signature = ctx.send(&[instructions::update_platform_fee(ctx.wallet(), fee_bps)])?;
+    Ok(sent(signature, json!({ "previous_fee_bps": previous, "platform_fee_bps": fee_bps })))
+}
+
+pub fn set_limits(ctx: &Context, min_entry_fee: u64, max_entry_fee: u64, max_players_limit: u32) -> Result<Value> {
+    let signature = ctx.send(&[instructions::update_race_limits(
+        ctx.wallet(),
+        min_entry_fee,
+        max_entry_fee,
+        max_players_limit,
+    )])?;
+    Ok(sent(signature, json!({
+        "min_entry_fee": min_entry_fee,
+        "max_entry_fee": max_entry_fee,
+        "max_players_limit": max_players_limit,
+    })))
+}
+
+pub fn set_revenue_split(ctx: &Context, creator_share_bps: u16, referrer_share_bps: u16) -> Result<Value> {
+    let signature = ctx.send(&[instructions::update_revenue_split(ctx.wallet(), creator_share_bps, referrer_share_bps)])?;
+    Ok(sent(signature, json!({ "creator_share_bps": creator_share_bps, "referrer_share_bps": referrer_share_bps })))
+}
+
+pub fn set_crank_reward(ctx: &Context, crank_reward_bps: u16, max_crank_reward: u64) -> Result<Value> {
+    let signature = ctx.send(&[instructions::update_crank_reward(ctx.wallet(), crank_reward_bps, max_crank_reward)])?;
+    Ok(sent(signature, json!({ "crank_reward_bps": crank_reward_bps, "max_crank_reward": max_crank_reward })))
+}
+
+pub fn set_operator(ctx: &Context, operator: Pubkey) -> Result<Value> {
+    let signature = ctx.send(&[instructions::update_operator(ctx.wallet(), operator)])?;
+    Ok(sent(signature, json!({ "operator": operator.to_string() })))
+}
+
+pub fn set_pause(ctx: &Context, paused: u8) -> Result<Value> {
+    let signature = ctx.send(&[instructions::set_pause(ctx.wallet(), paused)])?;
+    Ok(sent(signature, json!({ "paused": format!("{paused:#06b}") })))
+}
+
+pub fn propose_authority(ctx: &Context, new_authority: Pubkey) -> Result<Value> {
+    let signature = ctx.send(&[instructions::propose_authority(ctx.wallet(), new_authority)])?;
+    Ok(sent(signature, json!({ "pending_authority": new_authority.to_string() })))
+}
+
+pub fn accept_authority(ctx: &Context) -> Result<Value> {
+    let signature = ctx.send(&[instructions::accept_authority(ctx.wallet())])?;
+    Ok(sent(signature, json!({ "authority": ctx.wallet().to_string() })))
+}
+
+pub fn cancel_authority_transfer(ctx: &Context) -> Result<Value> {
+    let signature = ctx.send(&[instructions::cancel_authority_transfer(ctx.wallet())])?;
+    Ok(sent(signature, json!({})))
+}
+
+pub fn withdraw(ctx: &Context, amount: u64, destination: Option<Pubkey>, mint: Option<Pubkey>) -> Result<Value> {
+    let token = ctx.token_race(mint)?;
+    let signature = ctx.send(&[instructions::withdraw_platform_fees(ctx.wallet(), amount, destination, token)])?;
+    Ok(sent(signature, json!({
+        "amount": amount,
+        "destination": destination.unwrap_or(ctx.wallet()).to_string(),
+        "mint": mint.map(|mint| mint.to_string()),
+    })))
+}
+
+pub fn reconcile(ctx: &Context) -> Result<Value> {
+    let data = ctx.simulate(instructions::reconcile_fees())?;
+    let reconciliation = FeeReconciliation::try_from_slice(&data)?;
+    Ok(json!({
+        "total_fees_collected": reconciliation.total_fees_collected,
+        "total_fees_withdrawn": reconciliation.total_fees_withdrawn,
+        "total_rewards_paid": reconciliation.total_rewards_paid,
+        "fees_held": reconciliation.fees_held,
+        "surplus": reconciliation.surplus,
+        "shortfall": reconciliation.shortfall,
+    }))
+}
@@ -0,0 +1,50 @@
+pub mod admin;
+pub mod player;
+pub mod race;
+
+use gor_race_client::state::{BetKind, ClaimStatus, RaceMode, RaceStatus};
+use serde_json::{json, Value};
+use solana_sdk::signature::Signature;
+
+/// Report for a sent transaction, `fields` is an object of what it changed
+fn sent(signature: Signature, fields: Value) -> Value {
+    let mut report = json!({ "signature": signature.to_string() });
+    if let (Some(report), Value::Object(fields)) = (report.as_object_mut(), fields) {
+        report.extend(fields);
+    }
+    report
+}
+
+fn race_status(status: RaceStatus) -> &'static str {
+    match status {
+        RaceStatus::Pending => "pending",
+        RaceStatus::Racing => "racing",
+        RaceStatus::Completed => "completed",
+        RaceStatus::Cancelled => "cancelled",
+    }
+}
+
+fn race_mode(mode: RaceMode) -> &'static str {
+    match mode {
+        RaceMode::Fixed => "fixed",
+        RaceMode::PariMutuel => "parimutuel",
+    }
+}
+
+fn bet_kind(kind: BetKind) -> &'static str {
+    match kind {
+        BetKind::Win => "win",
+        BetKind::Show => "show",
+        BetKind::Exacta => "exacta",
+        BetKind::Quinella => "quinella",
+        BetKind::Trifecta => "trifecta",
+    }
+}
+
+fn claim_status(status: ClaimStatus) -> &'static str {
+    match status {
+        ClaimStatus::Unclaimed => "unclaimed",
+        ClaimStatus::Claimed => "claimed",
+        ClaimStatus::Refunded => "refunded",
+    }
+}
@@ -0,0 +1,145 @@
+use anyhow::{Context as _, Result};
+use gor_race_client::state::{PlayerProfile, RewardAccount, Username};
+use gor_race_client::{instructions, pda};
+use serde_json::{json, Value};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+
+use super::sent;
+use crate::context::Context;
+use crate::SortArg;
+
+pub fn create_profile(ctx: &Context, username: String) -> Result<Value> {
+    let signature = ctx.send(&[instructions::create_profile(ctx.wallet(), username.clone())])?;
+    Ok(sent(signature, json!({
+        "profile": pda::player_profile(&ctx.wallet()).0.to_string(),
+        "username": username,
+    })))
+}
+
+pub fn rename_profile(ctx: &Context, username: String) -> Result<Value> {
+    let profile = ctx.fetch::<PlayerProfile>(&pda::player_profile(&ctx.wallet()).0)?;
+    let signature = ctx.send(&[instructions::rename_profile(ctx.wallet(), &profile.username, username.clone())])?;
+    Ok(sent(signature, json!({ "previous_username": profile.username, "username": username })))
+}
+
+/// Reserve the username of every profile created before the username registry,
+/// listing the ones whose name a case variant already holds so they can rename
+pub fn backfill_usernames(ctx: &Context) -> Result<Value> {
+    let mut claimed = Vec::new();
+    let mut taken = Vec::new();
+    for (_, profile) in ctx.fetch_all::<PlayerProfile>(None)? {
+        match ctx.fetch_optional::<Username>(&pda::username(&profile.username).0)? {
+            Some(registry) if registry.player == profile.player => continue,
+            Some(registry) => taken.push(json!({
+                "player": profile.player.to_string(),
+                "username": profile.username,
+                "held_by": registry.player.to_string(),
+            })),
+            None => {
+                let signature = ctx.send(&[instructions::claim_username(ctx.wallet(), profile.player, &profile.username)])?;
+                claimed.push(json!({
+                    "player": profile.player.to_string(),
+                    "username": profile.username,
+                    "signature": signature.to_string(),
+                }));
+            }
+        }
+    }
+    Ok(json!({ "claimed": claimed, "taken": taken }))
+}
+
+pub fn claim_rewards(ctx: &Context) -> Result<Value> {
+    let rewards = ctx.fetch::<RewardAccount>(&pda::reward_account(&ctx.wallet()).0)?;
+    let signature = ctx.send(&[instructions::claim_rewards(ctx.wallet())])?;
+    Ok(sent(signature, json!({
+        "amount": rewards.owed,
+        "total_claimed": rewards.total_claimed + rewards.owed,
+    })))
+}
+
+/// Look a profile up by wallet, or by username through the registry
+pub fn show(ctx: &Context, player: Option<&str>) -> Result<Value> {
+    let player = match player {
+        None => ctx.wallet(),
+        Some(player) => match player.parse::<Pubkey>() {
+            Ok(player) => player,
+            Err(_) => {
+                let registry = ctx
+                    .fetch_optional::<Username>(&pda::username(player).0)?
+                    .with_context(|| format!("no player holds the username {player}"))?;
+                registry.player
+            }
+        },
+    };
+
+    let profile = ctx.fetch::<PlayerProfile>(&pda::player_profile(&player).0)?;
+    let rewards = ctx.fetch_optional::<RewardAccount>(&pda::reward_account(&player).0)?;
+    Ok(json!({
+        "player": player.to_string(),
+        "username": profile.username,
+        "total_races": profile.total_races,
+        "total_wins": profile.total_wins,
+        "total_podiums": profile.total_podiums,
+        "total_earnings": profile.total_earnings,
+        "win_rate": format!("{:.1}%", profile.win_rate() * 100.0),
+        "podium_rate": format!("{:.1}%", profile.podium_rate() * 100.0),
+        "score": score(&profile),
+        "rewards_owed": rewards.as_ref().map_or(0, |rewards| rewards.owed),
+        "rewards_claimed": rewards.as_ref().map_or(0, |rewards| rewards.total_claimed),
+        "created_at": profile.created_at,
+        "updated_at": profile.updated_at,
+    }))
+}
+
+pub fn leaderboard(ctx: &Context, sort: SortArg, limit: usize) -> Result<Value> {
+    let mut profiles: Vec<PlayerProfile> = ctx.fetch_all::<PlayerProfile>(None)?.into_iter().map(|(_, profile)| profile).collect();
+
+    let key = |profile: &PlayerProfile| -> f64 {
+        match sort {
+            SortArg::Score => score(profile) as f64,
+            SortArg::Wins => profile.total_wins as f64,
+            SortArg::Races => profile.total_races as f64,
+            SortArg::Earnings => profile.total_earnings as f64,
+            SortArg::Winrate => profile.win_rate(),
+            SortArg::Podiums => profile.total_podiums as f64,
+        }
+    };
+    profiles.sort_by(|a, b| key(b).total_cmp(&key(a)));
+
+    let rows: Vec<Value> = profiles
+        .iter()
+        .take(limit)
+        .enumerate()
+        .map(|(rank, profile)| json!({
+            "rank": rank + 1,
+            "username": profile.username,
+            "wins": profile.total_wins,
+            "races": profile.total_races,
+            "podiums": profile.total_podiums,
+            "earnings": profile.total_earnings,
+            "win_rate": format!("{:.1}%", profile.win_rate() * 100.0),
+            "score": score(profile),
+            "player": profile.player.to_string(),
+        }))
+        .collect();
+    Ok(Value::Array(rows))
+}
+
+/// Same weighting as `scripts/leaderboard.ts`: 10 per win, 3 per podium,
+/// 1 per race and per GOR earned, boosted for consistent winners
+fn score(profile: &PlayerProfile) -> u64 {
+    let base = (profile.total_wins as f64 * 10.0)
+        + (profile.total_podiums as f64 * 3.0)
+        + profile.total_races as f64
+        + (profile.total_earnings as f64 / LAMPORTS_PER_SOL as f64);
+
+    let consistency = match profile.win_rate() {
+        _ if profile.total_races < 10 => 1.0,
+        rate if rate >= 0.3 => 1.5,
+        rate if rate >= 0.2 => 1.3,
+        rate if rate >= 0.1 => 1.1,
+        _ => 1.0,
+    };
+    (base * consistency).floor() as u64
+}
@@ -0,0 +1,349 @@
+use anchor_lang::AnchorDeserialize;
+use anyhow::{bail, Result};
+use gor_race_client::accounts::winning_combination_pools;
+use gor_race_client::constants::{MAX_HORSES, ODDS_PRECISION};
+use gor_race_client::program::utils::{bond_share, refund_amount};
+use gor_race_client::state::{
+    BetKind, ClaimStatus, CombinationPool, PlatformVault, PlayerEntry, PlayerProfile, Race, RaceMode, RaceStatus,
+};
+use gor_race_client::{instructions, pda, JoinRaceArgs, RaceConfig};
+use serde_json::{json, Value};
+use solana_sdk::hash::hash;
+use solana_sdk::pubkey::Pubkey;
+
+use super::{bet_kind, claim_status, race_mode, race_status, sent};
+use crate::context::{race_address, Context};
+use crate::race_file;
+
+/// Offset of `PlayerEntry::race`, after the discriminator and player
+const PLAYER_ENTRY_RACE_OFFSET: usize = 8 + 32;
+/// Offset of `CombinationPool::race`, right after the discriminator
+const COMBINATION_POOL_RACE_OFFSET: usize = 8;
+
+pub fn create(
+    ctx: &Context,
+    wait_time: Option<i64>,
+    parimutuel: bool,
+    mint: Option<Pubkey>,
+    config: RaceConfig,
+) -> Result<Value> {
+    let mode = if parimutuel { RaceMode::PariMutuel } else { RaceMode::Fixed };
+
+    // The seed stays on this machine until execute reveals it
+    let seed: [u8; 32] = rand::random();
+    let seed_commitment = hash(&seed).to_bytes();
+    race_file::save_pending(&ctx.races_dir, &seed_commitment, &seed)?;
+
+    let token = ctx.token_race(mint)?;
+    let next_race_id = || -> Result<u64> { Ok(ctx.fetch::<PlatformVault>(&pda::platform_vault().0)?.next_race_id) };
+
+    // Another creator may take the id between reading and sending it, then the next one is tried
+    let mut retries = 0;
+    let (race_id, signature) = loop {
+        let race_id = next_race_id()?;
+        let instruction =
+            instructions::create_race(ctx.wallet(), race_id, wait_time, seed_commitment, mode, config.clone(), token);
+        let transaction = ctx.sign(&[instruction])?;
+        let Err(err) = ctx.rpc.send_and_confirm_transaction(&transaction) else {
+            break (race_id, transaction.signatures[0]);
+        };
+
+        // A confirmation timeout can hide our own race landing, only another creator's race means a retry
+        match ctx.fetch_optional::<Race>(&pda::race(race_id).0)? {
+            Some(race) if race.creator == ctx.wallet() && race.seed_commitment == seed_commitment => {
+                break (race_id, transaction.signatures[0]);
+            }
+            Some(_) if retries < 3 => retries += 1,
+            _ => return Err(err.into()),
+        }
+    };
+
+    let race_key = pda::race(race_id).0;
+    let race = ctx.fetch::<Race>(&race_key)?;
+    race_file::save(&ctx.races_dir, race_id, &json!({
+        "raceId": race_id.to_string(),
+        "racePda": race_key.to_string(),
+        "raceVaultPda": pda::race_vault(&race_key).0.to_string(),
+        "mint": mint.map(|mint| mint.to_string()),
+        "creator": ctx.wallet().to_string(),
+        "maxPlayers": race.max_players,
+        "minPlayers": race.min_players,
+        "entryFee": race.entry_fee.to_string(),
+        "waitTime": race.wait_time,
+        "referralCode": race.referral_code,
+        "startTime": race.start_time,
+        "horseNames": race.horse_names,
+        "seed": hex::encode(seed),
+    }))?;
+    race_file::remove_pending(&ctx.races_dir, &seed_commitment)?;
+
+    Ok(sent(signature, json!({
+        "race": race_key.to_string(),
+        "race_id": race_id,
+        "referral_code": race.referral_code,
+        "mode": race_mode(mode),
+        "entry_fee": race.entry_fee,
+        "max_players": race.max_players,
+        "min_players": race.min_players,
+        "wait_time": race.wait_time,
+    })))
+}
+
+pub fn join(
+    ctx: &Context,
+    race: &str,
+    kind: BetKind,
+    horses: &[u8],
+    amount: Option<u64>,
+    referrer: Option<Pubkey>,
+) -> Result<Value> {
+    let race_key = race_address(race)?;
+    let race = ctx.fetch::<Race>(&race_key)?;
+
+    let mut selection = [0u8; 3];
+    selection[..horses.len()].copy_from_slice(horses);
+    // Quinella pairs share a pool whichever order they are picked in
+    if kind == BetKind::Quinella {
+        selection[..2].sort_unstable();
+    }
+
+    let args = JoinRaceArgs {
+        bet_kind: kind,
+        selection,
+        referral_code: race.referral_code.clone(),
+        amount,
+        referrer,
+    };
+    let token = ctx.token_race(race.mint)?;
+    let signature = ctx.send(&[instructions::join_race(race_key, ctx.wallet(), args, token)])?;
+
+    let entry = ctx.fetch::<PlayerEntry>(&pda::player_entry(&race_key, &ctx.wallet()).0)?;
+    Ok(sent(signature, json!({
+        "race": race_key.to_string(),
+        "bet_kind": bet_kind(kind),
+        "selection": horses,
+        "entry_amount": entry.entry_amount,
+        "bets": entry.bets.len(),
+    })))
+}
+
+pub fn execute(ctx: &Context, race: &str, seed: Option<&str>) -> Result<Value> {
+    let race_key = race_address(race)?;
+    let race = ctx.fetch::<Race>(&race_key)?;
+
+    // Only finishing a racing race reveals the seed
+    let seed = match (race.status, seed) {
+        (RaceStatus::Racing, Some(seed)) => Some(race_file::parse_seed(seed)?),
+        (RaceStatus::Racing, None) => race_file::load_seed(&ctx.races_dir, race.race_id, &race.seed_commitment)?,
+        (RaceStatus::Pending, _) => None,
+        (status, _) => bail!("race is {}, there is nothing to execute", race_status(status)),
+    };
+
+    // Token races pay the crank reward to the wallet's token account
+    let token = ctx.token_race(race.mint)?;
+    let mut transaction = Vec::new();
+    if let Some(token) = token {
+        transaction.push(token.create_token_account(&ctx.wallet(), &ctx.wallet()));
+    }
+    transaction.push(instructions::execute_race(race_key, race.creator, ctx.wallet(), seed, token));
+    let signature = ctx.send(&transaction)?;
+
+    let race = ctx.fetch::<Race>(&race_key)?;
+    Ok(sent(signature, json!({
+        "race": race_key.to_string(),
+        "status": race_status(race.status),
+        "winning_horses": (race.status == RaceStatus::Completed).then_some(race.winning_horses),
+    })))
+}
+
+/// Prize for a completed race, refund for a cancelled one
+pub fn claim(ctx: &Context, race: &str, with_profile: bool) -> Result<Value> {
+    let race_key = race_address(race)?;
+    let race = ctx.fetch::<Race>(&race_key)?;
+    let entry_key = pda::player_entry(&race_key, &ctx.wallet()).0;
+    let entry = ctx.fetch::<PlayerEntry>(&entry_key)?;
+    let token = ctx.token_race(race.mint)?;
+
+    let instruction = match race.status {
+        RaceStatus::Cancelled => instructions::claim_refund(race_key, ctx.wallet(), token),
+        RaceStatus::Completed => {
+            let pools = winning_combination_pools(&race_key, &race, &entry.bets);
+            let with_profile = with_profile
+                && ctx.fetch_optional::<PlayerProfile>(&pda::player_profile(&ctx.wallet()).0)?.is_some();
+            instructions::claim_prize(race_key, ctx.wallet(), &pools, with_profile, token)
+        }
+        status => bail!("race is {}, nothing can be claimed yet", race_status(status)),
+    };
+    let signature = ctx.send(&[instruction])?;
+
+    // Refunds are net of the crank rewards already paid, plus a share of a forfeited
+    // bond, which is always in GOR and only adds up with the refund in native races
+    let entry = ctx.fetch::<PlayerEntry>(&entry_key)?;
+    let (amount, bond) = match entry.claim_status {
+        ClaimStatus::Refunded => {
+            let refund = refund_amount(entry.entry_amount, race.total_pool, race.crank_rewards).unwrap_or_default();
+            let bond = if race.bond_forfeited {
+                bond_share(race.creator_bond, entry.entry_amount, race.total_pool).unwrap_or_default()
+            } else {
+                0
+            };
+            (if race.mint.is_none() { refund + bond } else { refund }, bond)
+        }
+        _ => (entry.prize_amount, 0),
+    };
+    Ok(sent(signature, json!({
+        "race": race_key.to_string(),
+        "claim_status": claim_status(entry.claim_status),
+        "amount": amount,
+        "bond_share": bond,
+    })))
+}
+
+pub fn update_stats(ctx: &Context, race: &str) -> Result<Value> {
+    let race_key = race_address(race)?;
+    let race = ctx.fetch::<Race>(&race_key)?;
+    let entry = ctx.fetch::<PlayerEntry>(&pda::player_entry(&race_key, &ctx.wallet()).0)?;
+
+    let pools = winning_combination_pools(&race_key, &race, &entry.bets);
+    let signature = ctx.send(&[instructions::update_stats(race_key, ctx.wallet(), &pools)])?;
+
+    let profile = ctx.fetch::<PlayerProfile>(&pda::player_profile(&ctx.wallet()).0)?;
+    Ok(sent(signature, json!({
+        "total_races": profile.total_races,
+        "total_wins": profile.total_wins,
+        "total_podiums": profile.total_podiums,
+        "total_earnings": profile.total_earnings,
+    })))
+}
+
+pub fn cancel(ctx: &Context, race: &str) -> Result<Value> {
+    let race_key = race_address(race)?;
+    let signature = ctx.send(&[instructions::cancel_race(race_key, ctx.wallet())])?;
+    Ok(sent(signature, json!({ "race": race_key.to_string(), "status": "cancelled" })))
+}
+
+/// Close whatever can be closed, one transaction per account. Entries still
+/// owed a prize or waiting on stats are reported and left open, with their
+/// referrer credited so an unclaimed prize does not hold back the referral reward.
+pub fn close(ctx: &Context, race: &str) -> Result<Value> {
+    let race_key = race_address(race)?;
+    let race = ctx.fetch::<Race>(&race_key)?;
+    if !race.is_settled() {
+        bail!("race is {}, it can only be closed once settled", race_status(race.status));
+    }
+
+    let mut closed = Vec::new();
+    let entries = ctx.fetch_all::<PlayerEntry>(Some((PLAYER_ENTRY_RACE_OFFSET, race_key)))?;
+    for (entry_key, entry) in &entries {
+        let pools = winning_combination_pools(&race_key, &race, &entry.bets);
+        let result = ctx.send(&[instructions::close_player_entry(race_key, entry.player, entry.referrer, &pools)]);
+        if let (Err(_), Some(referrer)) = (&result, entry.referrer) {
+            if race.status == RaceStatus::Completed && !entry.referral_settled {
+                ctx.send(&[instructions::settle_referral(race_key, entry.player, referrer)])?;
+            }
+        }
+        closed.push(json!({
+            "account": entry_key.to_string(),
+            "kind": "entry",
+            "result": result.map_or_else(|err| format!("left open: {err}"), |signature| signature.to_string()),
+        }));
+    }
+
+    let race = ctx.fetch::<Race>(&race_key)?;
+    if race.all_entries_closed() {
+        let pools = ctx.fetch_all::<CombinationPool>(Some((COMBINATION_POOL_RACE_OFFSET, race_key)))?;
+        for (pool_key, pool) in &pools {
+            let signature = ctx.send(&[instructions::close_combination_pool(race_key, *pool_key, pool.payer)])?;
+            closed.push(json!({ "account": pool_key.to_string(), "kind": "combination_pool", "result": signature.to_string() }));
+        }
+
+        let token = ctx.token_race(race.mint)?;
+        let signature = ctx.send(&[instructions::close_race(race_key, race.creator, token)])?;
+        closed.push(json!({ "account": race_key.to_string(), "kind": "race", "result": signature.to_string() }));
+    }
+
+    Ok(json!({
+        "race": race_key.to_string(),
+        "race_closed": race.all_entries_closed(),
+        "closed": closed,
+    }))
+}
+
+pub fn odds(ctx: &Context, race: &str) -> Result<Value> {
+    let race_key = race_address(race)?;
+    let race = ctx.fetch::<Race>(&race_key)?;
+    let odds = <[u64; MAX_HORSES]>::try_from_slice(&ctx.simulate(instructions::get_odds(race_key, race.mint))?)?;
+
+    let horses: Vec<Value> = odds
+        .iter()
+        .enumerate()
+        .map(|(i, odds)| json!({
+            "horse": i + 1,
+            "name": race.horse_names[i],
+            "odds": format!("{:.2}", *odds as f64 / ODDS_PRECISION as f64),
+        }))
+        .collect();
+    Ok(Value::Array(horses))
+}
+
+/// Race address for an id, checked against the platform's race counter
+pub fn address(ctx: &Context, race_id: u64) -> Result<Value> {
+    let race = Pubkey::try_from_slice(&ctx.simulate(instructions::get_race_address(race_id))?)?;
+    Ok(json!({ "race_id": race_id, "race": race.to_string() }))
+}
+
+pub fn show(ctx: &Context, race: &str) -> Result<Value> {
+    let race_key = race_address(race)?;
+    let race = ctx.fetch::<Race>(&race_key)?;
+    let entries = ctx.fetch_all::<PlayerEntry>(Some((PLAYER_ENTRY_RACE_OFFSET, race_key)))?;
+
+    let horses: Vec<Value> = (0..MAX_HORSES)
+        .map(|i| {
+            let attributes = race.horse_attributes[i];
+            json!({
+                "horse": i + 1,
+                "name": race.horse_names[i],
+                "speed": attributes.speed,
+                "stamina": attributes.stamina,
+                "form": attributes.form,
+                "win_pool": race.horse_pools[i],
+                "win_bets": race.horse_entry_counts[i],
+                "show_pool": race.show_pools[i],
+            })
+        })
+        .collect();
+    let entries: Vec<Value> = entries
+        .iter()
+        .map(|(_, entry)| json!({
+            "player": entry.player.to_string(),
+            "bets": entry.bets.iter().map(|bet| format!("{} {:?}", bet_kind(bet.bet_kind), &bet.selection[..bet.bet_kind.selection_len()])).collect::<Vec<_>>(),
+            "staked": entry.entry_amount,
+            "status": claim_status(entry.claim_status),
+            "prize": entry.prize_amount,
+        }))
+        .collect();
+
+    Ok(json!({
+        "race": race_key.to_string(),
+        "race_id": race.race_id,
+        "referral_code": race.referral_code,
+        "creator": race.creator.to_string(),
+        "status": race_status(race.status),
+        "mode": race_mode(race.mode),
+        "mint": race.mint.map(|mint| mint.to_string()),
+        "entry_fee": race.entry_fee,
+        "players": format!("{}/{}", race.entry_count, race.max_players),
+        "min_players": race.min_players,
+        "total_pool": race.total_pool,
+        "platform_fee": race.platform_fee,
+        "crank_rewards": race.crank_rewards,
+        "prize_distribution": race.prize_distribution,
+        "start_time": race.start_time,
+        "wait_time": race.wait_time,
+        "race_start_time": race.race_start_time,
+        "end_time": race.end_time,
+        "winning_horses": (race.status == RaceStatus::Completed).then_some(race.winning_horses),
+        "horses": horses,
+        "entries": entries,
+    }))
+}
@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{anyhow, bail, Context as _, Result};
+use base64::Engine;
+use gor_race_client::accounts::{decode, discriminator};
+use gor_race_client::{pda, TokenRace};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::output::Format;
+
+pub struct Context {
+    pub rpc: RpcClient,
+    pub payer: Keypair,
+    pub format: Format,
+    pub races_dir: PathBuf,
+}
+
+impl Context {
+    pub fn new(url: &str, keypair: &str, format: Format, races_dir: PathBuf) -> Result<Self> {
+        let keypair_path = match keypair.strip_prefix("~/") {
+            Some(rest) => PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(rest),
+            None => PathBuf::from(keypair),
+        };
+        let payer = read_keypair_file(&keypair_path)
+            .map_err(|err| anyhow!("reading keypair {}: {err}", keypair_path.display()))?;
+
+        Ok(Self {
+            rpc: RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()),
+            payer,
+            format,
+            races_dir,
+        })
+    }
+
+    pub fn wallet(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Sign with the wallet, send and wait for confirmation
+    pub fn send(&self, instructions: &[Instruction]) -> Result<Signature> {
+        Ok(self.rpc.send_and_confirm_transaction(&self.sign(instructions)?)?)
+    }
+
+    /// Sign with the wallet against the latest blockhash, the signature is known before sending
+    pub fn sign(&self, instructions: &[Instruction]) -> Result<Transaction> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        Ok(Transaction::new_signed_with_payer(instructions, Some(&self.wallet()), &[&self.payer], blockhash))
+    }
+
+    /// Run a read-only instruction and return the data it set with `set_return_data`
+    pub fn simulate(&self, instruction: Instruction) -> Result<Vec<u8>> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction =
+            Transaction::new_signed_with_payer(&[instruction], Some(&self.wallet()), &[&self.payer], blockhash);
+        let result = self.rpc.simulate_transaction(&transaction)?.value;
+        if let Some(err) = result.err {
+            bail!("simulation failed: {err}\n{}", result.logs.unwrap_or_default().join("\n"));
+        }
+
+        let (data, _) = result.return_data.context("instruction returned no data")?.data;
+        Ok(base64::engine::general_purpose::STANDARD.decode(data)?)
+    }
+
+    pub fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        let data = self.rpc.get_account_data(address).with_context(|| format!("fetching {address}"))?;
+        Ok(decode(&data)?)
+    }
+
+    pub fn fetch_optional<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<Option<T>> {
+        let account = self.rpc.get_account_with_commitment(address, self.rpc.commitment())?.value;
+        account.map(|account| decode(&account.data)).transpose().map_err(Into::into)
+    }
+
+    /// Every account of one type, optionally narrowed by a pubkey field at `offset`
+    pub fn fetch_all<T>(&self, field: Option<(usize, Pubkey)>) -> Result<Vec<(Pubkey, T)>>
+    where
+        T: AccountDeserialize + Discriminator,
+    {
+        let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &discriminator::<T>()))];
+        if let Some((offset, key)) = field {
+            filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(offset, key.as_ref())));
+        }
+
+        let accounts = self.rpc.get_program_accounts_with_config(
+            &gor_race_client::ID,
+            RpcProgramAccountsConfig {
+                filters: Some(filters),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )?;
+        accounts
+            .into_iter()
+            .map(|(address, account)| Ok((address, decode(&account.data)?)))
+            .collect()
+    }
+
+    /// Token accounts for a race paid in `mint`, its owner program tells SPL Token from Token-2022
+    pub fn token_race(&self, mint: Option<Pubkey>) -> Result<Option<TokenRace>> {
+        mint.map(|mint| {
+            let token_program = self.rpc.get_account(&mint).with_context(|| format!("fetching mint {mint}"))?.owner;
+            Ok(TokenRace { mint, token_program })
+        })
+        .transpose()
+    }
+}
+
+/// Resolve a race given as race id, 8 character referral code or address
+pub fn race_address(race: &str) -> Result<Pubkey> {
+    if race.len() == 8 {
+        if let Some(race_id) = gor_race_client::program::utils::decode_referral_code(race) {
+            return Ok(pda::race(race_id).0);
+        }
+    }
+    if let Ok(race_id) = race.parse::<u64>() {
+        return Ok(pda::race(race_id).0);
+    }
+    race.parse().with_context(|| format!("{race} is not a race id, referral code or address"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gor_race_client::program::utils::generate_referral_code;
+
+    #[test]
+    fn test_race_address() {
+        let race_id = 1_751_544_318;
+        let race = pda::race(race_id).0;
+
+        assert_eq!(race_address("1751544318").unwrap(), race);
+        assert_eq!(race_address(&generate_referral_code(race_id)).unwrap(), race);
+        assert_eq!(race_address(&race.to_string()).unwrap(), race);
+        assert!(race_address("not a race").is_err());
+    }
+}
@@ -0,0 +1,301 @@
+//! `gor-race`, the operator command line for the gor-race program.
+//!
+//! Every program instruction has a subcommand, alongside readers that decode
+//! races, profiles and the leaderboard. Amounts are always in base units
+//! (lamports for native GOR races).
+
+mod commands;
+mod context;
+mod output;
+mod race_file;
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand, ValueEnum};
+use gor_race_client::state::BetKind;
+use solana_sdk::pubkey::Pubkey;
+
+use context::Context;
+use output::Format;
+
+#[derive(Parser)]
+#[clap(name = "gor-race", version, about = "Operate and inspect the gor-race program")]
+struct Cli {
+    /// RPC endpoint
+    #[clap(long, short = 'u', global = true, env = "ANCHOR_PROVIDER_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// Keypair that signs and pays for transactions
+    #[clap(long, short = 'k', global = true, env = "ANCHOR_WALLET", default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// Output format
+    #[clap(long, short = 'o', global = true, value_enum, default_value = "table")]
+    output: Format,
+
+    /// Where create-race saves race seeds and execute reads them, shared with the TypeScript scripts
+    #[clap(long, global = true, default_value = "races")]
+    races_dir: PathBuf,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the platform vault (once per deployment)
+    Initialize {
+        #[clap(long, default_value = "500")]
+        fee_bps: u16,
+    },
+    /// Rewrite a platform vault created by the first release to the current layout (authority only)
+    Migrate,
+    /// Create a player profile for the signing wallet
+    CreateProfile { username: String },
+    /// Change the signing wallet's username
+    RenameProfile { username: String },
+    /// Reserve the usernames of profiles created before the username registry
+    BackfillUsernames,
+    /// Create a race and save its secret seed to the races directory
+    CreateRace {
+        /// Seconds players have to join, 30 to 180
+        #[clap(long)]
+        wait_time: Option<i64>,
+        /// Pool betting race instead of a fixed entry fee race
+        #[clap(long)]
+        parimutuel: bool,
+        /// SPL or Token-2022 mint entries are paid in, native GOR when empty
+        #[clap(long)]
+        mint: Option<Pubkey>,
+        #[clap(long)]
+        entry_fee: Option<u64>,
+        #[clap(long)]
+        max_players: Option<u32>,
+        /// Entries needed to start, the race is cancelled with fewer
+        #[clap(long)]
+        min_players: Option<u32>,
+        /// Basis points per place, e.g. 5000,3000,1500
+        #[clap(long, use_value_delimiter = true)]
+        prize_distribution: Option<Vec<u16>>,
+    },
+    /// Place a bet on a race
+    Join {
+        /// Race id, referral code or race address
+        race: String,
+        #[clap(value_enum)]
+        bet_kind: BetArg,
+        /// Horse numbers in finishing order, 1 to 10
+        #[clap(required = true, max_values = 3)]
+        horses: Vec<u8>,
+        /// Stake, at least the entry fee which it defaults to
+        #[clap(long)]
+        amount: Option<u64>,
+        /// Wallet earning a share of the fee on this entry, native races only
+        #[clap(long)]
+        referrer: Option<Pubkey>,
+    },
+    /// Start a pending race, or finish a racing one by revealing its seed
+    Execute {
+        race: String,
+        /// Hex seed, read from the races directory when empty
+        #[clap(long)]
+        seed: Option<String>,
+    },
+    /// Claim a prize from a completed race, or a refund from a cancelled one
+    Claim {
+        race: String,
+        /// Do not count the race on the player's profile
+        #[clap(long)]
+        skip_profile: bool,
+    },
+    /// Claim creator and referrer rewards
+    ClaimRewards,
+    /// Count a race on the player's profile when claim did not
+    UpdateStats { race: String },
+    /// Cancel a stuck race so entries can be refunded (authority only)
+    CancelRace { race: String },
+    /// Close every settled entry and combination pool of a race, then the race itself
+    Close { race: String },
+    /// Show implied decimal odds for each horse
+    Odds { race: String },
+    /// Set the platform fee (authority only)
+    SetFee { fee_bps: u16 },
+    /// Set the entry fee and player limits (authority only)
+    SetLimits { min_entry_fee: u64, max_entry_fee: u64, max_players_limit: u32 },
+    /// Set the creator and referrer shares of the platform fee (authority only)
+    SetRevenueSplit { creator_share_bps: u16, referrer_share_bps: u16 },
+    /// Set the share of a race pool paid to whoever executes it (authority only)
+    SetCrankReward {
+        crank_reward_bps: u16,
+        /// Most one execute_race pays, in GOR base units scaled to token race mints, 0 for no cap
+        #[clap(long, default_value = "0")]
+        max_crank_reward: u64,
+    },
+    /// Set the operator allowed to reveal race seeds (authority only)
+    SetOperator { operator: Pubkey },
+    /// Pause instructions, or resume everything with no flags (authority only)
+    SetPause {
+        #[clap(value_enum)]
+        flags: Vec<PauseArg>,
+    },
+    /// Transfer the platform authority
+    #[clap(subcommand)]
+    Authority(AuthorityCommand),
+    /// Withdraw platform fees (authority only)
+    Withdraw {
+        amount: u64,
+        /// Wallet receiving the fees, the authority when empty
+        #[clap(long)]
+        destination: Option<Pubkey>,
+        /// Withdraw fees collected in this token instead of native GOR
+        #[clap(long)]
+        mint: Option<Pubkey>,
+    },
+    /// Compare recorded platform fees with the platform vault balance
+    Reconcile,
+    /// Read race accounts
+    #[clap(subcommand)]
+    Race(RaceCommand),
+    /// Read player profiles
+    #[clap(subcommand)]
+    Profile(ProfileCommand),
+    /// Rank player profiles
+    Leaderboard {
+        #[clap(long, value_enum, default_value = "score")]
+        sort: SortArg,
+        #[clap(long, default_value = "10")]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuthorityCommand {
+    /// Propose a new authority (authority only)
+    Propose { new_authority: Pubkey },
+    /// Accept a pending transfer with the signing wallet
+    Accept,
+    /// Drop a pending transfer (authority only)
+    Cancel,
+}
+
+#[derive(Subcommand)]
+enum RaceCommand {
+    /// Decode a race account
+    Show { race: String },
+    /// Look up the address of a race id
+    Address { race_id: u64 },
+}
+
+#[derive(Subcommand)]
+enum ProfileCommand {
+    /// Decode a player profile, by wallet or username, the signing wallet's when empty
+    Show { player: Option<String> },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BetArg {
+    Win,
+    Show,
+    Exacta,
+    Quinella,
+    Trifecta,
+}
+
+impl From<BetArg> for BetKind {
+    fn from(bet: BetArg) -> Self {
+        match bet {
+            BetArg::Win => BetKind::Win,
+            BetArg::Show => BetKind::Show,
+            BetArg::Exacta => BetKind::Exacta,
+            BetArg::Quinella => BetKind::Quinella,
+            BetArg::Trifecta => BetKind::Trifecta,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PauseArg {
+    CreateRace,
+    JoinRace,
+    ExecuteRace,
+    ClaimPrize,
+    All,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SortArg {
+    Score,
+    Wins,
+    Races,
+    Earnings,
+    Winrate,
+    Podiums,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let ctx = Context::new(&cli.url, &cli.keypair, cli.output, cli.races_dir)?;
+
+    let report = match cli.command {
+        Command::Initialize { fee_bps } => commands::admin::initialize(&ctx, fee_bps)?,
+        Command::Migrate => commands::admin::migrate(&ctx)?,
+        Command::CreateProfile { username } => commands::player::create_profile(&ctx, username)?,
+        Command::RenameProfile { username } => commands::player::rename_profile(&ctx, username)?,
+        Command::BackfillUsernames => commands::player::backfill_usernames(&ctx)?,
+        Command::CreateRace { wait_time, parimutuel, mint, entry_fee, max_players, min_players, prize_distribution } => {
+            let config = gor_race_client::RaceConfig { entry_fee, max_players, min_players, prize_distribution };
+            commands::race::create(&ctx, wait_time, parimutuel, mint, config)?
+        }
+        Command::Join { race, bet_kind, horses, amount, referrer } => {
+            commands::race::join(&ctx, &race, bet_kind.into(), &horses, amount, referrer)?
+        }
+        Command::Execute { race, seed } => commands::race::execute(&ctx, &race, seed.as_deref())?,
+        Command::Claim { race, skip_profile } => commands::race::claim(&ctx, &race, !skip_profile)?,
+        Command::ClaimRewards => commands::player::claim_rewards(&ctx)?,
+        Command::UpdateStats { race } => commands::race::update_stats(&ctx, &race)?,
+        Command::CancelRace { race } => commands::race::cancel(&ctx, &race)?,
+        Command::Close { race } => commands::race::close(&ctx, &race)?,
+        Command::Odds { race } => commands::race::odds(&ctx, &race)?,
+        Command::SetFee { fee_bps } => commands::admin::set_fee(&ctx, fee_bps)?,
+        Command::SetLimits { min_entry_fee, max_entry_fee, max_players_limit } => {
+            commands::admin::set_limits(&ctx, min_entry_fee, max_entry_fee, max_players_limit)?
+        }
+        Command::SetRevenueSplit { creator_share_bps, referrer_share_bps } => {
+            commands::admin::set_revenue_split(&ctx, creator_share_bps, referrer_share_bps)?
+        }
+        Command::SetCrankReward { crank_reward_bps, max_crank_reward } => {
+            commands::admin::set_crank_reward(&ctx, crank_reward_bps, max_crank_reward)?
+        }
+        Command::SetOperator { operator } => commands::admin::set_operator(&ctx, operator)?,
+        Command::SetPause { flags } => commands::admin::set_pause(&ctx, pause_bits(&flags))?,
+        Command::Authority(AuthorityCommand::Propose { new_authority }) => {
+            commands::admin::propose_authority(&ctx, new_authority)?
+        }
+        Command::Authority(AuthorityCommand::Accept) => commands::admin::accept_authority(&ctx)?,
+        Command::Authority(AuthorityCommand::Cancel) => commands::admin::cancel_authority_transfer(&ctx)?,
+        Command::Withdraw { amount, destination, mint } => commands::admin::withdraw(&ctx, amount, destination, mint)?,
+        Command::Reconcile => commands::admin::reconcile(&ctx)?,
+        Command::Race(RaceCommand::Show { race }) => commands::race::show(&ctx, &race)?,
+        Command::Race(RaceCommand::Address { race_id }) => commands::race::address(&ctx, race_id)?,
+        Command::Profile(ProfileCommand::Show { player }) => commands::player::show(&ctx, player.as_deref())?,
+        Command::Leaderboard { sort, limit } => commands::player::leaderboard(&ctx, sort, limit)?,
+    };
+
+    output::print(&report, ctx.format);
+    Ok(())
+}
+
+fn pause_bits(flags: &[PauseArg]) -> u8 {
+    use gor_race_client::constants::*;
+
+    flags.iter().fold(0, |bits, flag| {
+        bits | match flag {
+            PauseArg::CreateRace => PAUSE_CREATE_RACE,
+            PauseArg::JoinRace => PAUSE_JOIN_RACE,
+            PauseArg::ExecuteRace => PAUSE_EXECUTE_RACE,
+            PauseArg::ClaimPrize => PAUSE_CLAIM_PRIZE,
+            PauseArg::All => PAUSE_ALL,
+        }
+    })
+}
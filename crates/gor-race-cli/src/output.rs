@@ -0,0 +1,70 @@
+use clap::ValueEnum;
+use serde_json::{Map, Value};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+pub fn print(report: &Value, format: Format) {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(report).expect("reports are plain JSON")),
+        Format::Table => print_table(report),
+    }
+}
+
+/// Objects print as aligned `key: value` rows, lists of objects as columns
+fn print_table(report: &Value) {
+    match report {
+        Value::Object(fields) => {
+            let width = fields.keys().map(String::len).max().unwrap_or(0);
+            for (key, value) in fields {
+                match value {
+                    Value::Array(rows) if rows.iter().all(Value::is_object) && !rows.is_empty() => {
+                        println!("\n{key}:");
+                        print_rows(rows);
+                    }
+                    _ => println!("{key:<width$}  {}", cell(value)),
+                }
+            }
+        }
+        Value::Array(rows) if rows.is_empty() => println!("(none)"),
+        Value::Array(rows) => print_rows(rows),
+        value => println!("{}", cell(value)),
+    }
+}
+
+fn print_rows(rows: &[Value]) {
+    let empty = Map::new();
+    let columns: Vec<&String> = rows[0].as_object().unwrap_or(&empty).keys().collect();
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| columns.iter().map(|column| cell(&row[column.as_str()])).collect())
+        .collect();
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| cells.iter().map(|row| row[i].len()).chain([column.len()]).max().unwrap_or(0))
+        .collect();
+
+    let line = |values: Vec<&str>| {
+        let padded: Vec<String> = values.iter().zip(&widths).map(|(value, width)| format!("{value:<width$}")).collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+    line(columns.iter().map(|column| column.as_str()).collect());
+    let rules: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    line(rules.iter().map(String::as_str).collect());
+    for row in &cells {
+        line(row.iter().map(String::as_str).collect());
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(cell).collect::<Vec<_>>().join(", "),
+        value => value.to_string(),
+    }
+}
@@ -0,0 +1,57 @@
+//! Race info files under `races/`, written by create-race and read by execute.
+//! The layout matches `scripts/create-race.ts` so either tool can finish a
+//! race the other created. Seeds are first written to `pending_<commitment>.json`
+//! and moved into the race file once the race id is confirmed.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use serde_json::{json, Value};
+
+fn path(dir: &Path, race_id: u64) -> std::path::PathBuf {
+    dir.join(format!("race_{race_id}.json"))
+}
+
+/// Seed saved before create-race is sent, when the race id is not known yet
+fn pending_path(dir: &Path, seed_commitment: &[u8; 32]) -> std::path::PathBuf {
+    dir.join(format!("pending_{}.json", hex::encode(seed_commitment)))
+}
+
+pub fn save(dir: &Path, race_id: u64, info: &Value) -> Result<()> {
+    write(&path(dir, race_id), dir, info)
+}
+
+/// Keep the seed under its commitment until the race it creates is confirmed,
+/// so a crash or timeout after sending does not lose it
+pub fn save_pending(dir: &Path, seed_commitment: &[u8; 32], seed: &[u8; 32]) -> Result<()> {
+    write(&pending_path(dir, seed_commitment), dir, &json!({ "seed": hex::encode(seed) }))
+}
+
+pub fn remove_pending(dir: &Path, seed_commitment: &[u8; 32]) -> Result<()> {
+    let path = pending_path(dir, seed_commitment);
+    fs::remove_file(&path).with_context(|| format!("removing {}", path.display()))
+}
+
+fn write(path: &Path, dir: &Path, info: &Value) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    fs::write(path, serde_json::to_string_pretty(info)?).with_context(|| format!("writing {}", path.display()))
+}
+
+/// The secret seed committed at creation, if this machine created the race.
+/// Falls back to the pending seed when create-race never saw its confirmation.
+pub fn load_seed(dir: &Path, race_id: u64, seed_commitment: &[u8; 32]) -> Result<Option<[u8; 32]>> {
+    for path in [path(dir, race_id), pending_path(dir, seed_commitment)] {
+        let Ok(contents) = fs::read_to_string(path) else {
+            continue;
+        };
+        let info: Value = serde_json::from_str(&contents)?;
+        return info["seed"].as_str().map(parse_seed).transpose();
+    }
+    Ok(None)
+}
+
+pub fn parse_seed(hex_seed: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(hex_seed).context("seed is not hex")?;
+    bytes.try_into().map_err(|_| anyhow::anyhow!("seed must be 32 bytes"))
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use gor_race::state::{
+    Bet, BetKind, CombinationPool, PlatformVault, PlayerEntry, PlayerProfile, Race, RewardAccount, Username,
+};
+use gor_race::utils::winning_selection;
+use crate::pda;
+
+/// Decode any program account, checking its discriminator
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_race(data: &[u8]) -> Result<Race> {
+    decode(data)
+}
+
+pub fn decode_player_entry(data: &[u8]) -> Result<PlayerEntry> {
+    decode(data)
+}
+
+pub fn decode_player_profile(data: &[u8]) -> Result<PlayerProfile> {
+    decode(data)
+}
+
+pub fn decode_platform_vault(data: &[u8]) -> Result<PlatformVault> {
+    decode(data)
+}
+
+pub fn decode_reward_account(data: &[u8]) -> Result<RewardAccount> {
+    decode(data)
+}
+
+pub fn decode_username(data: &[u8]) -> Result<Username> {
+    decode(data)
+}
+
+pub fn decode_combination_pool(data: &[u8]) -> Result<CombinationPool> {
+    decode(data)
+}
+
+/// Leading bytes every account of this type starts with, for
+/// `getProgramAccounts` memcmp filters at offset 0
+pub fn discriminator<T: Discriminator>() -> [u8; 8] {
+    T::DISCRIMINATOR
+}
+
+/// Combination pools the program reads to settle an entry's exotic bets,
+/// passed as remaining accounts to claim_prize, update_stats and close_player_entry
+pub fn winning_combination_pools(race_key: &Pubkey, race: &Race, bets: &[Bet]) -> Vec<Pubkey> {
+    let mut pools = Vec::new();
+    for bet_kind in [BetKind::Exacta, BetKind::Quinella, BetKind::Trifecta] {
+        if bets.iter().any(|bet| bet.bet_kind == bet_kind) {
+            let selection = winning_selection(bet_kind, &race.winning_horses);
+            pools.push(pda::combination_pool(race_key, bet_kind, &selection).0);
+        }
+    }
+    pools
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_checks_discriminator() {
+        let player = Pubkey::new_unique();
+        let profile = PlayerProfile::new(player, "Rider".to_string(), 1_700_000_000).unwrap();
+        let mut data = Vec::new();
+        profile.try_serialize(&mut data).unwrap();
+
+        let decoded = decode_player_profile(&data).unwrap();
+        assert_eq!(decoded.player, player);
+        assert_eq!(decoded.username, "Rider");
+        assert_eq!(data[..8], discriminator::<PlayerProfile>());
+
+        // Same bytes read as another account type are rejected
+        assert!(decode_race(&data).is_err());
+        assert!(decode_player_profile(&data[..4]).is_err());
+    }
+}
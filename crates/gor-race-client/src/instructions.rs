@@ -0,0 +1,565 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{system_program, InstructionData};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use gor_race::instructions::RaceConfig;
+use gor_race::state::{BetKind, RaceMode};
+use gor_race::{accounts, instruction};
+use crate::pda;
+
+/// Mint and token program of a token race, leave it out for native GOR races
+#[derive(Clone, Copy, Debug)]
+pub struct TokenRace {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+}
+
+impl TokenRace {
+    /// Associated token account of `owner` for the race mint
+    pub fn token_account(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(owner, &self.mint, &self.token_program)
+    }
+
+    /// Create the associated token account of `owner`, doing nothing if it already exists
+    pub fn create_token_account(&self, payer: &Pubkey, owner: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: associated_token::ID,
+            accounts: vec![
+                AccountMeta::new(*payer, true),
+                AccountMeta::new(self.token_account(owner), false),
+                AccountMeta::new_readonly(*owner, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(self.token_program, false),
+            ],
+            // CreateIdempotent
+            data: vec![1],
+        }
+    }
+}
+
+/// Bet placed by join_race
+#[derive(Clone, Debug)]
+pub struct JoinRaceArgs {
+    pub bet_kind: BetKind,
+    pub selection: [u8; 3],
+    pub referral_code: String,
+    pub amount: Option<u64>, // Defaults to the race entry fee
+    pub referrer: Option<Pubkey>,
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: gor_race::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Append winning combination pools, read by the program as remaining accounts
+fn with_combination_pools(mut ix: Instruction, combination_pools: &[Pubkey]) -> Instruction {
+    ix.accounts.extend(combination_pools.iter().map(|pool| AccountMeta::new_readonly(*pool, false)));
+    ix
+}
+
+pub fn initialize(authority: Pubkey, platform_fee_bps: u16) -> Instruction {
+    build(
+        accounts::Initialize {
+            platform_vault: pda::platform_vault().0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::Initialize { platform_fee_bps },
+    )
+}
+
+pub fn migrate_platform_vault(authority: Pubkey) -> Instruction {
+    build(
+        accounts::MigratePlatformVault {
+            platform_vault: pda::platform_vault().0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::MigratePlatformVault {},
+    )
+}
+
+pub fn create_profile(player: Pubkey, username: String) -> Instruction {
+    build(
+        accounts::CreateProfile {
+            player_profile: pda::player_profile(&player).0,
+            username_account: pda::username(&username).0,
+            player,
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+        },
+        instruction::CreateProfile { username },
+    )
+}
+
+/// `current_username` is the name the profile holds now, its registry entry is released
+pub fn rename_profile(player: Pubkey, current_username: &str, new_username: String) -> Instruction {
+    build(
+        accounts::RenameProfile {
+            player_profile: pda::player_profile(&player).0,
+            old_username_account: pda::username(current_username).0,
+            new_username_account: pda::username(&new_username).0,
+            player,
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+        },
+        instruction::RenameProfile { new_username },
+    )
+}
+
+/// `player` owns the profile, anyone may pay to reserve its username
+pub fn claim_username(payer: Pubkey, player: Pubkey, username: &str) -> Instruction {
+    build(
+        accounts::ClaimUsername {
+            player_profile: pda::player_profile(&player).0,
+            username_account: pda::username(username).0,
+            payer,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimUsername {},
+    )
+}
+
+/// `race_id` must be the platform vault's `next_race_id`, the transaction
+/// fails if another race takes it first and can be rebuilt with the next one
+pub fn create_race(
+    creator: Pubkey,
+    race_id: u64,
+    wait_time: Option<i64>,
+    seed_commitment: [u8; 32],
+    mode: RaceMode,
+    config: RaceConfig,
+    token: Option<TokenRace>,
+) -> Instruction {
+    let race = pda::race(race_id).0;
+    let race_vault = pda::race_vault(&race).0;
+    let platform_vault = pda::platform_vault().0;
+
+    build(
+        accounts::CreateRace {
+            race,
+            creator,
+            creator_rewards: pda::reward_account(&creator).0,
+            race_vault,
+            platform_vault,
+            mint: token.map(|token| token.mint),
+            race_vault_token_account: token.map(|token| token.token_account(&race_vault)),
+            platform_token_account: token.map(|token| token.token_account(&platform_vault)),
+            token_program: token.map(|token| token.token_program),
+            associated_token_program: token.map(|_| associated_token::ID),
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+        },
+        instruction::CreateRace { wait_time, seed_commitment, mode, config },
+    )
+}
+
+/// Read-only, the race address comes back as return data
+pub fn get_race_address(race_id: u64) -> Instruction {
+    build(
+        accounts::GetRaceAddress {
+            platform_vault: pda::platform_vault().0,
+        },
+        instruction::GetRaceAddress { race_id },
+    )
+}
+
+pub fn join_race(race: Pubkey, player: Pubkey, args: JoinRaceArgs, token: Option<TokenRace>) -> Instruction {
+    let race_vault = pda::race_vault(&race).0;
+    let combination_pool = args.bet_kind
+        .is_combination()
+        .then(|| pda::combination_pool(&race, args.bet_kind, &args.selection).0);
+
+    build(
+        accounts::JoinRace {
+            race,
+            player_entry: pda::player_entry(&race, &player).0,
+            player,
+            combination_pool,
+            referrer_rewards: args.referrer.map(|referrer| pda::reward_account(&referrer).0),
+            race_vault,
+            platform_vault: pda::platform_vault().0,
+            mint: token.map(|token| token.mint),
+            player_token_account: token.map(|token| token.token_account(&player)),
+            race_vault_token_account: token.map(|token| token.token_account(&race_vault)),
+            token_program: token.map(|token| token.token_program),
+            system_program: system_program::ID,
+        },
+        instruction::JoinRace {
+            bet_kind: args.bet_kind,
+            selection: args.selection,
+            referral_code: args.referral_code,
+            amount: args.amount,
+            referrer: args.referrer,
+        },
+    )
+}
+
+/// Read-only, simulate it and decode the returned odds. Token races pass their mint.
+pub fn get_odds(race: Pubkey, mint: Option<Pubkey>) -> Instruction {
+    build(
+        accounts::GetOdds {
+            race,
+            platform_vault: pda::platform_vault().0,
+            mint,
+        },
+        instruction::GetOdds {},
+    )
+}
+
+/// Starts a pending race, or finishes a racing one once `seed` is revealed.
+/// Without a seed a racing race is cancelled after the reveal window.
+/// The crank reward of token races goes to the executor's associated token
+/// account, which must exist, see [`TokenRace::create_token_account`].
+pub fn execute_race(
+    race: Pubkey,
+    creator: Pubkey,
+    executor: Pubkey,
+    seed: Option<[u8; 32]>,
+    token: Option<TokenRace>,
+) -> Instruction {
+    let race_vault = pda::race_vault(&race).0;
+    let platform_vault = pda::platform_vault().0;
+
+    build(
+        accounts::ExecuteRace {
+            race,
+            race_vault,
+            platform_vault,
+            creator_rewards: pda::reward_account(&creator).0,
+            executor,
+            slot_hashes: sysvar::slot_hashes::ID,
+            clock: sysvar::clock::ID,
+            mint: token.map(|token| token.mint),
+            race_vault_token_account: token.map(|token| token.token_account(&race_vault)),
+            platform_token_account: token.map(|token| token.token_account(&platform_vault)),
+            executor_token_account: token.map(|token| token.token_account(&executor)),
+            token_program: token.map(|token| token.token_program),
+            system_program: system_program::ID,
+        },
+        instruction::ExecuteRace { seed },
+    )
+}
+
+/// Passing `with_profile` counts the race on the player's profile in the same transaction
+pub fn claim_prize(
+    race: Pubkey,
+    player: Pubkey,
+    combination_pools: &[Pubkey],
+    with_profile: bool,
+    token: Option<TokenRace>,
+) -> Instruction {
+    let race_vault = pda::race_vault(&race).0;
+
+    let ix = build(
+        accounts::ClaimPrize {
+            race,
+            player_entry: pda::player_entry(&race, &player).0,
+            player,
+            player_profile: with_profile.then(|| pda::player_profile(&player).0),
+            race_vault,
+            platform_vault: pda::platform_vault().0,
+            mint: token.map(|token| token.mint),
+            player_token_account: token.map(|token| token.token_account(&player)),
+            race_vault_token_account: token.map(|token| token.token_account(&race_vault)),
+            token_program: token.map(|token| token.token_program),
+            system_program: system_program::ID,
+        },
+        instruction::ClaimPrize {},
+    );
+    with_combination_pools(ix, combination_pools)
+}
+
+pub fn claim_refund(race: Pubkey, player: Pubkey, token: Option<TokenRace>) -> Instruction {
+    let race_vault = pda::race_vault(&race).0;
+
+    build(
+        accounts::ClaimRefund {
+            race,
+            player_entry: pda::player_entry(&race, &player).0,
+            player,
+            race_vault,
+            mint: token.map(|token| token.mint),
+            player_token_account: token.map(|token| token.token_account(&player)),
+            race_vault_token_account: token.map(|token| token.token_account(&race_vault)),
+            token_program: token.map(|token| token.token_program),
+            system_program: system_program::ID,
+        },
+        instruction::ClaimRefund {},
+    )
+}
+
+pub fn claim_rewards(beneficiary: Pubkey) -> Instruction {
+    build(
+        accounts::ClaimRewards {
+            reward_account: pda::reward_account(&beneficiary).0,
+            beneficiary,
+            platform_vault: pda::platform_vault().0,
+        },
+        instruction::ClaimRewards {},
+    )
+}
+
+pub fn cancel_race(race: Pubkey, authority: Pubkey) -> Instruction {
+    build(
+        accounts::CancelRace {
+            race,
+            platform_vault: pda::platform_vault().0,
+            authority,
+            slot_hashes: sysvar::slot_hashes::ID,
+            clock: sysvar::clock::ID,
+        },
+        instruction::CancelRace {},
+    )
+}
+
+/// `referrer` must be the one recorded on the entry, if any
+pub fn settle_referral(race: Pubkey, player: Pubkey, referrer: Pubkey) -> Instruction {
+    build(
+        accounts::SettleReferral {
+            race,
+            player_entry: pda::player_entry(&race, &player).0,
+            referrer_rewards: pda::reward_account(&referrer).0,
+        },
+        instruction::SettleReferral {},
+    )
+}
+
+pub fn close_player_entry(
+    race: Pubkey,
+    player: Pubkey,
+    referrer: Option<Pubkey>,
+    combination_pools: &[Pubkey],
+) -> Instruction {
+    let ix = build(
+        accounts::ClosePlayerEntry {
+            race,
+            player_entry: pda::player_entry(&race, &player).0,
+            player,
+            referrer_rewards: referrer.map(|referrer| pda::reward_account(&referrer).0),
+        },
+        instruction::ClosePlayerEntry {},
+    );
+    with_combination_pools(ix, combination_pools)
+}
+
+pub fn close_combination_pool(race: Pubkey, combination_pool: Pubkey, payer: Pubkey) -> Instruction {
+    build(
+        accounts::CloseCombinationPool {
+            race,
+            combination_pool,
+            payer,
+        },
+        instruction::CloseCombinationPool {},
+    )
+}
+
+pub fn close_race(race: Pubkey, creator: Pubkey, token: Option<TokenRace>) -> Instruction {
+    let race_vault = pda::race_vault(&race).0;
+    let platform_vault = pda::platform_vault().0;
+
+    build(
+        accounts::CloseRace {
+            race,
+            creator,
+            race_vault,
+            platform_vault,
+            mint: token.map(|token| token.mint),
+            race_vault_token_account: token.map(|token| token.token_account(&race_vault)),
+            platform_token_account: token.map(|token| token.token_account(&platform_vault)),
+            token_program: token.map(|token| token.token_program),
+            system_program: system_program::ID,
+        },
+        instruction::CloseRace {},
+    )
+}
+
+pub fn update_stats(race: Pubkey, player: Pubkey, combination_pools: &[Pubkey]) -> Instruction {
+    let ix = build(
+        accounts::UpdateStats {
+            player_profile: pda::player_profile(&player).0,
+            race,
+            player_entry: pda::player_entry(&race, &player).0,
+            player,
+            clock: sysvar::clock::ID,
+        },
+        instruction::UpdateStats {},
+    );
+    with_combination_pools(ix, combination_pools)
+}
+
+pub fn update_platform_fee(authority: Pubkey, new_fee_bps: u16) -> Instruction {
+    build(
+        accounts::UpdatePlatformFee {
+            platform_vault: pda::platform_vault().0,
+            authority,
+        },
+        instruction::UpdatePlatformFee { new_fee_bps },
+    )
+}
+
+pub fn update_race_limits(
+    authority: Pubkey,
+    min_entry_fee: u64,
+    max_entry_fee: u64,
+    max_players_limit: u32,
+) -> Instruction {
+    build(
+        accounts::UpdateRaceLimits {
+            platform_vault: pda::platform_vault().0,
+            authority,
+        },
+        instruction::UpdateRaceLimits { min_entry_fee, max_entry_fee, max_players_limit },
+    )
+}
+
+pub fn update_revenue_split(authority: Pubkey, creator_share_bps: u16, referrer_share_bps: u16) -> Instruction {
+    build(
+        accounts::UpdateRevenueSplit {
+            platform_vault: pda::platform_vault().0,
+            authority,
+        },
+        instruction::UpdateRevenueSplit { creator_share_bps, referrer_share_bps },
+    )
+}
+
+pub fn update_crank_reward(authority: Pubkey, crank_reward_bps: u16, max_crank_reward: u64) -> Instruction {
+    build(
+        accounts::UpdateCrankReward {
+            platform_vault: pda::platform_vault().0,
+            authority,
+        },
+        instruction::UpdateCrankReward { crank_reward_bps, max_crank_reward },
+    )
+}
+
+pub fn update_operator(authority: Pubkey, new_operator: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateOperator {
+            platform_vault: pda::platform_vault().0,
+            authority,
+        },
+        instruction::UpdateOperator { new_operator },
+    )
+}
+
+pub fn set_pause(authority: Pubkey, paused: u8) -> Instruction {
+    build(
+        accounts::SetPause {
+            platform_vault: pda::platform_vault().0,
+            authority,
+        },
+        instruction::SetPause { paused },
+    )
+}
+
+pub fn propose_authority(authority: Pubkey, new_authority: Pubkey) -> Instruction {
+    build(
+        accounts::ProposeAuthority {
+            platform_vault: pda::platform_vault().0,
+            authority,
+        },
+        instruction::ProposeAuthority { new_authority },
+    )
+}
+
+pub fn accept_authority(new_authority: Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAuthority {
+            platform_vault: pda::platform_vault().0,
+            new_authority,
+        },
+        instruction::AcceptAuthority {},
+    )
+}
+
+pub fn cancel_authority_transfer(authority: Pubkey) -> Instruction {
+    build(
+        accounts::CancelAuthorityTransfer {
+            platform_vault: pda::platform_vault().0,
+            authority,
+        },
+        instruction::CancelAuthorityTransfer {},
+    )
+}
+
+/// Read-only, simulate it and decode the returned `FeeReconciliation`
+pub fn reconcile_fees() -> Instruction {
+    build(
+        accounts::ReconcileFees {
+            platform_vault: pda::platform_vault().0,
+        },
+        instruction::ReconcileFees {},
+    )
+}
+
+/// Native fees go to `destination`, or the authority when empty. Token fees
+/// go to the associated token account of the same wallet.
+pub fn withdraw_platform_fees(
+    authority: Pubkey,
+    amount: u64,
+    destination: Option<Pubkey>,
+    token: Option<TokenRace>,
+) -> Instruction {
+    let platform_vault = pda::platform_vault().0;
+    let recipient = destination.unwrap_or(authority);
+
+    build(
+        accounts::WithdrawPlatformFees {
+            platform_vault,
+            authority,
+            destination: token.is_none().then_some(destination).flatten(),
+            mint: token.map(|token| token.mint),
+            platform_token_account: token.map(|token| token.token_account(&platform_vault)),
+            destination_token_account: token.map(|token| token.token_account(&recipient)),
+            token_program: token.map(|token| token.token_program),
+            system_program: system_program::ID,
+        },
+        instruction::WithdrawPlatformFees { amount },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_race_accounts() {
+        let race = pda::race(7).0;
+        let player = Pubkey::new_unique();
+        let args = JoinRaceArgs {
+            bet_kind: BetKind::Exacta,
+            selection: [3, 5, 0],
+            referral_code: "CODE".to_string(),
+            amount: None,
+            referrer: None,
+        };
+        let ix = join_race(race, player, args, None);
+
+        assert_eq!(ix.program_id, gor_race::ID);
+        assert_eq!(ix.accounts[1].pubkey, pda::player_entry(&race, &player).0);
+        assert!(ix.accounts[2].is_signer);
+        assert_eq!(ix.accounts[3].pubkey, pda::combination_pool(&race, BetKind::Exacta, &[3, 5, 0]).0);
+        // Accounts left out are passed as the program id
+        assert_eq!(ix.accounts[4].pubkey, gor_race::ID);
+    }
+
+    #[test]
+    fn test_combination_pools_are_remaining_accounts() {
+        let race = pda::race(7).0;
+        let player = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let ix = claim_prize(race, player, &[pool], true, None);
+
+        let last = ix.accounts.last().unwrap();
+        assert_eq!(last.pubkey, pool);
+        assert!(!last.is_writable && !last.is_signer);
+        assert_eq!(ix.accounts[3].pubkey, pda::player_profile(&player).0);
+    }
+}
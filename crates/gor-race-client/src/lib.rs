@@ -0,0 +1,18 @@
+//! Rust client for the gor-race program.
+//!
+//! Wraps the `cpi` build of the program with PDA derivation (`pda`), one
+//! instruction builder per handler (`instructions`) and account decoders
+//! (`accounts`). Nothing here talks to an RPC node, pair it with
+//! `solana-client` or any other transport to send and fetch.
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use gor_race as program;
+pub use gor_race::constants;
+pub use gor_race::events;
+pub use gor_race::instructions::{FeeReconciliation, RaceConfig};
+pub use gor_race::state;
+pub use gor_race::ID;
+pub use instructions::{JoinRaceArgs, TokenRace};
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::Pubkey;
+use gor_race::constants::*;
+use gor_race::state::{BetKind, Username};
+
+/// Platform vault, holding platform config and native fees
+pub fn platform_vault() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PLATFORM_VAULT_SEED], &gor_race::ID)
+}
+
+pub fn race(race_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RACE_SEED, &race_id.to_le_bytes()], &gor_race::ID)
+}
+
+/// System account holding a race's native stakes, also the authority of its token vault
+pub fn race_vault(race: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RACE_VAULT_SEED, race.as_ref()], &gor_race::ID)
+}
+
+pub fn player_entry(race: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PLAYER_ENTRY_SEED, race.as_ref(), player.as_ref()], &gor_race::ID)
+}
+
+pub fn player_profile(player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"player_profile", player.as_ref()], &gor_race::ID)
+}
+
+/// Registry entry for a username, whatever case it is given in
+pub fn username(username: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USERNAME_SEED, Username::fold(username).as_bytes()], &gor_race::ID)
+}
+
+/// Creator and referrer rewards owed to a beneficiary
+pub fn reward_account(beneficiary: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REWARD_ACCOUNT_SEED, beneficiary.as_ref()], &gor_race::ID)
+}
+
+/// Shared pool of every exotic bet on the same selection
+pub fn combination_pool(race: &Pubkey, bet_kind: BetKind, selection: &[u8; 3]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[COMBINATION_POOL_SEED, race.as_ref(), &[bet_kind as u8], selection],
+        &gor_race::ID,
+    )
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+use gor_race_client::accounts::{decode, decode_platform_vault, discriminator};
+use gor_race_client::state::{Race, RaceStatus};
+use gor_race_client::{instructions, pda, TokenRace};
+use log::{debug, info, warn};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::account::from_account;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::sysvar;
+use solana_sdk::transaction::Transaction;
+
+use crate::metrics::Metrics;
+use crate::schedule::{can_reveal, due_action, due_at, Action};
+
+/// Offset of `Race::status`, after the discriminator, race_id and creator
+const RACE_STATUS_OFFSET: usize = 8 + 8 + 32;
+
+pub struct Keeper {
+    pub rpc: RpcClient,
+    pub payer: Keypair,
+    pub races_dir: PathBuf,
+    pub max_retries: u32,
+    pub metrics: Arc<Metrics>,
+    /// Token program of each race mint seen so far, mints never change owner
+    token_programs: HashMap<Pubkey, Pubkey>,
+}
+
+impl Keeper {
+    pub fn new(rpc: RpcClient, payer: Keypair, races_dir: PathBuf, max_retries: u32, metrics: Arc<Metrics>) -> Self {
+        Self { rpc, payer, races_dir, max_retries, metrics, token_programs: HashMap::new() }
+    }
+
+    /// Execute every race that is due, returning the seconds until the next one will be
+    pub fn poll(&mut self) -> Result<Option<i64>> {
+        let now = self.cluster_time()?;
+        let operator = decode_platform_vault(&self.rpc.get_account_data(&pda::platform_vault().0)?)?.operator;
+
+        let pending = self.races_with_status(RaceStatus::Pending)?;
+        let racing = self.races_with_status(RaceStatus::Racing)?;
+        self.metrics.races_pending.store(pending.len() as u64, Ordering::Relaxed);
+        self.metrics.races_racing.store(racing.len() as u64, Ordering::Relaxed);
+        self.metrics.last_poll_time.store(now, Ordering::Relaxed);
+        debug!("{} pending and {} racing races at {now}", pending.len(), racing.len());
+
+        // Only fetched when there is a seed to reveal, the sysvar holds 512 slot hashes
+        let mut slot_hashes: Option<Vec<u8>> = None;
+        let mut next_due: Option<i64> = None;
+        for (race_key, race) in pending.into_iter().chain(racing) {
+            // Only the creator or the operator may reveal, and only with the seed create-race saved
+            let authorized = self.payer.pubkey() == race.creator || self.payer.pubkey() == operator;
+            let mut seed = match race.status {
+                RaceStatus::Racing if authorized => self.load_seed(&race),
+                _ => None,
+            };
+            if seed.is_some() {
+                let slot_hashes = match &slot_hashes {
+                    Some(slot_hashes) => slot_hashes,
+                    None => slot_hashes.insert(self.rpc.get_account_data(&sysvar::slot_hashes::ID)?),
+                };
+                if !can_reveal(true, &race, slot_hashes) {
+                    warn!("race {}: start slot hash expired, the race can only be cancelled", race.race_id);
+                    seed = None;
+                }
+            }
+
+            match due_action(&race, now, seed.is_some()) {
+                Some(action) => self.execute(race_key, &race, action, seed),
+                None => {
+                    if let Some(due) = due_at(&race, seed.is_some()) {
+                        next_due = Some(next_due.map_or(due, |next| next.min(due)));
+                    }
+                }
+            }
+        }
+
+        self.metrics.polls.fetch_add(1, Ordering::Relaxed);
+        Ok(next_due.map(|due| due - now))
+    }
+
+    fn execute(&mut self, race_key: Pubkey, race: &Race, action: Action, seed: Option<[u8; 32]>) {
+        let token = match race.mint.map(|mint| self.token_race(mint)).transpose() {
+            Ok(token) => token,
+            Err(err) => {
+                warn!("race {}: {err:#}", race.race_id);
+                self.metrics.execute_failures.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        };
+        // Token races pay the crank reward to the keeper's token account
+        let mut transaction = Vec::new();
+        if let Some(token) = token {
+            transaction.push(token.create_token_account(&self.payer.pubkey(), &self.payer.pubkey()));
+        }
+        transaction.push(instructions::execute_race(race_key, race.creator, self.payer.pubkey(), seed, token));
+
+        for attempt in 0..=self.max_retries {
+            if attempt > 0 {
+                self.metrics.retries.fetch_add(1, Ordering::Relaxed);
+                thread::sleep(Duration::from_millis(500 << attempt.min(5)));
+            }
+
+            match self.send(&transaction) {
+                Ok(signature) => {
+                    info!("race {}: {} ({signature})", race.race_id, action.label());
+                    self.metrics.record_execution(action);
+                    return;
+                }
+                Err(err) => warn!("race {}: {} attempt {} failed: {err:#}", race.race_id, action.label(), attempt + 1),
+            }
+        }
+        // Still due, the next poll tries again
+        self.metrics.execute_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn send(&self, instructions: &[Instruction]) -> Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction =
+            Transaction::new_signed_with_payer(instructions, Some(&self.payer.pubkey()), &[&self.payer], blockhash);
+        Ok(self.rpc.send_and_confirm_transaction(&transaction)?)
+    }
+
+    /// execute_race checks the Clock sysvar, which can drift from the keeper's own clock
+    fn cluster_time(&self) -> Result<i64> {
+        let account = self.rpc.get_account(&sysvar::clock::ID)?;
+        let clock: Clock = from_account(&account).context("decoding the clock sysvar")?;
+        Ok(clock.unix_timestamp)
+    }
+
+    fn races_with_status(&self, status: RaceStatus) -> Result<Vec<(Pubkey, Race)>> {
+        let filters = vec![
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &discriminator::<Race>())),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(RACE_STATUS_OFFSET, &[status as u8])),
+        ];
+        let accounts = self.rpc.get_program_accounts_with_config(
+            &gor_race_client::ID,
+            RpcProgramAccountsConfig {
+                filters: Some(filters),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )?;
+
+        Ok(accounts
+            .into_iter()
+            .filter_map(|(key, account)| match decode::<Race>(&account.data) {
+                Ok(race) => Some((key, race)),
+                Err(err) => {
+                    warn!("skipping undecodable race {key}: {err}");
+                    None
+                }
+            })
+            .collect())
+    }
+
+    fn token_race(&mut self, mint: Pubkey) -> Result<TokenRace> {
+        let token_program = match self.token_programs.get(&mint) {
+            Some(token_program) => *token_program,
+            None => {
+                let token_program = self.rpc.get_account(&mint).with_context(|| format!("fetching mint {mint}"))?.owner;
+                self.token_programs.insert(mint, token_program);
+                token_program
+            }
+        };
+        Ok(TokenRace { mint, token_program })
+    }
+
+    /// Seed saved by create-race in `races/race_<id>.json`, shared with the CLI and scripts,
+    /// or in `races/pending_<commitment>.json` when create-race never saw its confirmation
+    fn load_seed(&self, race: &Race) -> Option<[u8; 32]> {
+        let contents = fs::read_to_string(self.races_dir.join(format!("race_{}.json", race.race_id)))
+            .or_else(|_| {
+                fs::read_to_string(self.races_dir.join(format!("pending_{}.json", hex::encode(race.seed_commitment))))
+            })
+            .ok()?;
+        let info: serde_json::Value = serde_json::from_str(&contents).ok()?;
+        hex::decode(info["seed"].as_str()?).ok()?.try_into().ok()
+    }
+}
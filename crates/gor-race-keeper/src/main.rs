@@ -0,0 +1,90 @@
+//! `gor-race-keeper`, a long-running crank for the gor-race program.
+//!
+//! Races only move forward when someone calls `execute_race`: once to start
+//! them after the wait time and once more to reveal the seed after the race
+//! duration. The keeper polls race accounts, sends `execute_race` as soon as
+//! each race is due and serves its counters for Prometheus.
+
+mod keeper;
+mod metrics;
+mod schedule;
+
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use log::{debug, error, info};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{read_keypair_file, Signer};
+
+use keeper::Keeper;
+use metrics::Metrics;
+
+#[derive(Parser)]
+#[clap(name = "gor-race-keeper", version, about = "Execute gor-race races as soon as they are due")]
+struct Args {
+    /// RPC endpoint
+    #[clap(long, short = 'u', env = "ANCHOR_PROVIDER_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// Keypair that signs and pays for execute_race, the platform operator to finish every race
+    #[clap(long, short = 'k', env = "ANCHOR_WALLET", default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// Where create-race saved race seeds, needed to finish races
+    #[clap(long, default_value = "races")]
+    races_dir: PathBuf,
+
+    /// Longest wait between polls, in seconds
+    #[clap(long, default_value = "5")]
+    poll_interval: u64,
+
+    /// Times a failed execute_race is resent before waiting for the next poll
+    #[clap(long, default_value = "3")]
+    max_retries: u32,
+
+    /// Address the Prometheus metrics are served on
+    #[clap(long, default_value = "127.0.0.1:9464")]
+    metrics_addr: String,
+}
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let args = Args::parse();
+
+    let keypair_path = match args.keypair.strip_prefix("~/") {
+        Some(rest) => PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(rest),
+        None => PathBuf::from(&args.keypair),
+    };
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|err| anyhow!("reading keypair {}: {err}", keypair_path.display()))?;
+    info!("keeper {} polling {}", payer.pubkey(), args.url);
+
+    let metrics = Arc::new(Metrics::default());
+    metrics::serve(&args.metrics_addr, metrics.clone())?;
+
+    let rpc = RpcClient::new_with_commitment(args.url, CommitmentConfig::confirmed());
+    let mut keeper = Keeper::new(rpc, payer, args.races_dir, args.max_retries, metrics.clone());
+    let poll_interval = Duration::from_secs(args.poll_interval);
+
+    loop {
+        let wait = match keeper.poll() {
+            Ok(Some(seconds)) => {
+                debug!("next race due in {seconds}s");
+                poll_interval.min(Duration::from_secs(seconds.max(1) as u64))
+            }
+            Ok(None) => poll_interval,
+            Err(err) => {
+                error!("poll failed: {err:#}");
+                metrics.poll_failures.fetch_add(1, Ordering::Relaxed);
+                poll_interval
+            }
+        };
+        thread::sleep(wait);
+    }
+}
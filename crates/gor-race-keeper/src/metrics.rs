@@ -0,0 +1,91 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use anyhow::Result;
+use log::{info, warn};
+
+use crate::schedule::Action;
+
+/// Keeper counters, served in the Prometheus text format
+#[derive(Default)]
+pub struct Metrics {
+    pub polls: AtomicU64,
+    pub poll_failures: AtomicU64,
+    pub races_pending: AtomicU64,
+    pub races_racing: AtomicU64,
+    pub started: AtomicU64,
+    pub cancelled_underfilled: AtomicU64,
+    pub finished: AtomicU64,
+    pub cancelled_unrevealed: AtomicU64,
+    pub execute_failures: AtomicU64,
+    pub retries: AtomicU64,
+    pub last_poll_time: AtomicI64,
+}
+
+impl Metrics {
+    pub fn record_execution(&self, action: Action) {
+        let counter = match action {
+            Action::Start => &self.started,
+            Action::CancelUnderfilled => &self.cancelled_underfilled,
+            Action::Finish => &self.finished,
+            Action::CancelUnrevealed => &self.cancelled_unrevealed,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn render(&self) -> String {
+        let get = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(&str, u64)]| {
+            out.push_str(&format!("# HELP gor_race_keeper_{name} {help}\n# TYPE gor_race_keeper_{name} {kind}\n"));
+            for (labels, value) in samples {
+                out.push_str(&format!("gor_race_keeper_{name}{labels} {value}\n"));
+            }
+        };
+
+        metric("polls_total", "counter", "Race discovery rounds", &[("", get(&self.polls))]);
+        metric("poll_failures_total", "counter", "Discovery rounds that failed to reach the RPC node", &[("", get(&self.poll_failures))]);
+        metric("races", "gauge", "Races waiting on the keeper by status", &[
+            ("{status=\"pending\"}", get(&self.races_pending)),
+            ("{status=\"racing\"}", get(&self.races_racing)),
+        ]);
+        metric("executions_total", "counter", "Confirmed execute_race transactions by outcome", &[
+            ("{action=\"start\"}", get(&self.started)),
+            ("{action=\"cancel_underfilled\"}", get(&self.cancelled_underfilled)),
+            ("{action=\"finish\"}", get(&self.finished)),
+            ("{action=\"cancel_unrevealed\"}", get(&self.cancelled_unrevealed)),
+        ]);
+        metric("execute_failures_total", "counter", "execute_race calls that failed after every retry", &[("", get(&self.execute_failures))]);
+        metric("retries_total", "counter", "execute_race attempts that were retried", &[("", get(&self.retries))]);
+        metric("last_poll_timestamp_seconds", "gauge", "Cluster time of the last successful poll", &[
+            ("", self.last_poll_time.load(Ordering::Relaxed).max(0) as u64),
+        ]);
+        out
+    }
+}
+
+/// Answer every HTTP request on `addr` with the current metrics
+pub fn serve(addr: &str, metrics: Arc<Metrics>) -> Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    info!("serving metrics on http://{addr}/metrics");
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            // The request itself does not matter, every path returns the metrics
+            let _ = stream.read(&mut [0u8; 1024]);
+            let body = metrics.render();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            if let Err(err) = stream.write_all(response.as_bytes()) {
+                warn!("writing metrics response: {err}");
+            }
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use gor_race_client::constants::{RACE_DURATION, REVEAL_TIMEOUT};
+use gor_race_client::program::utils::find_slot_hash;
+use gor_race_client::state::{Race, RaceStatus};
+
+/// What execute_race will do to a race if it is sent now
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Wait time is over or the race filled up, with enough players the simulation starts
+    Start,
+    /// Wait time is over without enough players, entries get refunds
+    CancelUnderfilled,
+    /// Simulation is over and the keeper holds the seed, the results are revealed
+    Finish,
+    /// Nobody revealed the seed in time, entries get refunds
+    CancelUnrevealed,
+}
+
+impl Action {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::Start => "start",
+            Action::CancelUnderfilled => "cancel_underfilled",
+            Action::Finish => "finish",
+            Action::CancelUnrevealed => "cancel_unrevealed",
+        }
+    }
+}
+
+/// Mirrors the checks execute_race makes on chain, so the keeper only sends
+/// transactions that will succeed. `can_reveal` is whether the keeper holds
+/// the seed and may reveal it, as the creator or platform operator.
+pub fn due_action(race: &Race, now: i64, can_reveal: bool) -> Option<Action> {
+    match race.status {
+        RaceStatus::Pending if race.can_start_race(now) => Some(Action::Start),
+        RaceStatus::Pending if race.is_expired(now) => Some(Action::CancelUnderfilled),
+        RaceStatus::Racing if race.is_reveal_expired(now) && !can_reveal => Some(Action::CancelUnrevealed),
+        RaceStatus::Racing if race.is_race_finished(now) && can_reveal => Some(Action::Finish),
+        _ => None,
+    }
+}
+
+/// Whether a reveal of the held seed can still succeed. The finish mixes in the
+/// hash of the race's start slot, which drops out of the SlotHashes sysvar after
+/// 512 slots, so a keeper that was down too long can only cancel the race.
+pub fn can_reveal(holds_seed: bool, race: &Race, slot_hashes: &[u8]) -> bool {
+    holds_seed && find_slot_hash(slot_hashes, race.seed_slot).is_some()
+}
+
+/// When the race next needs executing, for scheduling the next poll
+pub fn due_at(race: &Race, can_reveal: bool) -> Option<i64> {
+    match (race.status, race.race_start_time) {
+        (RaceStatus::Pending, _) => Some(race.start_time + race.wait_time),
+        (RaceStatus::Racing, Some(started)) if can_reveal => Some(started + RACE_DURATION),
+        (RaceStatus::Racing, Some(started)) => Some(started + RACE_DURATION + REVEAL_TIMEOUT),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gor_race_client::constants::*;
+    use gor_race_client::state::{HorseAttributes, RaceMode};
+    use solana_sdk::pubkey::Pubkey;
+
+    fn race(status: RaceStatus, entry_count: u32, race_start_time: Option<i64>) -> Race {
+        Race {
+            race_id: 1,
+            creator: Pubkey::new_unique(),
+            status,
+            mode: RaceMode::Fixed,
+            mint: None,
+            horse_names: Default::default(),
+            horse_attributes: [HorseAttributes::default(); MAX_HORSES],
+            total_pool: 0,
+            horse_pools: [0; MAX_HORSES],
+            horse_entry_counts: [0; MAX_HORSES],
+            show_pools: [0; MAX_HORSES],
+            bet_pools: [0; BET_KINDS],
+            platform_fee: 0,
+            fees_collected: false,
+            referred_stake: 0,
+            referral_rewards: 0,
+            referral_rewards_paid: 0,
+            crank_rewards: 0,
+            entry_count,
+            closed_entries: 0,
+            open_combination_pools: 0,
+            max_players: 4,
+            min_players: MIN_PLAYERS_TO_START,
+            entry_fee: ENTRY_FEE,
+            prize_distribution: PRIZE_DISTRIBUTION,
+            wait_time: 60,
+            referral_code: String::new(),
+            start_time: 1_000,
+            race_start_time,
+            end_time: None,
+            winning_horses: [0; 3],
+            finishing_order: [0; MAX_HORSES],
+            checkpoints: [[0; MAX_HORSES]; RACE_CHECKPOINTS],
+            seed_commitment: [0; 32],
+            seed_slot: 0,
+            revealed_seed: None,
+            race_seed: [0; 32],
+            creator_bond: 0,
+            bond_forfeited: false,
+            bump: 0,
+        }
+    }
+
+    /// Raw SlotHashes sysvar data holding `slots`, newest first
+    fn slot_hashes(slots: &[u64]) -> Vec<u8> {
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+        for slot in slots {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[*slot as u8; 32]);
+        }
+        data
+    }
+
+    #[test]
+    fn test_can_reveal() {
+        let racing = Race { seed_slot: 100, ..race(RaceStatus::Racing, 2, Some(1_060)) };
+        let slot_hashes = slot_hashes(&[101, 100, 99]);
+        assert!(can_reveal(true, &racing, &slot_hashes));
+        assert!(!can_reveal(false, &racing, &slot_hashes));
+
+        let expired = Race { seed_slot: 98, ..racing };
+        assert!(!can_reveal(true, &expired, &slot_hashes));
+    }
+
+    #[test]
+    fn test_due_action() {
+        let pending = race(RaceStatus::Pending, 2, None);
+        assert_eq!(due_action(&pending, 1_059, true), None);
+        assert_eq!(due_action(&pending, 1_060, true), Some(Action::Start));
+        assert_eq!(due_at(&pending, true), Some(1_060));
+
+        let empty = race(RaceStatus::Pending, 0, None);
+        assert_eq!(due_action(&empty, 1_060, true), Some(Action::CancelUnderfilled));
+
+        // A full race starts without waiting, one short of the creator's minimum is cancelled
+        let full = race(RaceStatus::Pending, 4, None);
+        assert_eq!(due_action(&full, 1_001, true), Some(Action::Start));
+        let underfilled = Race { min_players: 3, ..race(RaceStatus::Pending, 2, None) };
+        assert_eq!(due_action(&underfilled, 1_060, true), Some(Action::CancelUnderfilled));
+
+        let racing = race(RaceStatus::Racing, 2, Some(1_060));
+        let finished = 1_060 + RACE_DURATION;
+        let reveal_expired = finished + REVEAL_TIMEOUT;
+        assert_eq!(due_action(&racing, finished - 1, true), None);
+        assert_eq!(due_action(&racing, finished, true), Some(Action::Finish));
+        // Without the seed the keeper can only cancel once the reveal window closes
+        assert_eq!(due_action(&racing, finished, false), None);
+        assert_eq!(due_action(&racing, reveal_expired, false), Some(Action::CancelUnrevealed));
+        assert_eq!(due_at(&racing, false), Some(reveal_expired));
+
+        // A seed held past the slot hash window cannot finish, it waits for the cancel
+        let slot_hashes = slot_hashes(&[racing.seed_slot + 1]);
+        assert!(!can_reveal(true, &racing, &slot_hashes));
+        assert_eq!(due_action(&racing, finished, can_reveal(true, &racing, &slot_hashes)), None);
+        assert_eq!(
+            due_action(&racing, reveal_expired, can_reveal(true, &racing, &slot_hashes)),
+            Some(Action::CancelUnrevealed)
+        );
+
+        let completed = race(RaceStatus::Completed, 2, Some(1_060));
+        assert_eq!(due_action(&completed, reveal_expired, true), None);
+    }
+}
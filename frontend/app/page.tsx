@@ -155,7 +155,7 @@ useEffect(() => {
         status: 'Pending' as const,
         startTime: Math.floor(Date.now() / 1000),
         waitTime: waitTime,
-        entryCount: 1, // Creator is the first entry
+        entryCount: 0, // The creator never bets on their own race
         totalPool: 0, // Will be updated as players join
         winningHorses: [],
         horseNames: []
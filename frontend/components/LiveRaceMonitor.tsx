@@ -40,10 +40,10 @@ export const LiveRaceMonitor: React.FC<LiveRaceMonitorProps> = ({
         setPlayerEntry(entryData);
       }
 
-      // Determine race phase
+      // Determine race phase, the decoded status is keyed by its camelCase variant
       if (raceData) {
         const status = Object.keys(raceData.status)[0];
-        setRacePhase(status as any);
+        setRacePhase(status === 'pending' ? 'waiting' : status as any);
       }
     } catch (error) {
       console.error('Failed to load race data:', error);
@@ -69,7 +69,7 @@ export const LiveRaceMonitor: React.FC<LiveRaceMonitorProps> = ({
 
     const updateTimer = () => {
       const now = Math.floor(Date.now() / 1000);
-      const raceStartTime = race.createdAt + race.waitTime;
+      const raceStartTime = race.startTime + race.waitTime;
       const remaining = Math.max(0, raceStartTime - now);
       setTimeLeft(remaining);
 
@@ -210,6 +210,7 @@ export const LiveRaceMonitor: React.FC<LiveRaceMonitorProps> = ({
           raceData={race}
           isRacing={racePhase === 'racing'}
           winningHorses={racePhase === 'completed' ? race.winningHorses : []}
+          checkpoints={racePhase === 'completed' ? race.checkpoints : []}
         />
       </CowboyCard>
 
@@ -8,12 +8,14 @@ interface RaceTrackProps {
   raceData?: any;
   isRacing?: boolean;
   winningHorses?: number[];
+  checkpoints?: number[][]; // On-chain running order at each checkpoint, replayed once the race is settled
 }
 
 export const RaceTrack: React.FC<RaceTrackProps> = ({
   raceData,
   isRacing = false,
   winningHorses = [],
+  checkpoints = [],
 }) => {
   const [raceProgress, setRaceProgress] = useState<number[]>(new Array(10).fill(0));
   const [isFinished, setIsFinished] = useState(false);
@@ -34,6 +36,33 @@ export const RaceTrack: React.FC<RaceTrackProps> = ({
     }
   }, [isRacing, isFinished]);
 
+  // Replay the race from its checkpoints: every leg moves the field forward
+  // and spreads horses out by their running order at that checkpoint
+  useEffect(() => {
+    if (checkpoints.length === 0) return;
+
+    let leg = 0;
+    const interval = setInterval(() => {
+      const order = checkpoints[leg];
+      const legProgress = ((leg + 1) / checkpoints.length) * 100;
+
+      setRaceProgress(prev =>
+        prev.map((_, index) => {
+          const place = order.indexOf(index + 1);
+          return Math.max(0, legProgress - place * 2);
+        })
+      );
+
+      leg += 1;
+      if (leg === checkpoints.length) {
+        clearInterval(interval);
+      }
+    }, 600);
+
+    return () => clearInterval(interval);
+    // Race data is refetched on every poll, only a new running order restarts the replay
+  }, [JSON.stringify(checkpoints)]);
+
   useEffect(() => {
     if (raceProgress.some(p => p >= 100)) {
       setIsFinished(true);
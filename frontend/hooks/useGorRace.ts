@@ -23,9 +23,12 @@ export interface Race {
   referralCode: string;
   entryCount: number;
   totalPool: number;
+  startTime: number;
   waitTime: number;
   status: any;
   winningHorses: number[];
+  finishingOrder: number[];
+  checkpoints: number[][];
   raceStartTime?: number;
   endTime?: number;
   platformFee: number;
@@ -244,6 +247,8 @@ export const useGorRace = () => {
         toast.error('This race has already started!');
       } else if (error.message?.includes('AlreadyJoined')) {
         toast.error('You already joined this race!');
+      } else if (error.message?.includes('RevealerCannotBet')) {
+        toast.error('You run this race, partner, so you cannot bet on it!');
       } else {
         toast.error('Failed to join race. The horse got cold feet!');
       }
@@ -337,9 +342,12 @@ export const useGorRace = () => {
           referralCode: race.referralCode || '',
           entryCount: (race.entryCount as any)?.toNumber ? (race.entryCount as any).toNumber() : Number(race.entryCount) || 0,
           totalPool: (race.totalPool as any)?.toNumber ? (race.totalPool as any).toNumber() : Number(race.totalPool) || 0,
+          startTime: (race.startTime as any)?.toNumber ? (race.startTime as any).toNumber() : Number(race.startTime) || 0,
           waitTime: (race.waitTime as any)?.toNumber ? (race.waitTime as any).toNumber() : Number(race.waitTime) || 0,
           status: race.status || null,
           winningHorses: Array.isArray(race.winningHorses) ? race.winningHorses.map((h: any) => (h as any)?.toNumber ? (h as any).toNumber() : Number(h) || 0) : [],
+          finishingOrder: Array.isArray(race.finishingOrder) ? race.finishingOrder.map((h: any) => Number(h) || 0) : [],
+          checkpoints: Array.isArray(race.checkpoints) ? race.checkpoints.map((order: any) => Array.from(order as number[], (h) => Number(h) || 0)) : [],
           raceStartTime: race.raceStartTime ? ((race.raceStartTime as any)?.toNumber ? (race.raceStartTime as any).toNumber() : Number(race.raceStartTime)) : undefined,
           endTime: race.endTime ? ((race.endTime as any)?.toNumber ? (race.endTime as any).toNumber() : Number(race.endTime)) : undefined,
           platformFee: (race.platformFee as any)?.toNumber ? (race.platformFee as any).toNumber() : Number(race.platformFee) || 0,
@@ -366,7 +374,8 @@ export const useGorRace = () => {
         // Convert BN fields to numbers for the interface
         const convertedEntry: PlayerEntry = {
           player: entry.player || playerKey,
-          horseNumber: (entry.horseNumber as any)?.toNumber ? (entry.horseNumber as any).toNumber() : Number(entry.horseNumber) || 0,
+          // The frontend places a single win bet, its selection is the player's horse
+          horseNumber: entry.bets[0]?.selection[0] || 0,
           statsUpdated: entry.statsUpdated || false,
           claimStatus: entry.claimStatus || null,
           prizeAmount: (entry.prizeAmount as any)?.toNumber ? (entry.prizeAmount as any).toNumber() : Number(entry.prizeAmount) || 0,
@@ -7,6 +7,7 @@ import { AnchorProvider, Program } from '@coral-xyz/anchor';
 import * as anchor from '@coral-xyz/anchor';
 import { GorRace } from '@/types/gor_race';
 import { PROGRAM_ID } from '@/lib/constants';
+import { GorRaceProgram } from '@/lib/program';
 import idl from '@/types/gor_race.json';
 import toast from 'react-hot-toast';
 
@@ -140,28 +141,9 @@ export const useProfile = () => {
 
     setLoading(true);
     try {
-      // Generate a unique race ID using timestamp + random component
-      const raceId = Math.floor(Date.now() / 1000) + Math.floor(Math.random() * 10000);
-      
-      // Calculate PDA for the race
-      const [racePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("race"), new anchor.BN(raceId).toArrayLike(Buffer, "le", 8)],
-        PROGRAM_ID
-      );
-      
-      console.log('🏁 Creating race with ID:', raceId);
-      console.log('📍 Race PDA:', racePda.toString());
-      
-      // Create race using the program
-      await program.methods
-        .createRace(new anchor.BN(raceId), new anchor.BN(waitTime))
-        .accounts({
-          race: racePda,
-          creator: publicKey,
-          systemProgram: anchor.web3.SystemProgram.programId,
-          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-        })
-        .rpc();
+      // Shares the race id retries and seed handling of the program client
+      const { raceId } = await new GorRaceProgram(anchorWallet, connection, publicKey).createRace(waitTime);
+      console.log('🏁 Created race with ID:', raceId);
       
       // Generate referral code (8-character alphanumeric based on race ID)
       const referralCode = generateReferralCode(raceId);
@@ -191,12 +173,17 @@ export const useProfile = () => {
         [Buffer.from("player_profile"), publicKey.toBuffer()],
         PROGRAM_ID
       );
+      const [usernamePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("username"), Buffer.from(username.toLowerCase())],
+        PROGRAM_ID
+      );
 
       // Create profile exactly like the CLI does
       await program.methods
         .createProfile(username)
         .accounts({
           playerProfile: playerProfilePda,
+          usernameAccount: usernamePda,
           player: publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
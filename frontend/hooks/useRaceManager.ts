@@ -3,7 +3,8 @@
 import { useState, useEffect, useCallback, useRef } from 'react';
 import { useProfile } from './useProfile';
 import { PublicKey } from '@solana/web3.js';
-import { PROGRAM_ID } from '@/lib/constants';
+import { PROGRAM_ID, RACE_DURATION, REVEAL_TIMEOUT, REVEAL_POLL_INTERVAL } from '@/lib/constants';
+import { loadRaceSeed } from '@/lib/raceSeed';
 import * as anchor from '@coral-xyz/anchor';
 import toast from 'react-hot-toast';
 import confetti from 'canvas-confetti';
@@ -30,6 +31,14 @@ export interface PlayerEntry {
   claimStatus: 'Unclaimed' | 'Claimed';
 }
 
+// Anchor decodes enums as `{ racing: {} }`, RaceInfo spells statuses like the program
+const capitalize = (name: string) => name.charAt(0).toUpperCase() + name.slice(1);
+
+// Offsets of `Race::creator` and `Race::status`, after the discriminator and race_id
+const RACE_CREATOR_OFFSET = 8 + 8;
+const RACE_STATUS_OFFSET = RACE_CREATOR_OFFSET + 32;
+const RACE_STATUS_RACING = 1;
+
 export const useRaceManager = () => {
   const { program, publicKey, connected } = useProfile();
   const [currentRace, setCurrentRace] = useState<RaceInfo | null>(null);
@@ -42,6 +51,10 @@ export const useRaceManager = () => {
   const executeRaceRef = useRef<((referralCode: string) => Promise<void>) | null>(null);
   const checkWinnerAndCelebrateRef = useRef<(() => void) | null>(null);
   const updateStatsRef = useRef<((referralCode: string) => Promise<void>) | null>(null);
+  const loadRaceRef = useRef<((referralCode: string) => Promise<RaceInfo | null>) | null>(null);
+  const loadPlayerEntryRef = useRef<(() => Promise<void>) | null>(null);
+  // Races with a reveal in flight, so overlapping polls never send it twice
+  const revealingRef = useRef<Set<string>>(new Set());
 
   // Timer effect for countdown - IMPROVED
   useEffect(() => {
@@ -106,7 +119,7 @@ export const useRaceManager = () => {
         raceId: raceData.raceId?.toNumber() || 0,
         referralCode: raceData.referralCode,
         creator: raceData.creator,
-        status: Object.keys(raceData.status)[0] as any,
+        status: capitalize(Object.keys(raceData.status)[0]) as RaceInfo['status'],
         startTime: raceData.startTime?.toNumber() || 0,
         waitTime: raceData.waitTime?.toNumber() || 0,
         raceStartTime: raceData.raceStartTime?.toNumber(),
@@ -188,7 +201,8 @@ export const useRaceManager = () => {
       
       const entry: PlayerEntry = {
         player: entryData.player,
-        horseNumber: entryData.horseNumber,
+        // The frontend places a single win bet, its selection is the player's horse
+        horseNumber: entryData.bets[0]?.selection[0] || 0,
         prizeAmount: entryData.prizeAmount?.toNumber() || 0,
         claimStatus: Object.keys(entryData.claimStatus)[0] as any,
       };
@@ -202,7 +216,7 @@ export const useRaceManager = () => {
 
   // Execute race when timer ends
   const executeRace = useCallback(async (referralCode: string) => {
-    if (!program || !currentRace) return;
+    if (!program || !publicKey || !currentRace) return;
 
     setLoading(true);
     try {
@@ -221,44 +235,146 @@ export const useRaceManager = () => {
         PROGRAM_ID
       );
 
+      const [creatorRewardsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reward_account"), currentRace.creator.toBuffer()],
+        PROGRAM_ID
+      );
+
+      // Starting the race needs no seed, the creator's saved seed is revealed later to finish it
       await program.methods
-        .executeRace()
+        .executeRace(null)
         .accounts({
           race: racePda,
           raceVault: raceVaultPda,
           platformVault: platformVaultPda,
-          recentBlockhashes: anchor.web3.SYSVAR_RECENT_BLOCKHASHES_PUBKEY,
+          creatorRewards: creatorRewardsPda,
+          executor: publicKey,
+          slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          mint: null,
+          raceVaultTokenAccount: null,
+          platformTokenAccount: null,
+          executorTokenAccount: null,
+          tokenProgram: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc();
 
       toast.success('🏁 Race started! The horses are running!');
       setRacePhase('racing');
-
-      // Simulate 60-second race duration
-      setTimeout(async () => {
-        console.log('🏆 Race completed - loading results');
-        await loadRaceByReferralCode(referralCode);
-        await loadPlayerEntry();
-        if (checkWinnerAndCelebrateRef.current) {
-          checkWinnerAndCelebrateRef.current();
-        }
-        if (updateStatsRef.current) {
-          await updateStatsRef.current(referralCode);
-        }
-      }, 60000);
-
     } catch (error: any) {
       console.error('Failed to execute race:', error);
-      toast.error('Failed to start race');
+      // A keeper or another player may have started it first
+      const race = await loadRaceByReferralCode(referralCode);
+      if (race?.status === 'Pending') {
+        toast.error('Failed to start race');
+      }
     } finally {
       setLoading(false);
     }
-  }, [program, currentRace, loadRaceByReferralCode, loadPlayerEntry]);
+  }, [program, publicKey, currentRace, loadRaceByReferralCode]);
 
   // Assign functions to refs to avoid circular dependencies
   executeRaceRef.current = executeRace;
+  loadRaceRef.current = loadRaceByReferralCode;
+  loadPlayerEntryRef.current = loadPlayerEntry;
+
+  // Only the creator's browser holds its seeds, so it reveals every race of its own
+  // that has finished racing, whoever started it, and retries until the reveal deadline
+  useEffect(() => {
+    if (!program || !publicKey) return;
+
+    const revealFinishedRaces = async () => {
+      try {
+        const racing = await program.account.race.all([
+          { memcmp: { offset: RACE_CREATOR_OFFSET, bytes: publicKey.toBase58() } },
+          { memcmp: { offset: RACE_STATUS_OFFSET, bytes: anchor.utils.bytes.bs58.encode([RACE_STATUS_RACING]) } },
+        ]);
+        if (racing.length === 0) return;
+
+        // execute_race checks the cluster clock, which can lag the browser's
+        const connection = program.provider.connection;
+        const now = (await connection.getBlockTime(await connection.getSlot())) ?? Math.floor(Date.now() / 1000);
+
+        for (const { publicKey: racePda, account: race } of racing) {
+          const key = racePda.toBase58();
+          const finishedAt = race.raceStartTime.toNumber() + RACE_DURATION;
+          if (now < finishedAt || now >= finishedAt + REVEAL_TIMEOUT || revealingRef.current.has(key)) continue;
+
+          const seed = loadRaceSeed(race.seedCommitment);
+          if (!seed) continue;
+
+          revealingRef.current.add(key);
+          try {
+            const [raceVaultPda] = PublicKey.findProgramAddressSync(
+              [Buffer.from("race_vault"), racePda.toBuffer()],
+              PROGRAM_ID
+            );
+            const [platformVaultPda] = PublicKey.findProgramAddressSync(
+              [Buffer.from("platform_vault")],
+              PROGRAM_ID
+            );
+            const [creatorRewardsPda] = PublicKey.findProgramAddressSync(
+              [Buffer.from("reward_account"), publicKey.toBuffer()],
+              PROGRAM_ID
+            );
+
+            await program.methods
+              .executeRace(seed)
+              .accounts({
+                race: racePda,
+                raceVault: raceVaultPda,
+                platformVault: platformVaultPda,
+                creatorRewards: creatorRewardsPda,
+                executor: publicKey,
+                slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+                clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+                mint: null,
+                raceVaultTokenAccount: null,
+                platformTokenAccount: null,
+                executorTokenAccount: null,
+                tokenProgram: null,
+                systemProgram: anchor.web3.SystemProgram.programId,
+              })
+              .rpc();
+            console.log('🔓 Revealed the seed of race', race.raceId.toString());
+          } catch (error) {
+            // Still racing on the cluster, or the transaction was dropped, the next poll retries
+            console.warn('Failed to reveal race seed, retrying:', error);
+          } finally {
+            revealingRef.current.delete(key);
+          }
+        }
+      } catch (error) {
+        console.error('Failed to look up races to reveal:', error);
+      }
+    };
+
+    revealFinishedRaces();
+    const interval = setInterval(revealFinishedRaces, REVEAL_POLL_INTERVAL);
+    return () => clearInterval(interval);
+  }, [program, publicKey]);
+
+  // Follow the current race until it completes or is cancelled, whoever reveals it
+  useEffect(() => {
+    if (!currentRace || racePhase !== 'racing') return;
+
+    const referralCode = currentRace.referralCode;
+    const interval = setInterval(async () => {
+      const race = await loadRaceRef.current?.(referralCode);
+      if (!race || race.status === 'Racing') return;
+
+      clearInterval(interval);
+      console.log('🏆 Race settled - loading results');
+      await loadPlayerEntryRef.current?.();
+      if (race.status === 'Completed') {
+        checkWinnerAndCelebrateRef.current?.();
+        await updateStatsRef.current?.(referralCode);
+      }
+    }, REVEAL_POLL_INTERVAL);
+
+    return () => clearInterval(interval);
+  }, [currentRace?.referralCode, racePhase]);
 
   // Check if player won and trigger celebration
   const checkWinnerAndCelebrate = useCallback(() => {
@@ -361,14 +477,25 @@ export const useRaceManager = () => {
         PROGRAM_ID
       );
 
+      const [playerProfilePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("player_profile"), publicKey.toBuffer()],
+        PROGRAM_ID
+      );
+      const hasProfile = (await program.provider.connection.getAccountInfo(playerProfilePda)) !== null;
+
       await program.methods
         .claimPrize()
         .accounts({
           race: racePda,
           playerEntry: playerEntryPda,
           player: publicKey,
+          playerProfile: hasProfile ? playerProfilePda : null,
           raceVault: raceVaultPda,
           platformVault: platformVaultPda,
+          mint: null,
+          playerTokenAccount: null,
+          raceVaultTokenAccount: null,
+          tokenProgram: null,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc();
@@ -8,6 +8,8 @@ export const RPC_ENDPOINT = "https://rpc.gorbagana.wtf";
 export const ENTRY_FEE = 100_000_000; // 0.1 GOR in lamports
 export const NUM_HORSES = 10;
 export const RACE_DURATION = 60; // seconds
+export const REVEAL_TIMEOUT = 90; // seconds after the race finishes before anyone can cancel it
+export const REVEAL_POLL_INTERVAL = 5000; // ms between checks for finished races to reveal
 
 // Horse names with cowboy theme
 export const HORSE_NAMES = [
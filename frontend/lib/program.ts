@@ -4,6 +4,7 @@ import { AnchorWallet } from '@solana/wallet-adapter-react';
 import { Connection, PublicKey } from '@solana/web3.js';
 import { GorRace } from '../types/gor_race';
 import { PROGRAM_ID, RPC_ENDPOINT } from './constants';
+import { generateRaceSeed, loadRaceSeed, saveRaceSeed } from './raceSeed';
 import idl from '../types/gor_race.json';
 
 export class GorRaceProgram {
@@ -60,6 +61,13 @@ export class GorRaceProgram {
     );
   }
 
+  getRewardAccountPda(beneficiary: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_account"), beneficiary.toBuffer()],
+      PROGRAM_ID
+    );
+  }
+
   // Core program methods
   async createProfile(username: string): Promise<string> {
     if (!this.publicKey) {
@@ -70,11 +78,16 @@ export class GorRaceProgram {
     console.log('Creating profile for player:', this.publicKey.toString());
     const [playerProfilePda] = this.getPlayerProfilePda(this.publicKey);
     console.log('Player profile PDA:', playerProfilePda.toString());
+    const [usernamePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("username"), Buffer.from(username.toLowerCase())],
+      PROGRAM_ID
+    );
 
     const tx = await this.program.methods
       .createProfile(username)
       .accounts({
         playerProfile: playerProfilePda,
+        usernameAccount: usernamePda,
         player: this.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
@@ -85,34 +98,79 @@ export class GorRaceProgram {
   }
 
   async createRace(waitTime: number): Promise<{ raceId: number; txSignature: string }> {
-    const raceId = Math.floor(Date.now() / 1000) + Math.floor(Math.random() * 10000);
-    const [racePda] = this.getRacePda(raceId);
+    const [platformVaultPda] = this.getPlatformVaultPda();
+    const [creatorRewardsPda] = this.getRewardAccountPda(this.publicKey);
 
-    const tx = await this.program.methods
-      .createRace(new anchor.BN(raceId), new anchor.BN(waitTime))
-      .accounts({
-        race: racePda,
-        creator: this.publicKey,
-        systemProgram: anchor.web3.SystemProgram.programId,
-        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-      })
-      .rpc();
+    const { seed, commitment } = await generateRaceSeed();
+    saveRaceSeed(commitment, seed);
+
+    // Races are numbered by the platform counter, another creator may take the id
+    // between reading and sending it, then the next one is tried
+    for (let retries = 0; ; retries++) {
+      const platformVault = await this.program.account.platformVault.fetch(platformVaultPda);
+      const raceId = platformVault.nextRaceId.toNumber();
+      const [racePda] = this.getRacePda(raceId);
+      const [raceVaultPda] = this.getRaceVaultPda(racePda);
+
+      try {
+        const tx = await this.program.methods
+          .createRace(
+            new anchor.BN(waitTime),
+            commitment,
+            { fixed: {} },
+            { entryFee: null, maxPlayers: null, minPlayers: null, prizeDistribution: null }
+          )
+          .accounts({
+            platformVault: platformVaultPda,
+            race: racePda,
+            creator: this.publicKey,
+            creatorRewards: creatorRewardsPda,
+            raceVault: raceVaultPda,
+            mint: null,
+            raceVaultTokenAccount: null,
+            platformTokenAccount: null,
+            tokenProgram: null,
+            associatedTokenProgram: null,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .rpc();
 
-    return { raceId, txSignature: tx };
+        return { raceId, txSignature: tx };
+      } catch (error: any) {
+        // A confirmation timeout can hide our own race landing, only another creator's race means a retry
+        const race = await this.program.account.race.fetchNullable(racePda);
+        if (race && race.creator.equals(this.publicKey) &&
+            Buffer.from(race.seedCommitment).equals(Buffer.from(commitment))) {
+          return { raceId, txSignature: error.signature ?? '' };
+        }
+        if (!race || retries >= 3) {
+          throw error;
+        }
+      }
+    }
   }
 
   async joinRace(raceId: number, horseNumber: number, referralCode: string): Promise<string> {
     const [racePda] = this.getRacePda(raceId);
     const [raceVaultPda] = this.getRaceVaultPda(racePda);
+    const [platformVaultPda] = this.getPlatformVaultPda();
     const [playerEntryPda] = this.getPlayerEntryPda(racePda, this.publicKey);
 
     const tx = await this.program.methods
-      .joinRace(horseNumber, referralCode)
+      .joinRace({ win: {} }, [horseNumber, 0, 0], referralCode, null, null)
       .accounts({
         race: racePda,
         playerEntry: playerEntryPda,
         player: this.publicKey,
+        combinationPool: null,
+        referrerRewards: null,
         raceVault: raceVaultPda,
+        platformVault: platformVaultPda,
+        mint: null,
+        playerTokenAccount: null,
+        raceVaultTokenAccount: null,
+        tokenProgram: null,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
@@ -120,19 +178,30 @@ export class GorRaceProgram {
     return tx;
   }
 
+  // Starts a pending race, or finishes a racing one by revealing the creator's saved seed
   async executeRace(raceId: number): Promise<string> {
     const [racePda] = this.getRacePda(raceId);
     const [raceVaultPda] = this.getRaceVaultPda(racePda);
     const [platformVaultPda] = this.getPlatformVaultPda();
+    const race = await this.program.account.race.fetch(racePda);
+    const [creatorRewardsPda] = this.getRewardAccountPda(race.creator);
+    const seed = 'racing' in race.status ? loadRaceSeed(race.seedCommitment) : null;
 
     const tx = await this.program.methods
-      .executeRace()
+      .executeRace(seed)
       .accounts({
         race: racePda,
         raceVault: raceVaultPda,
         platformVault: platformVaultPda,
-        recentBlockhashes: anchor.web3.SYSVAR_RECENT_BLOCKHASHES_PUBKEY,
+        creatorRewards: creatorRewardsPda,
+        executor: this.publicKey,
+        slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        mint: null,
+        raceVaultTokenAccount: null,
+        platformTokenAccount: null,
+        executorTokenAccount: null,
+        tokenProgram: null,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
@@ -145,6 +214,8 @@ export class GorRaceProgram {
     const [raceVaultPda] = this.getRaceVaultPda(racePda);
     const [platformVaultPda] = this.getPlatformVaultPda();
     const [playerEntryPda] = this.getPlayerEntryPda(racePda, this.publicKey);
+    const [playerProfilePda] = this.getPlayerProfilePda(this.publicKey);
+    const hasProfile = (await this.provider.connection.getAccountInfo(playerProfilePda)) !== null;
 
     const tx = await this.program.methods
       .claimPrize()
@@ -152,8 +223,13 @@ export class GorRaceProgram {
         race: racePda,
         playerEntry: playerEntryPda,
         player: this.publicKey,
+        playerProfile: hasProfile ? playerProfilePda : null,
         raceVault: raceVaultPda,
         platformVault: platformVaultPda,
+        mint: null,
+        playerTokenAccount: null,
+        raceVaultTokenAccount: null,
+        tokenProgram: null,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
@@ -0,0 +1,23 @@
+// The creator commits to sha256(seed) at create_race and reveals the seed to finish the race.
+// Seeds are kept in localStorage under their commitment, which is known before the race id.
+const SEED_PREFIX = 'gor_race_seed_';
+
+const toHex = (bytes: Uint8Array): string =>
+  Array.from(bytes, (b) => b.toString(16).padStart(2, '0')).join('');
+
+export async function generateRaceSeed(): Promise<{ seed: Uint8Array; commitment: number[] }> {
+  const seed = crypto.getRandomValues(new Uint8Array(32));
+  const digest = await crypto.subtle.digest('SHA-256', seed);
+  return { seed, commitment: Array.from(new Uint8Array(digest)) };
+}
+
+// Must run before create_race is sent, a lost seed means the race can only be cancelled
+export function saveRaceSeed(commitment: number[], seed: Uint8Array) {
+  localStorage.setItem(SEED_PREFIX + toHex(Uint8Array.from(commitment)), toHex(seed));
+}
+
+export function loadRaceSeed(commitment: number[]): number[] | null {
+  const hex = localStorage.getItem(SEED_PREFIX + toHex(Uint8Array.from(commitment)));
+  if (!hex) return null;
+  return hex.match(/../g)!.map((byte) => parseInt(byte, 16));
+}
@@ -31,10 +31,38 @@ export type GorRace = {
         }
       ]
     },
+    {
+      "name": "migratePlatformVault",
+      "docs": [
+        "Rewrite a platform vault left in the first release's layout to the current one,",
+        "keeping its authority, fee and fee ledger (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The handler checks its owner, discriminator and size before rewriting it."
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "createProfile",
       "docs": [
-        "Create a player profile with username"
+        "Create a player profile with a username no other player holds"
       ],
       "accounts": [
         {
@@ -42,6 +70,11 @@ export type GorRace = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "usernameAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "player",
           "isMut": true,
@@ -66,18 +99,34 @@ export type GorRace = {
       ]
     },
     {
-      "name": "createRace",
+      "name": "renameProfile",
       "docs": [
-        "Create a new race that players can join"
+        "Change a profile's username, releasing the old name for others"
       ],
       "accounts": [
         {
-          "name": "race",
+          "name": "playerProfile",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "creator",
+          "name": "oldUsernameAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Profiles created before the registry may have no entry, or share it with a case variant."
+          ]
+        },
+        {
+          "name": "newUsernameAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Same account as the old entry when only the case of the name changes"
+          ]
+        },
+        {
+          "name": "player",
           "isMut": true,
           "isSigner": true
         },
@@ -94,108 +143,162 @@ export type GorRace = {
       ],
       "args": [
         {
-          "name": "raceId",
-          "type": "u64"
-        },
-        {
-          "name": "waitTime",
-          "type": {
-            "option": "i64"
-          }
+          "name": "newUsername",
+          "type": "string"
         }
       ]
     },
     {
-      "name": "joinRace",
+      "name": "claimUsername",
       "docs": [
-        "Join a race by selecting a horse and paying entry fee"
+        "Reserve the username of a profile created before the username registry (permissionless)"
       ],
       "accounts": [
         {
-          "name": "race",
-          "isMut": true,
+          "name": "playerProfile",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "playerEntry",
+          "name": "usernameAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "player",
+          "name": "payer",
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "raceVault",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "horseNumber",
-          "type": "u8"
-        },
-        {
-          "name": "referralCode",
-          "type": "string"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "executeRace",
+      "name": "createRace",
       "docs": [
-        "Execute the race after timeout or when conditions are met"
+        "Create a new race that players can join, numbered by the platform's race counter.",
+        "The creator posts CREATOR_BOND, forfeited to the entries if the seed is never revealed"
       ],
       "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Hands out the race id, two creations in one slot get consecutive ids"
+          ]
+        },
         {
           "name": "race",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "creator",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "creatorRewards",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Accrues the creator's share of the platform fee"
+          ]
+        },
         {
           "name": "raceVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "platformVault",
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Mint entries are paid in, leave empty for a native GOR race"
+          ]
+        },
+        {
+          "name": "raceVaultTokenAccount",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "platformTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
         },
         {
-          "name": "recentBlockhashes",
+          "name": "tokenProgram",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
-          "name": "clock",
+          "name": "associatedTokenProgram",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "waitTime",
+          "type": {
+            "option": "i64"
+          }
+        },
+        {
+          "name": "seedCommitment",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "mode",
+          "type": {
+            "defined": "RaceMode"
+          }
+        },
+        {
+          "name": "config",
+          "type": {
+            "defined": "RaceConfig"
+          }
+        }
+      ]
     },
     {
-      "name": "claimPrize",
+      "name": "joinRace",
       "docs": [
-        "Claim prize after race completion"
+        "Join a race by placing a win, show, exacta, quinella or trifecta bet,",
+        "paying the entry fee or staking any amount above it, optionally naming a referrer"
       ],
       "accounts": [
         {
           "name": "race",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -208,6 +311,18 @@ export type GorRace = {
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "combinationPool",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "referrerRewards",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "raceVault",
           "isMut": true,
@@ -215,90 +330,205 @@ export type GorRace = {
         },
         {
           "name": "platformVault",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "playerTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "raceVaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
         {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
-    },
-    {
-      "name": "updateStats",
-      "docs": [
-        "Update player statistics after race completion"
-      ],
-      "accounts": [
+      "args": [
         {
-          "name": "playerProfile",
-          "isMut": true,
-          "isSigner": false
+          "name": "betKind",
+          "type": {
+            "defined": "BetKind"
+          }
         },
         {
-          "name": "race",
-          "isMut": false,
-          "isSigner": false
+          "name": "selection",
+          "type": {
+            "array": [
+              "u8",
+              3
+            ]
+          }
         },
         {
-          "name": "playerEntry",
-          "isMut": true,
-          "isSigner": false
+          "name": "referralCode",
+          "type": "string"
         },
         {
-          "name": "player",
-          "isMut": false,
-          "isSigner": false
+          "name": "amount",
+          "type": {
+            "option": "u64"
+          }
         },
         {
-          "name": "clock",
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "getRaceAddress",
+      "docs": [
+        "Address of the race with `race_id`, which must already be created (read-only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "raceId",
+          "type": "u64"
+        }
+      ],
+      "returns": "publicKey"
     },
     {
-      "name": "updatePlatformFee",
+      "name": "getOdds",
       "docs": [
-        "Update platform fee (authority only)"
+        "Implied decimal odds for each horse, scaled by ODDS_PRECISION (read-only)"
       ],
       "accounts": [
+        {
+          "name": "race",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "platformVault",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "mint",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Required for token races, the crank reward cap scales to its decimals"
+          ]
         }
       ],
-      "args": [
-        {
-          "name": "newFeeBps",
-          "type": "u16"
-        }
-      ]
+      "args": [],
+      "returns": {
+        "array": [
+          "u64",
+          10
+        ]
+      }
     },
     {
-      "name": "withdrawPlatformFees",
+      "name": "executeRace",
       "docs": [
-        "Withdraw platform fees (authority only)"
+        "Execute the race after timeout or when conditions are met,",
+        "revealing the committed seed to finish it"
       ],
       "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "raceVault",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "platformVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "creatorRewards",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
+        },
+        {
+          "name": "executor",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Anyone may start a race or cancel an unrevealed one; only the race",
+            "creator or the platform operator may reveal the seed"
+          ]
+        },
+        {
+          "name": "slotHashes",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "raceVaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "platformTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "executorTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Receives the crank reward of token races, executors without one waive it"
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
         },
         {
           "name": "systemProgram",
@@ -308,13 +538,3896 @@ export type GorRace = {
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
+          "name": "seed",
+          "type": {
+            "option": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "claimPrize",
+      "docs": [
+        "Claim prize after race completion, counting the race on the player's profile if passed"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "playerEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "playerProfile",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Counts the race on the player's profile as the prize is paid"
+          ]
+        },
+        {
+          "name": "raceVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "platformVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "playerTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "raceVaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimRefund",
+      "docs": [
+        "Claim back the entry amount from a cancelled race, plus a share of the creator bond",
+        "when the seed was never revealed"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "playerEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "raceVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "playerTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "raceVaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimRewards",
+      "docs": [
+        "Claim creator and referrer rewards accrued from platform fees"
+      ],
+      "accounts": [
+        {
+          "name": "rewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "beneficiary",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelRace",
+      "docs": [
+        "Emergency cancel a stuck race so entries can be refunded (authority only). A racing",
+        "race can only be cancelled once its seed can no longer be revealed"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "platformVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "slotHashes",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "settleReferral",
+      "docs": [
+        "Credit an entry's referrer once the race completes, without waiting for the entry",
+        "to close (permissionless)"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "playerEntry",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Anyone may settle, so a winner who never claims cannot hold back the referrer's reward"
+          ]
+        },
+        {
+          "name": "referrerRewards",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closePlayerEntry",
+      "docs": [
+        "Close a claimed, refunded or prize-less entry, returning its rent to the player"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "playerEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Receives the entry rent, anyone may close a settled entry"
+          ]
+        },
+        {
+          "name": "referrerRewards",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Required when the entry named a referrer that settle_referral has not credited"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeCombinationPool",
+      "docs": [
+        "Close an exotic bet pool once every entry is closed, returning its rent to the payer"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "combinationPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Receives the pool rent, anyone may close a pool once the race is settled"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeRace",
+      "docs": [
+        "Close a fully settled race, sweeping vault dust to the platform",
+        "and returning rent to the creator"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "creator",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Receives the race, vault and vault token account rent, anyone may close a settled race"
+          ]
+        },
+        {
+          "name": "raceVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "raceVaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "platformTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateStats",
+      "docs": [
+        "Count a completed race on the player's profile when claim_prize did not"
+      ],
+      "accounts": [
+        {
+          "name": "playerProfile",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "race",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "playerEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "player",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updatePlatformFee",
+      "docs": [
+        "Update platform fee (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "updateRaceLimits",
+      "docs": [
+        "Update the entry fee and player limits races are validated against (authority only).",
+        "Entry fees are in GOR base units, token races scale them to the mint's decimals."
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "minEntryFee",
+          "type": "u64"
+        },
+        {
+          "name": "maxEntryFee",
+          "type": "u64"
+        },
+        {
+          "name": "maxPlayersLimit",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "updateRevenueSplit",
+      "docs": [
+        "Set the creator and referrer shares of the platform fee in basis points (authority only).",
+        "Rewards are paid in GOR, token races can only be created while both are 0."
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "creatorShareBps",
+          "type": "u16"
+        },
+        {
+          "name": "referrerShareBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "updateCrankReward",
+      "docs": [
+        "Set the share of a race pool paid to whoever executes it, capped per call (authority only).",
+        "The cap is in GOR base units, token races scale it to the mint's decimals."
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "crankRewardBps",
+          "type": "u16"
+        },
+        {
+          "name": "maxCrankReward",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateOperator",
+      "docs": [
+        "Set the operator allowed to reveal race seeds (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newOperator",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "setPause",
+      "docs": [
+        "Pause or resume instructions with a bitmask of PAUSE_* flags (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "paused",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "proposeAuthority",
+      "docs": [
+        "Propose a new platform authority, which takes over once it accepts (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newAuthority",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "acceptAuthority",
+      "docs": [
+        "Accept a pending authority transfer (pending authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelAuthorityTransfer",
+      "docs": [
+        "Drop a pending authority transfer (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "reconcileFees",
+      "docs": [
+        "Compare recorded native platform fees with the platform vault balance (read-only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "FeeReconciliation"
+      }
+    },
+    {
+      "name": "withdrawPlatformFees",
+      "docs": [
+        "Withdraw platform fees (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Receives native fees, leave empty to pay the authority"
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Mint to withdraw fees in, leave empty to withdraw native GOR"
+          ]
+        },
+        {
+          "name": "platformTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "destinationTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Receives token fees, any token account of the mint"
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "combinationPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "race",
+            "type": "publicKey"
+          },
+          {
+            "name": "payer",
+            "type": "publicKey"
+          },
+          {
+            "name": "betKind",
+            "type": {
+              "defined": "BetKind"
+            }
+          },
+          {
+            "name": "selection",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "totalStake",
+            "type": "u64"
+          },
+          {
+            "name": "betCount",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "platformVault",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingAuthority",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "operator",
+            "type": "publicKey"
+          },
+          {
+            "name": "platformFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "totalFeesCollected",
+            "type": "u64"
+          },
+          {
+            "name": "totalFeesWithdrawn",
+            "type": "u64"
+          },
+          {
+            "name": "creatorShareBps",
+            "type": "u16"
+          },
+          {
+            "name": "referrerShareBps",
+            "type": "u16"
+          },
+          {
+            "name": "totalRewardsOwed",
+            "type": "u64"
+          },
+          {
+            "name": "totalRewardsPaid",
+            "type": "u64"
+          },
+          {
+            "name": "minEntryFee",
+            "type": "u64"
+          },
+          {
+            "name": "maxEntryFee",
+            "type": "u64"
+          },
+          {
+            "name": "maxPlayersLimit",
+            "type": "u32"
+          },
+          {
+            "name": "crankRewardBps",
+            "type": "u16"
+          },
+          {
+            "name": "maxCrankReward",
+            "type": "u64"
+          },
+          {
+            "name": "nextRaceId",
+            "type": "u64"
+          },
+          {
+            "name": "paused",
+            "type": "u8"
+          },
+          {
+            "name": "executePausedAt",
+            "type": "i64"
+          },
+          {
+            "name": "executeResumedAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "playerEntry",
+      "docs": [
+        "A player's ticket for one race, holding every bet they placed on it"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "player",
+            "type": "publicKey"
+          },
+          {
+            "name": "race",
+            "type": "publicKey"
+          },
+          {
+            "name": "bets",
+            "type": {
+              "vec": {
+                "defined": "Bet"
+              }
+            }
+          },
+          {
+            "name": "entryAmount",
+            "type": "u64"
+          },
+          {
+            "name": "referrer",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "claimStatus",
+            "type": {
+              "defined": "ClaimStatus"
+            }
+          },
+          {
+            "name": "prizeAmount",
+            "type": "u64"
+          },
+          {
+            "name": "statsUpdated",
+            "type": "bool"
+          },
+          {
+            "name": "referralSettled",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "playerProfile",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "player",
+            "docs": [
+              "The player's wallet address"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "username",
+            "docs": [
+              "The player's chosen username (max 32 characters)"
+            ],
+            "type": "string"
+          },
+          {
+            "name": "totalRaces",
+            "docs": [
+              "Total number of races participated in"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "totalWins",
+            "docs": [
+              "Total number of wins (1st place)"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "totalPodiums",
+            "docs": [
+              "Total number of podium finishes (1st, 2nd, 3rd place)"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "totalEarnings",
+            "docs": [
+              "Total GOR earned from prizes"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "createdAt",
+            "docs": [
+              "Account creation timestamp"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "updatedAt",
+            "docs": [
+              "Last updated timestamp"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "race",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "raceId",
+            "type": "u64"
+          },
+          {
+            "name": "creator",
+            "type": "publicKey"
+          },
+          {
+            "name": "status",
+            "type": {
+              "defined": "RaceStatus"
+            }
+          },
+          {
+            "name": "mode",
+            "type": {
+              "defined": "RaceMode"
+            }
+          },
+          {
+            "name": "mint",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "horseNames",
+            "type": {
+              "array": [
+                "string",
+                10
+              ]
+            }
+          },
+          {
+            "name": "horseAttributes",
+            "type": {
+              "array": [
+                {
+                  "defined": "HorseAttributes"
+                },
+                10
+              ]
+            }
+          },
+          {
+            "name": "totalPool",
+            "type": "u64"
+          },
+          {
+            "name": "horsePools",
+            "type": {
+              "array": [
+                "u64",
+                10
+              ]
+            }
+          },
+          {
+            "name": "horseEntryCounts",
+            "type": {
+              "array": [
+                "u32",
+                10
+              ]
+            }
+          },
+          {
+            "name": "showPools",
+            "type": {
+              "array": [
+                "u64",
+                10
+              ]
+            }
+          },
+          {
+            "name": "betPools",
+            "type": {
+              "array": [
+                "u64",
+                5
+              ]
+            }
+          },
+          {
+            "name": "platformFee",
+            "type": "u64"
+          },
+          {
+            "name": "feesCollected",
+            "type": "bool"
+          },
+          {
+            "name": "referredStake",
+            "type": "u64"
+          },
+          {
+            "name": "referralRewards",
+            "type": "u64"
+          },
+          {
+            "name": "referralRewardsPaid",
+            "type": "u64"
+          },
+          {
+            "name": "crankRewards",
+            "type": "u64"
+          },
+          {
+            "name": "entryCount",
+            "type": "u32"
+          },
+          {
+            "name": "closedEntries",
+            "type": "u32"
+          },
+          {
+            "name": "openCombinationPools",
+            "type": "u32"
+          },
+          {
+            "name": "maxPlayers",
+            "type": "u32"
+          },
+          {
+            "name": "minPlayers",
+            "type": "u32"
+          },
+          {
+            "name": "entryFee",
+            "type": "u64"
+          },
+          {
+            "name": "prizeDistribution",
+            "type": {
+              "array": [
+                "u16",
+                5
+              ]
+            }
+          },
+          {
+            "name": "waitTime",
+            "type": "i64"
+          },
+          {
+            "name": "referralCode",
+            "type": "string"
+          },
+          {
+            "name": "startTime",
+            "type": "i64"
+          },
+          {
+            "name": "raceStartTime",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "endTime",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "winningHorses",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "finishingOrder",
+            "type": {
+              "array": [
+                "u8",
+                10
+              ]
+            }
+          },
+          {
+            "name": "checkpoints",
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    "u8",
+                    10
+                  ]
+                },
+                10
+              ]
+            }
+          },
+          {
+            "name": "seedCommitment",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "seedSlot",
+            "type": "u64"
+          },
+          {
+            "name": "revealedSeed",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          },
+          {
+            "name": "raceSeed",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "creatorBond",
+            "type": "u64"
+          },
+          {
+            "name": "bondForfeited",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rewardAccount",
+      "docs": [
+        "Creator and referrer earnings, paid out of the platform vault by claim_rewards.",
+        "Only native races accrue them, token race fees stay whole with the platform"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "beneficiary",
+            "type": "publicKey"
+          },
+          {
+            "name": "owed",
+            "type": "u64"
+          },
+          {
+            "name": "totalEarned",
+            "type": "u64"
+          },
+          {
+            "name": "totalClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "username",
+      "docs": [
+        "Registry entry reserving a username, seeded by its lowercase form so",
+        "names that differ only in case cannot both be taken"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "player",
+            "type": "publicKey"
+          },
+          {
+            "name": "profile",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "RaceConfig",
+      "docs": [
+        "Per-race overrides, anything left empty falls back to the platform defaults"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "entryFee",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxPlayers",
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "minPlayers",
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "prizeDistribution",
+            "type": {
+              "option": {
+                "vec": "u16"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeReconciliation",
+      "docs": [
+        "Native fee ledger compared with the lamports the platform vault holds",
+        "above its rent-exempt minimum"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "totalFeesCollected",
+            "type": "u64"
+          },
+          {
+            "name": "totalFeesWithdrawn",
+            "type": "u64"
+          },
+          {
+            "name": "totalRewardsPaid",
+            "type": "u64"
+          },
+          {
+            "name": "feesHeld",
+            "type": "u64"
+          },
+          {
+            "name": "surplus",
+            "type": "u64"
+          },
+          {
+            "name": "shortfall",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Bet",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "betKind",
+            "type": {
+              "defined": "BetKind"
+            }
+          },
+          {
+            "name": "selection",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "stake",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "HorseAttributes",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "speed",
+            "type": "u8"
+          },
+          {
+            "name": "stamina",
+            "type": "u8"
+          },
+          {
+            "name": "form",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ClaimStatus",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Unclaimed"
+          },
+          {
+            "name": "Claimed"
+          },
+          {
+            "name": "Refunded"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BetKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Win"
+          },
+          {
+            "name": "Show"
+          },
+          {
+            "name": "Exacta"
+          },
+          {
+            "name": "Quinella"
+          },
+          {
+            "name": "Trifecta"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RaceStatus",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Pending"
+          },
+          {
+            "name": "Racing"
+          },
+          {
+            "name": "Completed"
+          },
+          {
+            "name": "Cancelled"
+          }
+        ]
+      }
+    },
+    {
+      "name": "CancelReason",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Underfilled"
+          },
+          {
+            "name": "SeedNotRevealed"
+          },
+          {
+            "name": "Authority"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RaceMode",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Fixed"
+          },
+          {
+            "name": "PariMutuel"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "AuthorityProposed",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "pendingAuthority",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuthorityTransferCancelled",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "pendingAuthority",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AuthorityTransferred",
+      "fields": [
+        {
+          "name": "previousAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newAuthority",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PauseUpdated",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "paused",
+          "type": "u8",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeesWithdrawn",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "destination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PlatformInitialized",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "platformFeeBps",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "minEntryFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxEntryFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxPlayersLimit",
+          "type": "u32",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PlatformVaultMigrated",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "platformFeeBps",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "totalFeesCollected",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeeUpdated",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "previousFeeBps",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "platformFeeBps",
+          "type": "u16",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "OperatorUpdated",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "previousOperator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "operator",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RaceLimitsUpdated",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "minEntryFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxEntryFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxPlayersLimit",
+          "type": "u32",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RevenueSplitUpdated",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "creatorShareBps",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "referrerShareBps",
+          "type": "u16",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CrankRewardUpdated",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "crankRewardBps",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "maxCrankReward",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RaceCreated",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "raceId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mode",
+          "type": {
+            "defined": "RaceMode"
+          },
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "entryFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxPlayers",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "minPlayers",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "prizeDistribution",
+          "type": {
+            "array": [
+              "u16",
+              5
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "waitTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "referralCode",
+          "type": "string",
+          "index": false
+        },
+        {
+          "name": "horseNames",
+          "type": {
+            "array": [
+              "string",
+              10
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "horseAttributes",
+          "type": {
+            "array": [
+              {
+                "defined": "HorseAttributes"
+              },
+              10
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "seedCommitment",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "creatorBond",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "startTime",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PlayerJoined",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "betKind",
+          "type": {
+            "defined": "BetKind"
+          },
+          "index": false
+        },
+        {
+          "name": "selection",
+          "type": {
+            "array": [
+              "u8",
+              3
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "stake",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "newPlayer",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "totalPool",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "entryCount",
+          "type": "u32",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RaceStarted",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "raceStartTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "seedSlot",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "entryCount",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "totalPool",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CrankRewardPaid",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "executor",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "crankRewards",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RaceCompleted",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "revealedSeed",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "raceSeed",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "winningHorses",
+          "type": {
+            "array": [
+              "u8",
+              3
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "finishingOrder",
+          "type": {
+            "array": [
+              "u8",
+              10
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "checkpoints",
+          "type": {
+            "array": [
+              {
+                "array": [
+                  "u8",
+                  10
+                ]
+              },
+              10
+            ]
+          },
+          "index": false
+        },
+        {
+          "name": "totalPool",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "platformFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorReward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referralRewards",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "endTime",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RaceCancelled",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "reason",
+          "type": {
+            "defined": "CancelReason"
+          },
+          "index": false
+        },
+        {
+          "name": "endTime",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PrizeClaimed",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "prizeAmount",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RefundClaimed",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "refundAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "bondShare",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "StatsUpdated",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "bestFinish",
+          "type": {
+            "option": "u8"
+          },
+          "index": false
+        },
+        {
+          "name": "prizeAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalRaces",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "totalWins",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "totalPodiums",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "totalEarnings",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RewardsClaimed",
+      "fields": [
+        {
+          "name": "beneficiary",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalClaimed",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ProfileCreated",
+      "fields": [
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "profile",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "username",
+          "type": "string",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ProfileRenamed",
+      "fields": [
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "profile",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "previousUsername",
+          "type": "string",
+          "index": false
+        },
+        {
+          "name": "username",
+          "type": "string",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "UsernameClaimed",
+      "fields": [
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "profile",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "username",
+          "type": "string",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ReferralSettled",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "referrerReward",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "EntryClosed",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "referrerReward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "closedEntries",
+          "type": "u32",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CombinationPoolClosed",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "combinationPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "openCombinationPools",
+          "type": "u32",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RaceClosed",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "dust",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "releasedRewards",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "InvalidHorseNumber",
+      "msg": "Invalid horse number selected"
+    },
+    {
+      "code": 6001,
+      "name": "RaceNotPending",
+      "msg": "Race already started or completed"
+    },
+    {
+      "code": 6002,
+      "name": "RaceNotReady",
+      "msg": "Race not ready for execution"
+    },
+    {
+      "code": 6003,
+      "name": "AlreadyJoined",
+      "msg": "Player already backs this horse"
+    },
+    {
+      "code": 6004,
+      "name": "AlreadyClaimed",
+      "msg": "Prize already claimed"
+    },
+    {
+      "code": 6005,
+      "name": "NoPrize",
+      "msg": "No prize to claim"
+    },
+    {
+      "code": 6006,
+      "name": "InvalidPlatformFee",
+      "msg": "Invalid platform fee"
+    },
+    {
+      "code": 6007,
+      "name": "InsufficientFunds",
+      "msg": "Insufficient funds"
+    },
+    {
+      "code": 6008,
+      "name": "RaceFull",
+      "msg": "Race is full"
+    },
+    {
+      "code": 6009,
+      "name": "Unauthorized",
+      "msg": "Unauthorized"
+    },
+    {
+      "code": 6010,
+      "name": "MathOverflow",
+      "msg": "Math overflow"
+    },
+    {
+      "code": 6011,
+      "name": "RaceNotCompleted",
+      "msg": "Race not completed"
+    },
+    {
+      "code": 6012,
+      "name": "InvalidWaitTime",
+      "msg": "Invalid wait time"
+    },
+    {
+      "code": 6013,
+      "name": "InvalidReferralCode",
+      "msg": "Invalid referral code"
+    },
+    {
+      "code": 6014,
+      "name": "UsernameTooLong",
+      "msg": "Username too long (max 32 characters)"
+    },
+    {
+      "code": 6015,
+      "name": "UsernameEmpty",
+      "msg": "Username cannot be empty"
+    },
+    {
+      "code": 6016,
+      "name": "UsernameAlreadyTaken",
+      "msg": "Username already taken"
+    },
+    {
+      "code": 6017,
+      "name": "ProfileNotFound",
+      "msg": "Player profile not found"
+    },
+    {
+      "code": 6018,
+      "name": "InvalidSeedCommitment",
+      "msg": "Seed commitment cannot be empty"
+    },
+    {
+      "code": 6019,
+      "name": "InvalidSeedReveal",
+      "msg": "Revealed seed does not match the commitment"
+    },
+    {
+      "code": 6020,
+      "name": "SlotHashUnavailable",
+      "msg": "Slot hash for the race start slot is no longer available"
+    },
+    {
+      "code": 6021,
+      "name": "RevealWindowOpen",
+      "msg": "Seed reveal window has not expired yet"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidStakeAmount",
+      "msg": "Invalid stake amount"
+    },
+    {
+      "code": 6023,
+      "name": "RaceNotCancelled",
+      "msg": "Race not cancelled"
+    },
+    {
+      "code": 6024,
+      "name": "RaceNotCancellable",
+      "msg": "Race already completed or cancelled"
+    },
+    {
+      "code": 6025,
+      "name": "MissingTokenAccounts",
+      "msg": "Token accounts are required for races paid in an SPL mint"
+    },
+    {
+      "code": 6026,
+      "name": "InvalidMint",
+      "msg": "Mint does not match the race mint"
+    },
+    {
+      "code": 6027,
+      "name": "InvalidEntryFee",
+      "msg": "Entry fee outside platform limits"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidMaxPlayers",
+      "msg": "Max players outside platform limits"
+    },
+    {
+      "code": 6029,
+      "name": "RaceNotFound",
+      "msg": "No race has been created with this id"
+    },
+    {
+      "code": 6030,
+      "name": "InvalidMinPlayers",
+      "msg": "Min players must be between MIN_PLAYERS_TO_START and max players"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidPrizeDistribution",
+      "msg": "Invalid prize distribution"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidRaceLimits",
+      "msg": "Invalid race limits"
+    },
+    {
+      "code": 6033,
+      "name": "InvalidBetSelection",
+      "msg": "Invalid bet selection"
+    },
+    {
+      "code": 6034,
+      "name": "MissingCombinationPool",
+      "msg": "Combination pool account required for exotic bets"
+    },
+    {
+      "code": 6035,
+      "name": "InvalidCombinationPool",
+      "msg": "Combination pool does not match the bet"
+    },
+    {
+      "code": 6036,
+      "name": "TooManyBets",
+      "msg": "Too many bets on this entry"
+    },
+    {
+      "code": 6037,
+      "name": "RaceNotSettled",
+      "msg": "Race is not completed or cancelled"
+    },
+    {
+      "code": 6038,
+      "name": "EntryNotSettled",
+      "msg": "Entry still has a prize or refund to claim"
+    },
+    {
+      "code": 6039,
+      "name": "RaceHasOpenAccounts",
+      "msg": "Race still has open entries or combination pools"
+    },
+    {
+      "code": 6040,
+      "name": "NoPendingAuthority",
+      "msg": "No authority transfer is pending"
+    },
+    {
+      "code": 6041,
+      "name": "Paused",
+      "msg": "This instruction is paused"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidPauseFlags",
+      "msg": "Unknown pause flags"
+    },
+    {
+      "code": 6043,
+      "name": "FeesAlreadyCollected",
+      "msg": "Platform fees were already collected for this race"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidRevenueSplit",
+      "msg": "Creator and referrer shares cannot exceed the platform fee"
+    },
+    {
+      "code": 6045,
+      "name": "InvalidCrankReward",
+      "msg": "Crank reward cannot exceed MAX_CRANK_REWARD_BPS of the pool"
+    },
+    {
+      "code": 6046,
+      "name": "InvalidReferrer",
+      "msg": "Invalid referrer"
+    },
+    {
+      "code": 6047,
+      "name": "MissingRewardAccount",
+      "msg": "Reward account is required"
+    },
+    {
+      "code": 6048,
+      "name": "NoReferralToSettle",
+      "msg": "Entry has no referrer left to credit"
+    },
+    {
+      "code": 6049,
+      "name": "NoRewards",
+      "msg": "No rewards to claim"
+    },
+    {
+      "code": 6050,
+      "name": "PrizeNotClaimed",
+      "msg": "Claim the prize before updating stats"
+    },
+    {
+      "code": 6051,
+      "name": "RevealerCannotBet",
+      "msg": "The race creator and operator cannot bet on the race"
+    },
+    {
+      "code": 6052,
+      "name": "PlatformVaultMigrated",
+      "msg": "Platform vault is not in the first release's layout"
+    },
+    {
+      "code": 6053,
+      "name": "UnsupportedMintExtension",
+      "msg": "Mint has a permanent delegate, transfer hook or is non-transferable"
+    },
+    {
+      "code": 6054,
+      "name": "TokenRaceRewardsUnsupported",
+      "msg": "Token races pay no creator or referrer rewards, set the revenue split to 0 or race in GOR"
+    }
+  ]
+};
+
+export const IDL: GorRace = {
+  "version": "0.1.0",
+  "name": "gor_race",
+  "instructions": [
+    {
+      "name": "initialize",
+      "docs": [
+        "Initialize the platform vault and set platform parameters"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "platformFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "migratePlatformVault",
+      "docs": [
+        "Rewrite a platform vault left in the first release's layout to the current one,",
+        "keeping its authority, fee and fee ledger (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The handler checks its owner, discriminator and size before rewriting it."
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createProfile",
+      "docs": [
+        "Create a player profile with a username no other player holds"
+      ],
+      "accounts": [
+        {
+          "name": "playerProfile",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "usernameAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "username",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "renameProfile",
+      "docs": [
+        "Change a profile's username, releasing the old name for others"
+      ],
+      "accounts": [
+        {
+          "name": "playerProfile",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oldUsernameAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Profiles created before the registry may have no entry, or share it with a case variant."
+          ]
+        },
+        {
+          "name": "newUsernameAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Same account as the old entry when only the case of the name changes"
+          ]
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newUsername",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "claimUsername",
+      "docs": [
+        "Reserve the username of a profile created before the username registry (permissionless)"
+      ],
+      "accounts": [
+        {
+          "name": "playerProfile",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "usernameAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createRace",
+      "docs": [
+        "Create a new race that players can join, numbered by the platform's race counter.",
+        "The creator posts CREATOR_BOND, forfeited to the entries if the seed is never revealed"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Hands out the race id, two creations in one slot get consecutive ids"
+          ]
+        },
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "creator",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "creatorRewards",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Accrues the creator's share of the platform fee"
+          ]
+        },
+        {
+          "name": "raceVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Mint entries are paid in, leave empty for a native GOR race"
+          ]
+        },
+        {
+          "name": "raceVaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "platformTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "waitTime",
+          "type": {
+            "option": "i64"
+          }
+        },
+        {
+          "name": "seedCommitment",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "mode",
+          "type": {
+            "defined": "RaceMode"
+          }
+        },
+        {
+          "name": "config",
+          "type": {
+            "defined": "RaceConfig"
+          }
+        }
+      ]
+    },
+    {
+      "name": "joinRace",
+      "docs": [
+        "Join a race by placing a win, show, exacta, quinella or trifecta bet,",
+        "paying the entry fee or staking any amount above it, optionally naming a referrer"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "playerEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "combinationPool",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "referrerRewards",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "raceVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "platformVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "playerTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "raceVaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "betKind",
+          "type": {
+            "defined": "BetKind"
+          }
+        },
+        {
+          "name": "selection",
+          "type": {
+            "array": [
+              "u8",
+              3
+            ]
+          }
+        },
+        {
+          "name": "referralCode",
+          "type": "string"
+        },
+        {
+          "name": "amount",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "getRaceAddress",
+      "docs": [
+        "Address of the race with `race_id`, which must already be created (read-only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "raceId",
+          "type": "u64"
+        }
+      ],
+      "returns": "publicKey"
+    },
+    {
+      "name": "getOdds",
+      "docs": [
+        "Implied decimal odds for each horse, scaled by ODDS_PRECISION (read-only)"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "platformVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Required for token races, the crank reward cap scales to its decimals"
+          ]
+        }
+      ],
+      "args": [],
+      "returns": {
+        "array": [
+          "u64",
+          10
+        ]
+      }
+    },
+    {
+      "name": "executeRace",
+      "docs": [
+        "Execute the race after timeout or when conditions are met,",
+        "revealing the committed seed to finish it"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "raceVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "creatorRewards",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "executor",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Anyone may start a race or cancel an unrevealed one; only the race",
+            "creator or the platform operator may reveal the seed"
+          ]
+        },
+        {
+          "name": "slotHashes",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "raceVaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "platformTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "executorTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Receives the crank reward of token races, executors without one waive it"
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "seed",
+          "type": {
+            "option": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "claimPrize",
+      "docs": [
+        "Claim prize after race completion, counting the race on the player's profile if passed"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "playerEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "playerProfile",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Counts the race on the player's profile as the prize is paid"
+          ]
+        },
+        {
+          "name": "raceVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "platformVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "playerTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "raceVaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimRefund",
+      "docs": [
+        "Claim back the entry amount from a cancelled race, plus a share of the creator bond",
+        "when the seed was never revealed"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "playerEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "raceVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "playerTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "raceVaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimRewards",
+      "docs": [
+        "Claim creator and referrer rewards accrued from platform fees"
+      ],
+      "accounts": [
+        {
+          "name": "rewardAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "beneficiary",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelRace",
+      "docs": [
+        "Emergency cancel a stuck race so entries can be refunded (authority only). A racing",
+        "race can only be cancelled once its seed can no longer be revealed"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "platformVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "slotHashes",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "settleReferral",
+      "docs": [
+        "Credit an entry's referrer once the race completes, without waiting for the entry",
+        "to close (permissionless)"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "playerEntry",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Anyone may settle, so a winner who never claims cannot hold back the referrer's reward"
+          ]
+        },
+        {
+          "name": "referrerRewards",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closePlayerEntry",
+      "docs": [
+        "Close a claimed, refunded or prize-less entry, returning its rent to the player"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "playerEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Receives the entry rent, anyone may close a settled entry"
+          ]
+        },
+        {
+          "name": "referrerRewards",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Required when the entry named a referrer that settle_referral has not credited"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeCombinationPool",
+      "docs": [
+        "Close an exotic bet pool once every entry is closed, returning its rent to the payer"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "combinationPool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Receives the pool rent, anyone may close a pool once the race is settled"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeRace",
+      "docs": [
+        "Close a fully settled race, sweeping vault dust to the platform",
+        "and returning rent to the creator"
+      ],
+      "accounts": [
+        {
+          "name": "race",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "creator",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Receives the race, vault and vault token account rent, anyone may close a settled race"
+          ]
+        },
+        {
+          "name": "raceVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "raceVaultTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "platformTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateStats",
+      "docs": [
+        "Count a completed race on the player's profile when claim_prize did not"
+      ],
+      "accounts": [
+        {
+          "name": "playerProfile",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "race",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "playerEntry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "player",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updatePlatformFee",
+      "docs": [
+        "Update platform fee (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "updateRaceLimits",
+      "docs": [
+        "Update the entry fee and player limits races are validated against (authority only).",
+        "Entry fees are in GOR base units, token races scale them to the mint's decimals."
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "minEntryFee",
+          "type": "u64"
+        },
+        {
+          "name": "maxEntryFee",
+          "type": "u64"
+        },
+        {
+          "name": "maxPlayersLimit",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "updateRevenueSplit",
+      "docs": [
+        "Set the creator and referrer shares of the platform fee in basis points (authority only).",
+        "Rewards are paid in GOR, token races can only be created while both are 0."
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "creatorShareBps",
+          "type": "u16"
+        },
+        {
+          "name": "referrerShareBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "updateCrankReward",
+      "docs": [
+        "Set the share of a race pool paid to whoever executes it, capped per call (authority only).",
+        "The cap is in GOR base units, token races scale it to the mint's decimals."
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "crankRewardBps",
+          "type": "u16"
+        },
+        {
+          "name": "maxCrankReward",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateOperator",
+      "docs": [
+        "Set the operator allowed to reveal race seeds (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newOperator",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "setPause",
+      "docs": [
+        "Pause or resume instructions with a bitmask of PAUSE_* flags (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "paused",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "proposeAuthority",
+      "docs": [
+        "Propose a new platform authority, which takes over once it accepts (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newAuthority",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "acceptAuthority",
+      "docs": [
+        "Accept a pending authority transfer (pending authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelAuthorityTransfer",
+      "docs": [
+        "Drop a pending authority transfer (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "reconcileFees",
+      "docs": [
+        "Compare recorded native platform fees with the platform vault balance (read-only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "FeeReconciliation"
+      }
+    },
+    {
+      "name": "withdrawPlatformFees",
+      "docs": [
+        "Withdraw platform fees (authority only)"
+      ],
+      "accounts": [
+        {
+          "name": "platformVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Receives native fees, leave empty to pay the authority"
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Mint to withdraw fees in, leave empty to withdraw native GOR"
+          ]
+        },
+        {
+          "name": "platformTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "destinationTokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Receives token fees, any token account of the mint"
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     }
   ],
   "accounts": [
+    {
+      "name": "combinationPool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "race",
+            "type": "publicKey"
+          },
+          {
+            "name": "payer",
+            "type": "publicKey"
+          },
+          {
+            "name": "betKind",
+            "type": {
+              "defined": "BetKind"
+            }
+          },
+          {
+            "name": "selection",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "totalStake",
+            "type": "u64"
+          },
+          {
+            "name": "betCount",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
     {
       "name": "platformVault",
       "type": {
@@ -324,6 +4437,16 @@ export type GorRace = {
             "name": "authority",
             "type": "publicKey"
           },
+          {
+            "name": "pendingAuthority",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "operator",
+            "type": "publicKey"
+          },
           {
             "name": "platformFeeBps",
             "type": "u16"
@@ -333,8 +4456,60 @@ export type GorRace = {
             "type": "u64"
           },
           {
-            "name": "feesTransferred",
-            "type": "bool"
+            "name": "totalFeesWithdrawn",
+            "type": "u64"
+          },
+          {
+            "name": "creatorShareBps",
+            "type": "u16"
+          },
+          {
+            "name": "referrerShareBps",
+            "type": "u16"
+          },
+          {
+            "name": "totalRewardsOwed",
+            "type": "u64"
+          },
+          {
+            "name": "totalRewardsPaid",
+            "type": "u64"
+          },
+          {
+            "name": "minEntryFee",
+            "type": "u64"
+          },
+          {
+            "name": "maxEntryFee",
+            "type": "u64"
+          },
+          {
+            "name": "maxPlayersLimit",
+            "type": "u32"
+          },
+          {
+            "name": "crankRewardBps",
+            "type": "u16"
+          },
+          {
+            "name": "maxCrankReward",
+            "type": "u64"
+          },
+          {
+            "name": "nextRaceId",
+            "type": "u64"
+          },
+          {
+            "name": "paused",
+            "type": "u8"
+          },
+          {
+            "name": "executePausedAt",
+            "type": "i64"
+          },
+          {
+            "name": "executeResumedAt",
+            "type": "i64"
           },
           {
             "name": "bump",
@@ -345,6 +4520,9 @@ export type GorRace = {
     },
     {
       "name": "playerEntry",
+      "docs": [
+        "A player's ticket for one race, holding every bet they placed on it"
+      ],
       "type": {
         "kind": "struct",
         "fields": [
@@ -357,13 +4535,23 @@ export type GorRace = {
             "type": "publicKey"
           },
           {
-            "name": "horseNumber",
-            "type": "u8"
+            "name": "bets",
+            "type": {
+              "vec": {
+                "defined": "Bet"
+              }
+            }
           },
           {
             "name": "entryAmount",
             "type": "u64"
           },
+          {
+            "name": "referrer",
+            "type": {
+              "option": "publicKey"
+            }
+          },
           {
             "name": "claimStatus",
             "type": {
@@ -378,6 +4566,10 @@ export type GorRace = {
             "name": "statsUpdated",
             "type": "bool"
           },
+          {
+            "name": "referralSettled",
+            "type": "bool"
+          },
           {
             "name": "bump",
             "type": "u8"
@@ -468,6 +4660,18 @@ export type GorRace = {
               "defined": "RaceStatus"
             }
           },
+          {
+            "name": "mode",
+            "type": {
+              "defined": "RaceMode"
+            }
+          },
+          {
+            "name": "mint",
+            "type": {
+              "option": "publicKey"
+            }
+          },
           {
             "name": "horseNames",
             "type": {
@@ -477,48 +4681,362 @@ export type GorRace = {
               ]
             }
           },
+          {
+            "name": "horseAttributes",
+            "type": {
+              "array": [
+                {
+                  "defined": "HorseAttributes"
+                },
+                10
+              ]
+            }
+          },
           {
             "name": "totalPool",
             "type": "u64"
           },
+          {
+            "name": "horsePools",
+            "type": {
+              "array": [
+                "u64",
+                10
+              ]
+            }
+          },
+          {
+            "name": "horseEntryCounts",
+            "type": {
+              "array": [
+                "u32",
+                10
+              ]
+            }
+          },
+          {
+            "name": "showPools",
+            "type": {
+              "array": [
+                "u64",
+                10
+              ]
+            }
+          },
+          {
+            "name": "betPools",
+            "type": {
+              "array": [
+                "u64",
+                5
+              ]
+            }
+          },
           {
             "name": "platformFee",
             "type": "u64"
           },
           {
-            "name": "entryCount",
-            "type": "u32"
+            "name": "feesCollected",
+            "type": "bool"
+          },
+          {
+            "name": "referredStake",
+            "type": "u64"
+          },
+          {
+            "name": "referralRewards",
+            "type": "u64"
+          },
+          {
+            "name": "referralRewardsPaid",
+            "type": "u64"
+          },
+          {
+            "name": "crankRewards",
+            "type": "u64"
+          },
+          {
+            "name": "entryCount",
+            "type": "u32"
+          },
+          {
+            "name": "closedEntries",
+            "type": "u32"
+          },
+          {
+            "name": "openCombinationPools",
+            "type": "u32"
+          },
+          {
+            "name": "maxPlayers",
+            "type": "u32"
+          },
+          {
+            "name": "minPlayers",
+            "type": "u32"
+          },
+          {
+            "name": "entryFee",
+            "type": "u64"
+          },
+          {
+            "name": "prizeDistribution",
+            "type": {
+              "array": [
+                "u16",
+                5
+              ]
+            }
+          },
+          {
+            "name": "waitTime",
+            "type": "i64"
+          },
+          {
+            "name": "referralCode",
+            "type": "string"
+          },
+          {
+            "name": "startTime",
+            "type": "i64"
+          },
+          {
+            "name": "raceStartTime",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "endTime",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "winningHorses",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "finishingOrder",
+            "type": {
+              "array": [
+                "u8",
+                10
+              ]
+            }
+          },
+          {
+            "name": "checkpoints",
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    "u8",
+                    10
+                  ]
+                },
+                10
+              ]
+            }
+          },
+          {
+            "name": "seedCommitment",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "seedSlot",
+            "type": "u64"
+          },
+          {
+            "name": "revealedSeed",
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          },
+          {
+            "name": "raceSeed",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "creatorBond",
+            "type": "u64"
+          },
+          {
+            "name": "bondForfeited",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rewardAccount",
+      "docs": [
+        "Creator and referrer earnings, paid out of the platform vault by claim_rewards.",
+        "Only native races accrue them, token race fees stay whole with the platform"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "beneficiary",
+            "type": "publicKey"
+          },
+          {
+            "name": "owed",
+            "type": "u64"
+          },
+          {
+            "name": "totalEarned",
+            "type": "u64"
+          },
+          {
+            "name": "totalClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "username",
+      "docs": [
+        "Registry entry reserving a username, seeded by its lowercase form so",
+        "names that differ only in case cannot both be taken"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "player",
+            "type": "publicKey"
+          },
+          {
+            "name": "profile",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "RaceConfig",
+      "docs": [
+        "Per-race overrides, anything left empty falls back to the platform defaults"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "entryFee",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxPlayers",
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "minPlayers",
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "prizeDistribution",
+            "type": {
+              "option": {
+                "vec": "u16"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "FeeReconciliation",
+      "docs": [
+        "Native fee ledger compared with the lamports the platform vault holds",
+        "above its rent-exempt minimum"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "totalFeesCollected",
+            "type": "u64"
           },
           {
-            "name": "maxPlayers",
-            "type": "u32"
+            "name": "totalFeesWithdrawn",
+            "type": "u64"
           },
           {
-            "name": "waitTime",
-            "type": "i64"
+            "name": "totalRewardsPaid",
+            "type": "u64"
           },
           {
-            "name": "referralCode",
-            "type": "string"
+            "name": "feesHeld",
+            "type": "u64"
           },
           {
-            "name": "startTime",
-            "type": "i64"
+            "name": "surplus",
+            "type": "u64"
           },
           {
-            "name": "raceStartTime",
-            "type": {
-              "option": "i64"
-            }
-          },
+            "name": "shortfall",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Bet",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "endTime",
+            "name": "betKind",
             "type": {
-              "option": "i64"
+              "defined": "BetKind"
             }
           },
           {
-            "name": "winningHorses",
+            "name": "selection",
             "type": {
               "array": [
                 "u8",
@@ -527,14 +5045,32 @@ export type GorRace = {
             }
           },
           {
-            "name": "bump",
+            "name": "stake",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "HorseAttributes",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "speed",
+            "type": "u8"
+          },
+          {
+            "name": "stamina",
+            "type": "u8"
+          },
+          {
+            "name": "form",
             "type": "u8"
           }
         ]
       }
-    }
-  ],
-  "types": [
+    },
     {
       "name": "ClaimStatus",
       "type": {
@@ -545,6 +5081,32 @@ export type GorRace = {
           },
           {
             "name": "Claimed"
+          },
+          {
+            "name": "Refunded"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BetKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Win"
+          },
+          {
+            "name": "Show"
+          },
+          {
+            "name": "Exacta"
+          },
+          {
+            "name": "Quinella"
+          },
+          {
+            "name": "Trifecta"
           }
         ]
       }
@@ -568,672 +5130,885 @@ export type GorRace = {
           }
         ]
       }
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "InvalidHorseNumber",
-      "msg": "Invalid horse number selected"
-    },
-    {
-      "code": 6001,
-      "name": "RaceNotPending",
-      "msg": "Race already started or completed"
-    },
-    {
-      "code": 6002,
-      "name": "RaceNotReady",
-      "msg": "Race not ready for execution"
-    },
-    {
-      "code": 6003,
-      "name": "AlreadyJoined",
-      "msg": "Player already joined this race"
-    },
-    {
-      "code": 6004,
-      "name": "AlreadyClaimed",
-      "msg": "Prize already claimed"
-    },
-    {
-      "code": 6005,
-      "name": "NoPrize",
-      "msg": "No prize to claim"
-    },
-    {
-      "code": 6006,
-      "name": "InvalidPlatformFee",
-      "msg": "Invalid platform fee"
-    },
-    {
-      "code": 6007,
-      "name": "InsufficientFunds",
-      "msg": "Insufficient funds"
-    },
-    {
-      "code": 6008,
-      "name": "RaceFull",
-      "msg": "Race is full"
-    },
-    {
-      "code": 6009,
-      "name": "Unauthorized",
-      "msg": "Unauthorized"
-    },
-    {
-      "code": 6010,
-      "name": "MathOverflow",
-      "msg": "Math overflow"
-    },
-    {
-      "code": 6011,
-      "name": "RaceNotCompleted",
-      "msg": "Race not completed"
-    },
-    {
-      "code": 6012,
-      "name": "InvalidWaitTime",
-      "msg": "Invalid wait time"
-    },
-    {
-      "code": 6013,
-      "name": "InvalidReferralCode",
-      "msg": "Invalid referral code"
     },
     {
-      "code": 6014,
-      "name": "UsernameTooLong",
-      "msg": "Username too long (max 32 characters)"
-    },
-    {
-      "code": 6015,
-      "name": "UsernameEmpty",
-      "msg": "Username cannot be empty"
-    },
-    {
-      "code": 6016,
-      "name": "UsernameAlreadyTaken",
-      "msg": "Username already taken"
+      "name": "CancelReason",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Underfilled"
+          },
+          {
+            "name": "SeedNotRevealed"
+          },
+          {
+            "name": "Authority"
+          }
+        ]
+      }
     },
     {
-      "code": 6017,
-      "name": "ProfileNotFound",
-      "msg": "Player profile not found"
+      "name": "RaceMode",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Fixed"
+          },
+          {
+            "name": "PariMutuel"
+          }
+        ]
+      }
     }
-  ]
-};
-
-export const IDL: GorRace = {
-  "version": "0.1.0",
-  "name": "gor_race",
-  "instructions": [
+  ],
+  "events": [
     {
-      "name": "initialize",
-      "docs": [
-        "Initialize the platform vault and set platform parameters"
-      ],
-      "accounts": [
-        {
-          "name": "platformVault",
-          "isMut": true,
-          "isSigner": false
-        },
+      "name": "AuthorityProposed",
+      "fields": [
         {
           "name": "authority",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "platformFeeBps",
-          "type": "u16"
-        }
-      ]
-    },
-    {
-      "name": "createProfile",
-      "docs": [
-        "Create a player profile with username"
-      ],
-      "accounts": [
-        {
-          "name": "playerProfile",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "player",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "clock",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "username",
-          "type": "string"
+          "name": "pendingAuthority",
+          "type": "publicKey",
+          "index": false
         }
       ]
     },
     {
-      "name": "createRace",
-      "docs": [
-        "Create a new race that players can join"
-      ],
-      "accounts": [
-        {
-          "name": "race",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "creator",
-          "isMut": true,
-          "isSigner": true
-        },
+      "name": "AuthorityTransferCancelled",
+      "fields": [
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "clock",
-          "isMut": false,
-          "isSigner": false
+          "name": "pendingAuthority",
+          "type": "publicKey",
+          "index": false
         }
-      ],
-      "args": [
+      ]
+    },
+    {
+      "name": "AuthorityTransferred",
+      "fields": [
         {
-          "name": "raceId",
-          "type": "u64"
+          "name": "previousAuthority",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "waitTime",
-          "type": {
-            "option": "i64"
-          }
+          "name": "newAuthority",
+          "type": "publicKey",
+          "index": false
         }
       ]
     },
     {
-      "name": "joinRace",
-      "docs": [
-        "Join a race by selecting a horse and paying entry fee"
-      ],
-      "accounts": [
+      "name": "PauseUpdated",
+      "fields": [
         {
-          "name": "race",
-          "isMut": true,
-          "isSigner": false
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "playerEntry",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "paused",
+          "type": "u8",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeesWithdrawn",
+      "fields": [
         {
-          "name": "player",
-          "isMut": true,
-          "isSigner": true
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "raceVault",
-          "isMut": true,
-          "isSigner": false
+          "name": "destination",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "horseNumber",
-          "type": "u8"
+          "name": "mint",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
         },
         {
-          "name": "referralCode",
-          "type": "string"
+          "name": "amount",
+          "type": "u64",
+          "index": false
         }
       ]
     },
     {
-      "name": "executeRace",
-      "docs": [
-        "Execute the race after timeout or when conditions are met"
-      ],
-      "accounts": [
-        {
-          "name": "race",
-          "isMut": true,
-          "isSigner": false
-        },
+      "name": "PlatformInitialized",
+      "fields": [
         {
-          "name": "raceVault",
-          "isMut": true,
-          "isSigner": false
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "platformVault",
-          "isMut": true,
-          "isSigner": false
+          "name": "platformFeeBps",
+          "type": "u16",
+          "index": false
         },
         {
-          "name": "recentBlockhashes",
-          "isMut": false,
-          "isSigner": false
+          "name": "minEntryFee",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "clock",
-          "isMut": false,
-          "isSigner": false
+          "name": "maxEntryFee",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "maxPlayersLimit",
+          "type": "u32",
+          "index": false
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "claimPrize",
-      "docs": [
-        "Claim prize after race completion"
-      ],
-      "accounts": [
+      "name": "PlatformVaultMigrated",
+      "fields": [
         {
-          "name": "race",
-          "isMut": false,
-          "isSigner": false
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "playerEntry",
-          "isMut": true,
-          "isSigner": false
+          "name": "platformFeeBps",
+          "type": "u16",
+          "index": false
         },
         {
-          "name": "player",
-          "isMut": true,
-          "isSigner": true
-        },
+          "name": "totalFeesCollected",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeeUpdated",
+      "fields": [
         {
-          "name": "raceVault",
-          "isMut": true,
-          "isSigner": false
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "platformVault",
-          "isMut": true,
-          "isSigner": false
+          "name": "previousFeeBps",
+          "type": "u16",
+          "index": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "platformFeeBps",
+          "type": "u16",
+          "index": false
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "updateStats",
-      "docs": [
-        "Update player statistics after race completion"
-      ],
-      "accounts": [
-        {
-          "name": "playerProfile",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "race",
-          "isMut": false,
-          "isSigner": false
-        },
+      "name": "OperatorUpdated",
+      "fields": [
         {
-          "name": "playerEntry",
-          "isMut": true,
-          "isSigner": false
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "player",
-          "isMut": false,
-          "isSigner": false
+          "name": "previousOperator",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "clock",
-          "isMut": false,
-          "isSigner": false
+          "name": "operator",
+          "type": "publicKey",
+          "index": false
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "updatePlatformFee",
-      "docs": [
-        "Update platform fee (authority only)"
-      ],
-      "accounts": [
+      "name": "RaceLimitsUpdated",
+      "fields": [
         {
-          "name": "platformVault",
-          "isMut": true,
-          "isSigner": false
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "authority",
-          "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
+          "name": "minEntryFee",
+          "type": "u64",
+          "index": false
+        },
         {
-          "name": "newFeeBps",
-          "type": "u16"
+          "name": "maxEntryFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxPlayersLimit",
+          "type": "u32",
+          "index": false
         }
       ]
     },
     {
-      "name": "withdrawPlatformFees",
-      "docs": [
-        "Withdraw platform fees (authority only)"
-      ],
-      "accounts": [
-        {
-          "name": "platformVault",
-          "isMut": true,
-          "isSigner": false
-        },
+      "name": "RevenueSplitUpdated",
+      "fields": [
         {
           "name": "authority",
-          "isMut": true,
-          "isSigner": true
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
+          "name": "creatorShareBps",
+          "type": "u16",
+          "index": false
+        },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "referrerShareBps",
+          "type": "u16",
+          "index": false
         }
       ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "platformVault",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "authority",
-            "type": "publicKey"
-          },
-          {
-            "name": "platformFeeBps",
-            "type": "u16"
-          },
-          {
-            "name": "totalFeesCollected",
-            "type": "u64"
-          },
-          {
-            "name": "feesTransferred",
-            "type": "bool"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          }
-        ]
-      }
     },
     {
-      "name": "playerEntry",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "player",
-            "type": "publicKey"
-          },
-          {
-            "name": "race",
-            "type": "publicKey"
-          },
-          {
-            "name": "horseNumber",
-            "type": "u8"
-          },
-          {
-            "name": "entryAmount",
-            "type": "u64"
-          },
-          {
-            "name": "claimStatus",
-            "type": {
-              "defined": "ClaimStatus"
-            }
-          },
-          {
-            "name": "prizeAmount",
-            "type": "u64"
-          },
-          {
-            "name": "statsUpdated",
-            "type": "bool"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          }
-        ]
-      }
+      "name": "CrankRewardUpdated",
+      "fields": [
+        {
+          "name": "authority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "crankRewardBps",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "maxCrankReward",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "playerProfile",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "player",
-            "docs": [
-              "The player's wallet address"
-            ],
-            "type": "publicKey"
-          },
-          {
-            "name": "username",
-            "docs": [
-              "The player's chosen username (max 32 characters)"
-            ],
-            "type": "string"
+      "name": "RaceCreated",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "raceId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creator",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mode",
+          "type": {
+            "defined": "RaceMode"
           },
-          {
-            "name": "totalRaces",
-            "docs": [
-              "Total number of races participated in"
-            ],
-            "type": "u32"
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": {
+            "option": "publicKey"
           },
-          {
-            "name": "totalWins",
-            "docs": [
-              "Total number of wins (1st place)"
-            ],
-            "type": "u32"
+          "index": false
+        },
+        {
+          "name": "entryFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxPlayers",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "minPlayers",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "prizeDistribution",
+          "type": {
+            "array": [
+              "u16",
+              5
+            ]
           },
-          {
-            "name": "totalPodiums",
-            "docs": [
-              "Total number of podium finishes (1st, 2nd, 3rd place)"
-            ],
-            "type": "u32"
+          "index": false
+        },
+        {
+          "name": "waitTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "referralCode",
+          "type": "string",
+          "index": false
+        },
+        {
+          "name": "horseNames",
+          "type": {
+            "array": [
+              "string",
+              10
+            ]
           },
-          {
-            "name": "totalEarnings",
-            "docs": [
-              "Total GOR earned from prizes"
-            ],
-            "type": "u64"
+          "index": false
+        },
+        {
+          "name": "horseAttributes",
+          "type": {
+            "array": [
+              {
+                "defined": "HorseAttributes"
+              },
+              10
+            ]
           },
-          {
-            "name": "createdAt",
-            "docs": [
-              "Account creation timestamp"
-            ],
-            "type": "i64"
+          "index": false
+        },
+        {
+          "name": "seedCommitment",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
           },
-          {
-            "name": "updatedAt",
-            "docs": [
-              "Last updated timestamp"
-            ],
-            "type": "i64"
-          }
-        ]
-      }
+          "index": false
+        },
+        {
+          "name": "creatorBond",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "startTime",
+          "type": "i64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "race",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "raceId",
-            "type": "u64"
-          },
-          {
-            "name": "creator",
-            "type": "publicKey"
-          },
-          {
-            "name": "status",
-            "type": {
-              "defined": "RaceStatus"
-            }
-          },
-          {
-            "name": "horseNames",
-            "type": {
-              "array": [
-                "string",
-                10
-              ]
-            }
-          },
-          {
-            "name": "totalPool",
-            "type": "u64"
-          },
-          {
-            "name": "platformFee",
-            "type": "u64"
-          },
-          {
-            "name": "entryCount",
-            "type": "u32"
-          },
-          {
-            "name": "maxPlayers",
-            "type": "u32"
+      "name": "PlayerJoined",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "betKind",
+          "type": {
+            "defined": "BetKind"
           },
-          {
-            "name": "waitTime",
-            "type": "i64"
+          "index": false
+        },
+        {
+          "name": "selection",
+          "type": {
+            "array": [
+              "u8",
+              3
+            ]
           },
-          {
-            "name": "referralCode",
-            "type": "string"
+          "index": false
+        },
+        {
+          "name": "stake",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "publicKey"
           },
-          {
-            "name": "startTime",
-            "type": "i64"
+          "index": false
+        },
+        {
+          "name": "newPlayer",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "totalPool",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "entryCount",
+          "type": "u32",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RaceStarted",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "raceStartTime",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "seedSlot",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "entryCount",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "totalPool",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CrankRewardPaid",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "executor",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "crankRewards",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RaceCompleted",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "revealedSeed",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
           },
-          {
-            "name": "raceStartTime",
-            "type": {
-              "option": "i64"
-            }
+          "index": false
+        },
+        {
+          "name": "raceSeed",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
           },
-          {
-            "name": "endTime",
-            "type": {
-              "option": "i64"
-            }
+          "index": false
+        },
+        {
+          "name": "winningHorses",
+          "type": {
+            "array": [
+              "u8",
+              3
+            ]
           },
-          {
-            "name": "winningHorses",
-            "type": {
-              "array": [
-                "u8",
-                3
-              ]
-            }
+          "index": false
+        },
+        {
+          "name": "finishingOrder",
+          "type": {
+            "array": [
+              "u8",
+              10
+            ]
           },
-          {
-            "name": "bump",
-            "type": "u8"
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
-    {
-      "name": "ClaimStatus",
-      "type": {
-        "kind": "enum",
-        "variants": [
-          {
-            "name": "Unclaimed"
+          "index": false
+        },
+        {
+          "name": "checkpoints",
+          "type": {
+            "array": [
+              {
+                "array": [
+                  "u8",
+                  10
+                ]
+              },
+              10
+            ]
           },
-          {
-            "name": "Claimed"
-          }
-        ]
-      }
+          "index": false
+        },
+        {
+          "name": "totalPool",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "platformFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "creatorReward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referralRewards",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "endTime",
+          "type": "i64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "RaceStatus",
-      "type": {
-        "kind": "enum",
-        "variants": [
-          {
-            "name": "Pending"
-          },
-          {
-            "name": "Racing"
+      "name": "RaceCancelled",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "reason",
+          "type": {
+            "defined": "CancelReason"
           },
-          {
-            "name": "Completed"
+          "index": false
+        },
+        {
+          "name": "endTime",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PrizeClaimed",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "prizeAmount",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RefundClaimed",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "refundAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "bondShare",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "StatsUpdated",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "bestFinish",
+          "type": {
+            "option": "u8"
           },
-          {
-            "name": "Cancelled"
-          }
-        ]
-      }
+          "index": false
+        },
+        {
+          "name": "prizeAmount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalRaces",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "totalWins",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "totalPodiums",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "totalEarnings",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RewardsClaimed",
+      "fields": [
+        {
+          "name": "beneficiary",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "totalClaimed",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ProfileCreated",
+      "fields": [
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "profile",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "username",
+          "type": "string",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ProfileRenamed",
+      "fields": [
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "profile",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "previousUsername",
+          "type": "string",
+          "index": false
+        },
+        {
+          "name": "username",
+          "type": "string",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "UsernameClaimed",
+      "fields": [
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "profile",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "username",
+          "type": "string",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ReferralSettled",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "referrer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "referrerReward",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "EntryClosed",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "player",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "referrerReward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "closedEntries",
+          "type": "u32",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "CombinationPoolClosed",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "combinationPool",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "openCombinationPools",
+          "type": "u32",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RaceClosed",
+      "fields": [
+        {
+          "name": "race",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "dust",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "releasedRewards",
+          "type": "u64",
+          "index": false
+        }
+      ]
     }
   ],
   "errors": [
@@ -1255,7 +6030,7 @@ export const IDL: GorRace = {
     {
       "code": 6003,
       "name": "AlreadyJoined",
-      "msg": "Player already joined this race"
+      "msg": "Player already backs this horse"
     },
     {
       "code": 6004,
@@ -1326,6 +6101,191 @@ export const IDL: GorRace = {
       "code": 6017,
       "name": "ProfileNotFound",
       "msg": "Player profile not found"
+    },
+    {
+      "code": 6018,
+      "name": "InvalidSeedCommitment",
+      "msg": "Seed commitment cannot be empty"
+    },
+    {
+      "code": 6019,
+      "name": "InvalidSeedReveal",
+      "msg": "Revealed seed does not match the commitment"
+    },
+    {
+      "code": 6020,
+      "name": "SlotHashUnavailable",
+      "msg": "Slot hash for the race start slot is no longer available"
+    },
+    {
+      "code": 6021,
+      "name": "RevealWindowOpen",
+      "msg": "Seed reveal window has not expired yet"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidStakeAmount",
+      "msg": "Invalid stake amount"
+    },
+    {
+      "code": 6023,
+      "name": "RaceNotCancelled",
+      "msg": "Race not cancelled"
+    },
+    {
+      "code": 6024,
+      "name": "RaceNotCancellable",
+      "msg": "Race already completed or cancelled"
+    },
+    {
+      "code": 6025,
+      "name": "MissingTokenAccounts",
+      "msg": "Token accounts are required for races paid in an SPL mint"
+    },
+    {
+      "code": 6026,
+      "name": "InvalidMint",
+      "msg": "Mint does not match the race mint"
+    },
+    {
+      "code": 6027,
+      "name": "InvalidEntryFee",
+      "msg": "Entry fee outside platform limits"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidMaxPlayers",
+      "msg": "Max players outside platform limits"
+    },
+    {
+      "code": 6029,
+      "name": "RaceNotFound",
+      "msg": "No race has been created with this id"
+    },
+    {
+      "code": 6030,
+      "name": "InvalidMinPlayers",
+      "msg": "Min players must be between MIN_PLAYERS_TO_START and max players"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidPrizeDistribution",
+      "msg": "Invalid prize distribution"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidRaceLimits",
+      "msg": "Invalid race limits"
+    },
+    {
+      "code": 6033,
+      "name": "InvalidBetSelection",
+      "msg": "Invalid bet selection"
+    },
+    {
+      "code": 6034,
+      "name": "MissingCombinationPool",
+      "msg": "Combination pool account required for exotic bets"
+    },
+    {
+      "code": 6035,
+      "name": "InvalidCombinationPool",
+      "msg": "Combination pool does not match the bet"
+    },
+    {
+      "code": 6036,
+      "name": "TooManyBets",
+      "msg": "Too many bets on this entry"
+    },
+    {
+      "code": 6037,
+      "name": "RaceNotSettled",
+      "msg": "Race is not completed or cancelled"
+    },
+    {
+      "code": 6038,
+      "name": "EntryNotSettled",
+      "msg": "Entry still has a prize or refund to claim"
+    },
+    {
+      "code": 6039,
+      "name": "RaceHasOpenAccounts",
+      "msg": "Race still has open entries or combination pools"
+    },
+    {
+      "code": 6040,
+      "name": "NoPendingAuthority",
+      "msg": "No authority transfer is pending"
+    },
+    {
+      "code": 6041,
+      "name": "Paused",
+      "msg": "This instruction is paused"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidPauseFlags",
+      "msg": "Unknown pause flags"
+    },
+    {
+      "code": 6043,
+      "name": "FeesAlreadyCollected",
+      "msg": "Platform fees were already collected for this race"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidRevenueSplit",
+      "msg": "Creator and referrer shares cannot exceed the platform fee"
+    },
+    {
+      "code": 6045,
+      "name": "InvalidCrankReward",
+      "msg": "Crank reward cannot exceed MAX_CRANK_REWARD_BPS of the pool"
+    },
+    {
+      "code": 6046,
+      "name": "InvalidReferrer",
+      "msg": "Invalid referrer"
+    },
+    {
+      "code": 6047,
+      "name": "MissingRewardAccount",
+      "msg": "Reward account is required"
+    },
+    {
+      "code": 6048,
+      "name": "NoReferralToSettle",
+      "msg": "Entry has no referrer left to credit"
+    },
+    {
+      "code": 6049,
+      "name": "NoRewards",
+      "msg": "No rewards to claim"
+    },
+    {
+      "code": 6050,
+      "name": "PrizeNotClaimed",
+      "msg": "Claim the prize before updating stats"
+    },
+    {
+      "code": 6051,
+      "name": "RevealerCannotBet",
+      "msg": "The race creator and operator cannot bet on the race"
+    },
+    {
+      "code": 6052,
+      "name": "PlatformVaultMigrated",
+      "msg": "Platform vault is not in the first release's layout"
+    },
+    {
+      "code": 6053,
+      "name": "UnsupportedMintExtension",
+      "msg": "Mint has a permanent delegate, transfer hook or is non-transferable"
+    },
+    {
+      "code": 6054,
+      "name": "TokenRaceRewardsUnsupported",
+      "msg": "Token races pay no creator or referrer rewards, set the revenue split to 0 or race in GOR"
     }
   ]
 };
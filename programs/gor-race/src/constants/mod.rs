@@ -1,21 +1,48 @@
-use anchor_lang::prelude::*;
-
 pub const MAX_HORSES: usize = 10;
 pub const ENTRY_FEE: u64 = 100_000_000; 
 pub const DEFAULT_WAIT_TIME: i64 = 60; // 60 seconds default
 pub const MIN_WAIT_TIME: i64 = 30; // 30 seconds minimum
 pub const MAX_WAIT_TIME: i64 = 180; // 3 minutes maximum
 pub const RACE_DURATION: i64 = 60; // 1 minute race simulation
+pub const RACE_CHECKPOINTS: usize = 10; // Running order is recorded this many times over the race
+pub const MIN_LEG_DISTANCE: u64 = 80; // Distance a horse covers between checkpoints
+pub const MAX_LEG_DISTANCE: u64 = 120;
+pub const MIN_HORSE_ATTRIBUTE: u8 = 40; // Speed, stamina and form are rolled in this range
+pub const MAX_HORSE_ATTRIBUTE: u8 = 100;
+pub const SPEED_DIVISOR: u64 = 4; // Each leg gains speed / SPEED_DIVISOR
+pub const FORM_DIVISOR: u64 = 10; // Each leg gains form / FORM_DIVISOR
+pub const FATIGUE_DIVISOR: u64 = 4; // Second half legs lose (MAX_HORSE_ATTRIBUTE - stamina) / FATIGUE_DIVISOR
+pub const REVEAL_TIMEOUT: i64 = 90; // Seed must be revealed within 90 seconds of the race finishing
+pub const STATS_GRACE_PERIOD: i64 = 7 * 24 * 60 * 60; // Prize-less entries may be closed without stats after a week
 pub const MIN_PLAYERS_TO_START: u32 = 1; // Minimum 1 players to start race
 pub const PLATFORM_FEE_BPS: u16 = 500; // 5%
 pub const MAX_PLAYERS_PER_RACE: u32 = 100;
+pub const MAX_CRANK_REWARD_BPS: u16 = 100; // Executors get at most 1% of a race pool per execute_race
+pub const MIN_ENTRY_FEE: u64 = 10_000_000; // 0.01 GOR default lower bound for native races
+pub const MAX_ENTRY_FEE: u64 = 100_000_000_000; // 100 GOR default upper bound for native races
+pub const CREATOR_BOND: u64 = 100_000_000; // 0.1 GOR held on each race, paid to its entries if the seed is never revealed
+pub const NATIVE_DECIMALS: u8 = 9; // Platform amounts are set in GOR base units, token races scale them to the mint
 
 pub const PLATFORM_VAULT_SEED: &[u8] = b"platform_vault";
 pub const RACE_SEED: &[u8] = b"race";
 pub const PLAYER_ENTRY_SEED: &[u8] = b"player_entry";
 pub const RACE_VAULT_SEED: &[u8] = b"race_vault";
+pub const COMBINATION_POOL_SEED: &[u8] = b"combination_pool";
+pub const REWARD_ACCOUNT_SEED: &[u8] = b"reward_account";
+pub const USERNAME_SEED: &[u8] = b"username";
+
+pub const MAX_PRIZE_PLACES: usize = 5; // Places a race prize table can pay out
+pub const PRIZE_DISTRIBUTION: [u16; MAX_PRIZE_PLACES] = [5000, 3000, 1500, 0, 0]; // 50%, 30%, 15% in basis points
+pub const BET_KINDS: usize = 5; // Win, show, exacta, quinella, trifecta
+pub const MAX_BETS_PER_ENTRY: usize = 8; // Distinct bets one player can hold on a race
+pub const ODDS_PRECISION: u64 = 10_000; // Implied odds are decimal odds scaled by this factor
 
-pub const PRIZE_DISTRIBUTION: [u16; 3] = [5000, 3000, 1500]; // 50%, 30%, 15% in basis points
+// PlatformVault pause bits, each halts one instruction. Refunds are never paused.
+pub const PAUSE_CREATE_RACE: u8 = 1 << 0;
+pub const PAUSE_JOIN_RACE: u8 = 1 << 1;
+pub const PAUSE_EXECUTE_RACE: u8 = 1 << 2;
+pub const PAUSE_CLAIM_PRIZE: u8 = 1 << 3;
+pub const PAUSE_ALL: u8 = PAUSE_CREATE_RACE | PAUSE_JOIN_RACE | PAUSE_EXECUTE_RACE | PAUSE_CLAIM_PRIZE;
 
 pub const HORSE_NAME_POOL: [&str; 30] = [
     "Bonk", "Samo", "Orca", "Raydium", "Marinade",
@@ -11,7 +11,7 @@ pub enum GorRaceError {
     #[msg("Race not ready for execution")]
     RaceNotReady,
     
-    #[msg("Player already joined this race")]
+    #[msg("Player already backs this horse")]
     AlreadyJoined,
     
     #[msg("Prize already claimed")]
@@ -55,4 +55,115 @@ pub enum GorRaceError {
     
     #[msg("Player profile not found")]
     ProfileNotFound,
-}
\ No newline at end of file
+    
+    #[msg("Seed commitment cannot be empty")]
+    InvalidSeedCommitment,
+    
+    #[msg("Revealed seed does not match the commitment")]
+    InvalidSeedReveal,
+    
+    #[msg("Slot hash for the race start slot is no longer available")]
+    SlotHashUnavailable,
+    
+    #[msg("Seed reveal window has not expired yet")]
+    RevealWindowOpen,
+    
+    #[msg("Invalid stake amount")]
+    InvalidStakeAmount,
+    
+    #[msg("Race not cancelled")]
+    RaceNotCancelled,
+    
+    #[msg("Race already completed or cancelled")]
+    RaceNotCancellable,
+    
+    #[msg("Token accounts are required for races paid in an SPL mint")]
+    MissingTokenAccounts,
+    
+    #[msg("Mint does not match the race mint")]
+    InvalidMint,
+    
+    #[msg("Entry fee outside platform limits")]
+    InvalidEntryFee,
+    
+    #[msg("Max players outside platform limits")]
+    InvalidMaxPlayers,
+    
+    #[msg("No race has been created with this id")]
+    RaceNotFound,
+    
+    #[msg("Min players must be between MIN_PLAYERS_TO_START and max players")]
+    InvalidMinPlayers,
+    
+    #[msg("Invalid prize distribution")]
+    InvalidPrizeDistribution,
+    
+    #[msg("Invalid race limits")]
+    InvalidRaceLimits,
+    
+    #[msg("Invalid bet selection")]
+    InvalidBetSelection,
+    
+    #[msg("Combination pool account required for exotic bets")]
+    MissingCombinationPool,
+    
+    #[msg("Combination pool does not match the bet")]
+    InvalidCombinationPool,
+    
+    #[msg("Too many bets on this entry")]
+    TooManyBets,
+    
+    #[msg("Race is not completed or cancelled")]
+    RaceNotSettled,
+    
+    #[msg("Entry still has a prize or refund to claim")]
+    EntryNotSettled,
+    
+    #[msg("Race still has open entries or combination pools")]
+    RaceHasOpenAccounts,
+    
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    
+    #[msg("This instruction is paused")]
+    Paused,
+    
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+    
+    #[msg("Platform fees were already collected for this race")]
+    FeesAlreadyCollected,
+    
+    #[msg("Creator and referrer shares cannot exceed the platform fee")]
+    InvalidRevenueSplit,
+    
+    #[msg("Crank reward cannot exceed MAX_CRANK_REWARD_BPS of the pool")]
+    InvalidCrankReward,
+    
+    #[msg("Invalid referrer")]
+    InvalidReferrer,
+    
+    #[msg("Reward account is required")]
+    MissingRewardAccount,
+    
+    #[msg("Entry has no referrer left to credit")]
+    NoReferralToSettle,
+    
+    #[msg("No rewards to claim")]
+    NoRewards,
+    
+    #[msg("Claim the prize before updating stats")]
+    PrizeNotClaimed,
+    
+    #[msg("The race creator and operator cannot bet on the race")]
+    RevealerCannotBet,
+    
+    #[msg("Platform vault is not in the first release's layout")]
+    PlatformVaultMigrated,
+    
+    #[msg("Mint has a permanent delegate, transfer hook or is non-transferable")]
+    UnsupportedMintExtension,
+    
+    #[msg("Token races pay no creator or referrer rewards, set the revenue split to 0 or race in GOR")]
+    TokenRaceRewardsUnsupported,
+}
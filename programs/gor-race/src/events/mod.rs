@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::{BetKind, CancelReason, HorseAttributes, RaceMode};
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub authority: Pubkey,
+    pub paused: u8,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub authority: Pubkey,
+    pub destination: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PlatformInitialized {
+    pub authority: Pubkey,
+    pub platform_fee_bps: u16,
+    pub min_entry_fee: u64,
+    pub max_entry_fee: u64,
+    pub max_players_limit: u32,
+}
+
+#[event]
+pub struct PlatformVaultMigrated {
+    pub authority: Pubkey,
+    pub platform_fee_bps: u16,
+    pub total_fees_collected: u64,
+}
+
+#[event]
+pub struct FeeUpdated {
+    pub authority: Pubkey,
+    pub previous_fee_bps: u16,
+    pub platform_fee_bps: u16,
+}
+
+#[event]
+pub struct OperatorUpdated {
+    pub authority: Pubkey,
+    pub previous_operator: Pubkey,
+    pub operator: Pubkey,
+}
+
+#[event]
+pub struct RaceLimitsUpdated {
+    pub authority: Pubkey,
+    pub min_entry_fee: u64,
+    pub max_entry_fee: u64,
+    pub max_players_limit: u32,
+}
+
+#[event]
+pub struct RevenueSplitUpdated {
+    pub authority: Pubkey,
+    pub creator_share_bps: u16,
+    pub referrer_share_bps: u16,
+}
+
+#[event]
+pub struct CrankRewardUpdated {
+    pub authority: Pubkey,
+    pub crank_reward_bps: u16,
+    pub max_crank_reward: u64,
+}
+
+#[event]
+pub struct RaceCreated {
+    pub race: Pubkey,
+    pub race_id: u64,
+    pub creator: Pubkey,
+    pub mode: RaceMode,
+    pub mint: Option<Pubkey>,
+    pub entry_fee: u64,
+    pub max_players: u32,
+    pub min_players: u32,
+    pub prize_distribution: [u16; MAX_PRIZE_PLACES],
+    pub wait_time: i64,
+    pub referral_code: String,
+    pub horse_names: [String; MAX_HORSES],
+    pub horse_attributes: [HorseAttributes; MAX_HORSES],
+    pub seed_commitment: [u8; 32],
+    pub creator_bond: u64,
+    pub start_time: i64,
+}
+
+#[event]
+pub struct PlayerJoined {
+    pub race: Pubkey,
+    pub player: Pubkey,
+    pub bet_kind: BetKind,
+    pub selection: [u8; 3],
+    pub stake: u64, // Amount that reached the vault
+    pub referrer: Option<Pubkey>,
+    pub new_player: bool, // First bet of this player on the race
+    pub total_pool: u64,
+    pub entry_count: u32,
+}
+
+#[event]
+pub struct RaceStarted {
+    pub race: Pubkey,
+    pub race_start_time: i64,
+    pub seed_slot: u64,
+    pub entry_count: u32,
+    pub total_pool: u64,
+}
+
+#[event]
+pub struct CrankRewardPaid {
+    pub race: Pubkey,
+    pub executor: Pubkey,
+    pub amount: u64,
+    pub crank_rewards: u64,
+}
+
+#[event]
+pub struct RaceCompleted {
+    pub race: Pubkey,
+    pub revealed_seed: [u8; 32],
+    pub race_seed: [u8; 32],
+    pub winning_horses: [u8; 3],
+    pub finishing_order: [u8; MAX_HORSES],
+    pub checkpoints: [[u8; MAX_HORSES]; RACE_CHECKPOINTS],
+    pub total_pool: u64,
+    pub platform_fee: u64,
+    pub creator_reward: u64,
+    pub referral_rewards: u64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct RaceCancelled {
+    pub race: Pubkey,
+    pub reason: CancelReason,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct PrizeClaimed {
+    pub race: Pubkey,
+    pub player: Pubkey,
+    pub prize_amount: u64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub race: Pubkey,
+    pub player: Pubkey,
+    pub refund_amount: u64,
+    pub bond_share: u64, // Lamports of a forfeited creator bond, paid on top of the refund
+}
+
+#[event]
+pub struct StatsUpdated {
+    pub race: Pubkey,
+    pub player: Pubkey,
+    pub best_finish: Option<u8>, // Podium place, 0 for 1st
+    pub prize_amount: u64,
+    pub total_races: u32,
+    pub total_wins: u32,
+    pub total_podiums: u32,
+    pub total_earnings: u64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct ProfileCreated {
+    pub player: Pubkey,
+    pub profile: Pubkey,
+    pub username: String,
+}
+
+#[event]
+pub struct ProfileRenamed {
+    pub player: Pubkey,
+    pub profile: Pubkey,
+    pub previous_username: String,
+    pub username: String,
+}
+
+#[event]
+pub struct UsernameClaimed {
+    pub player: Pubkey,
+    pub profile: Pubkey,
+    pub username: String,
+}
+
+#[event]
+pub struct ReferralSettled {
+    pub race: Pubkey,
+    pub player: Pubkey,
+    pub referrer: Pubkey,
+    pub referrer_reward: u64,
+}
+
+#[event]
+pub struct EntryClosed {
+    pub race: Pubkey,
+    pub player: Pubkey,
+    pub referrer_reward: u64,
+    pub closed_entries: u32,
+}
+
+#[event]
+pub struct CombinationPoolClosed {
+    pub race: Pubkey,
+    pub combination_pool: Pubkey,
+    pub open_combination_pools: u32,
+}
+
+#[event]
+pub struct RaceClosed {
+    pub race: Pubkey,
+    pub dust: u64, // Swept to the platform
+    pub released_rewards: u64, // Unpaid referral rewards returned to the platform
+}
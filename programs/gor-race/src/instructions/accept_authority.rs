@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump,
+        constraint = platform_vault.pending_authority == Some(new_authority.key()) @ GorRaceError::Unauthorized
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
+    pub new_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let platform_vault = &mut ctx.accounts.platform_vault;
+    let previous_authority = platform_vault.authority;
+    
+    platform_vault.authority = ctx.accounts.new_authority.key();
+    platform_vault.pending_authority = None;
+    
+    emit!(AuthorityTransferred {
+        previous_authority,
+        new_authority: platform_vault.authority,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump,
+        constraint = platform_vault.authority == authority.key() @ GorRaceError::Unauthorized
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+    let platform_vault = &mut ctx.accounts.platform_vault;
+    let pending_authority = platform_vault.pending_authority
+        .take()
+        .ok_or(GorRaceError::NoPendingAuthority)?;
+    
+    emit!(AuthorityTransferCancelled {
+        authority: platform_vault.authority,
+        pending_authority,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct CancelRace<'info> {
+    #[account(
+        mut,
+        constraint = race.status == RaceStatus::Pending || race.status == RaceStatus::Racing @ GorRaceError::RaceNotCancellable
+    )]
+    pub race: Account<'info, Race>,
+    
+    #[account(
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump,
+        constraint = platform_vault.authority == authority.key() @ GorRaceError::Unauthorized
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
+    pub authority: Signer<'info>,
+    
+    /// CHECK: SlotHashes is read in place, it is too large to deserialize
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<CancelRace>) -> Result<()> {
+    let race = &mut ctx.accounts.race;
+    
+    // Once racing, the result is known to whoever holds the seed. The authority may only
+    // cancel when nobody can reveal it any more, never veto a result that can still settle.
+    if race.status == RaceStatus::Racing {
+        // The start slot's hash only lands in SlotHashes from the next slot on
+        let slot_hash_expired = ctx.accounts.clock.slot > race.seed_slot
+            && find_slot_hash(&ctx.accounts.slot_hashes.try_borrow_data()?, race.seed_slot).is_none();
+        require!(
+            race.is_reveal_expired(ctx.accounts.clock.unix_timestamp) || slot_hash_expired,
+            GorRaceError::RevealWindowOpen
+        );
+    }
+    
+    // No fee has been taken yet, refunds share any crank reward already paid out of the pool
+    race.status = RaceStatus::Cancelled;
+    race.end_time = Some(ctx.accounts.clock.unix_timestamp);
+    
+    emit!(RaceCancelled {
+        race: race.key(),
+        reason: CancelReason::Authority,
+        end_time: ctx.accounts.clock.unix_timestamp,
+    });
+    
+    msg!("Race {} cancelled by authority", race.race_id);
+    
+    Ok(())
+}
@@ -1,15 +1,18 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
 
 #[derive(Accounts)]
 pub struct ClaimPrize<'info> {
     #[account(
         constraint = race.status == RaceStatus::Completed @ GorRaceError::RaceNotCompleted
     )]
-    pub race: Account<'info, Race>,
+    pub race: Box<Account<'info, Race>>,
     
     #[account(
         mut,
@@ -23,6 +26,14 @@ pub struct ClaimPrize<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
     
+    /// Counts the race on the player's profile as the prize is paid
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Option<Account<'info, PlayerProfile>>,
+    
     #[account(
         mut,
         seeds = [RACE_VAULT_SEED, race.key().as_ref()],
@@ -32,42 +43,46 @@ pub struct ClaimPrize<'info> {
     pub race_vault: AccountInfo<'info>,
     
     #[account(
-        mut,
         seeds = [PLATFORM_VAULT_SEED],
-        bump
+        bump = platform_vault.bump,
+        constraint = !platform_vault.is_paused(PAUSE_CLAIM_PRIZE) @ GorRaceError::Paused
     )]
     pub platform_vault: Account<'info, PlatformVault>,
     
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = player,
+        token::token_program = token_program
+    )]
+    pub player_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = race_vault,
+        associated_token::token_program = token_program
+    )]
+    pub race_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<ClaimPrize>) -> Result<()> {
     let race = &ctx.accounts.race;
+    let race_key = race.key();
     let player_entry = &mut ctx.accounts.player_entry;
     
-    // Calculate prize amount
-    let prize_pool = race.total_pool
-        .checked_sub(race.platform_fee)
-        .ok_or(GorRaceError::MathOverflow)?;
-    
-    let mut prize_amount = 0u64;
-    
-    // Check if player won any position
-    for (i, &winning_horse) in race.winning_horses.iter().enumerate() {
-        if winning_horse == player_entry.horse_number {
-            prize_amount = prize_pool
-                .checked_mul(PRIZE_DISTRIBUTION[i] as u64)
-                .ok_or(GorRaceError::MathOverflow)?
-                .checked_div(10000)
-                .ok_or(GorRaceError::MathOverflow)?;
-            break;
-        }
-    }
+    // Settle every bet on the entry in one go
+    let prize_amount = entry_payout(race, &race_key, &player_entry.bets, ctx.remaining_accounts, ctx.program_id)?;
     
     require!(prize_amount > 0, GorRaceError::NoPrize);
     
     // Prepare seeds for signing
-    let race_key = race.key();
     let race_vault_seeds = &[
         RACE_VAULT_SEED,
         race_key.as_ref(),
@@ -75,46 +90,65 @@ pub fn handler(ctx: Context<ClaimPrize>) -> Result<()> {
     ];
     
     // Transfer prize to player
-    transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.race_vault.to_account_info(),
-                to: ctx.accounts.player.to_account_info(),
-            },
-            &[&race_vault_seeds[..]],
-        ),
-        prize_amount,
-    )?;
-    
-    // Handle platform fee transfer if needed
-    if race.platform_fee > 0 && !ctx.accounts.platform_vault.fees_transferred {
-        // Get account info without holding a mutable reference
-        let platform_vault_account = ctx.accounts.platform_vault.to_account_info();
-        
-        transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.race_vault.to_account_info(),
-                    to: platform_vault_account,
-                },
+    match race.mint {
+        None => {
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.race_vault.to_account_info(),
+                        to: ctx.accounts.player.to_account_info(),
+                    },
+                    &[&race_vault_seeds[..]],
+                ),
+                prize_amount,
+            )?;
+        }
+        Some(race_mint) => {
+            let mint = require_token_account(&ctx.accounts.mint)?;
+            require_keys_eq!(mint.key(), race_mint, GorRaceError::InvalidMint);
+            
+            transfer_tokens(
+                require_token_account(&ctx.accounts.token_program)?,
+                mint,
+                require_token_account(&ctx.accounts.race_vault_token_account)?,
+                require_token_account(&ctx.accounts.player_token_account)?,
+                ctx.accounts.race_vault.to_account_info(),
                 &[&race_vault_seeds[..]],
-            ),
-            race.platform_fee,
-        )?;
-        
-        // Now update the platform vault fields
-        let platform_vault = &mut ctx.accounts.platform_vault;
-        platform_vault.total_fees_collected = platform_vault.total_fees_collected
-            .checked_add(race.platform_fee)
-            .ok_or(GorRaceError::MathOverflow)?;
-        platform_vault.fees_transferred = true;
+                prize_amount,
+            )?;
+        }
     }
     
     // Update player entry
     player_entry.prize_amount = prize_amount;
     player_entry.claim_status = ClaimStatus::Claimed;
     
+    emit!(PrizeClaimed {
+        race: race_key,
+        player: player_entry.player,
+        prize_amount,
+    });
+    
+    // Stats are recorded with the final prize, update_stats can still do it later
+    if let Some(player_profile) = ctx.accounts.player_profile.as_mut() {
+        if !player_entry.stats_updated {
+            let best_finish = best_finish(&player_entry.bets, &race.winning_horses);
+            player_profile.record_race(best_finish, prize_amount, Clock::get()?.unix_timestamp);
+            player_entry.stats_updated = true;
+            
+            emit!(StatsUpdated {
+                race: race_key,
+                player: player_entry.player,
+                best_finish: best_finish.map(|place| place as u8),
+                prize_amount,
+                total_races: player_profile.total_races,
+                total_wins: player_profile.total_wins,
+                total_podiums: player_profile.total_podiums,
+                total_earnings: player_profile.total_earnings,
+            });
+        }
+    }
+    
     Ok(())
 }
\ No newline at end of file
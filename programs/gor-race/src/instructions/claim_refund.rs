@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        mut,
+        constraint = race.status == RaceStatus::Cancelled @ GorRaceError::RaceNotCancelled
+    )]
+    pub race: Box<Account<'info, Race>>,
+    
+    #[account(
+        mut,
+        seeds = [PLAYER_ENTRY_SEED, race.key().as_ref(), player.key().as_ref()],
+        bump = player_entry.bump,
+        constraint = player_entry.claim_status == ClaimStatus::Unclaimed @ GorRaceError::AlreadyClaimed,
+        constraint = player_entry.player == player.key()
+    )]
+    pub player_entry: Account<'info, PlayerEntry>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [RACE_VAULT_SEED, race.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA that holds native GOR tokens
+    pub race_vault: SystemAccount<'info>,
+    
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = player,
+        token::token_program = token_program
+    )]
+    pub player_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = race_vault,
+        associated_token::token_program = token_program
+    )]
+    pub race_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimRefund>) -> Result<()> {
+    let race = &ctx.accounts.race;
+    let race_key = race.key();
+    let player_entry = &mut ctx.accounts.player_entry;
+    let refund_amount = refund_amount(player_entry.entry_amount, race.total_pool, race.crank_rewards)
+        .ok_or(GorRaceError::MathOverflow)?;
+    
+    // Prepare seeds for signing
+    let race_vault_seeds = &[
+        RACE_VAULT_SEED,
+        race_key.as_ref(),
+        &[ctx.bumps.race_vault],
+    ];
+    
+    // Return the entry amount, less its share of any crank rewards, to the player
+    match race.mint {
+        None => {
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.race_vault.to_account_info(),
+                        to: ctx.accounts.player.to_account_info(),
+                    },
+                    &[&race_vault_seeds[..]],
+                ),
+                refund_amount,
+            )?;
+        }
+        Some(race_mint) => {
+            let mint = require_token_account(&ctx.accounts.mint)?;
+            require_keys_eq!(mint.key(), race_mint, GorRaceError::InvalidMint);
+            
+            transfer_tokens(
+                require_token_account(&ctx.accounts.token_program)?,
+                mint,
+                require_token_account(&ctx.accounts.race_vault_token_account)?,
+                require_token_account(&ctx.accounts.player_token_account)?,
+                ctx.accounts.race_vault.to_account_info(),
+                &[&race_vault_seeds[..]],
+                refund_amount,
+            )?;
+        }
+    }
+    
+    // A creator who never revealed the seed pays their bond out of the race account, in lamports for any mint
+    let bond_share = if race.bond_forfeited {
+        bond_share(race.creator_bond, player_entry.entry_amount, race.total_pool)
+            .ok_or(GorRaceError::MathOverflow)?
+    } else {
+        0
+    };
+    if bond_share > 0 {
+        ctx.accounts.race.sub_lamports(bond_share)?;
+        ctx.accounts.player.add_lamports(bond_share)?;
+    }
+    
+    player_entry.claim_status = ClaimStatus::Refunded;
+    
+    emit!(RefundClaimed {
+        race: race_key,
+        player: player_entry.player,
+        refund_amount,
+        bond_share,
+    });
+    
+    msg!("Refunded {} to {}", refund_amount, player_entry.player);
+    
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        seeds = [REWARD_ACCOUNT_SEED, beneficiary.key().as_ref()],
+        bump = reward_account.bump,
+        constraint = reward_account.owed > 0 @ GorRaceError::NoRewards
+    )]
+    pub reward_account: Account<'info, RewardAccount>,
+    
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump,
+        constraint = !platform_vault.is_paused(PAUSE_CLAIM_PRIZE) @ GorRaceError::Paused
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+}
+
+pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
+    let amount = ctx.accounts.reward_account.owed;
+    
+    // Rewards were reserved out of collected fees, so the vault stays rent exempt
+    **ctx.accounts.platform_vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.beneficiary.to_account_info().try_borrow_mut_lamports()? += amount;
+    
+    let platform_vault = &mut ctx.accounts.platform_vault;
+    platform_vault.total_rewards_owed = platform_vault.total_rewards_owed
+        .checked_sub(amount)
+        .ok_or(GorRaceError::MathOverflow)?;
+    platform_vault.total_rewards_paid = platform_vault.total_rewards_paid
+        .checked_add(amount)
+        .ok_or(GorRaceError::MathOverflow)?;
+    
+    let reward_account = &mut ctx.accounts.reward_account;
+    reward_account.owed = 0;
+    reward_account.total_claimed = reward_account.total_claimed
+        .checked_add(amount)
+        .ok_or(GorRaceError::MathOverflow)?;
+    
+    emit!(RewardsClaimed {
+        beneficiary: reward_account.beneficiary,
+        amount,
+        total_claimed: reward_account.total_claimed,
+    });
+    
+    msg!("Paid {} in rewards to {}", amount, reward_account.beneficiary);
+    
+    Ok(())
+}
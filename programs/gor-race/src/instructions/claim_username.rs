@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlayerProfile, Username};
+use crate::constants::USERNAME_SEED;
+use crate::errors::GorRaceError;
+use crate::events::UsernameClaimed;
+
+#[derive(Accounts)]
+pub struct ClaimUsername<'info> {
+    #[account(
+        seeds = [b"player_profile", player_profile.player.as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Username::SIZE,
+        seeds = [USERNAME_SEED, Username::fold(&player_profile.username).as_bytes()],
+        bump
+    )]
+    pub username_account: Account<'info, Username>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimUsername>) -> Result<()> {
+    let player_profile = &ctx.accounts.player_profile;
+    let player = player_profile.player;
+
+    // Profiles created before the registry never reserved their name. When two of them
+    // differ only in case, the first to be claimed keeps it and the other has to rename.
+    let username_account = &mut ctx.accounts.username_account;
+    require!(username_account.is_available_to(player), GorRaceError::UsernameAlreadyTaken);
+    username_account.player = player;
+    username_account.profile = player_profile.key();
+    username_account.bump = ctx.bumps.username_account;
+
+    emit!(UsernameClaimed {
+        player,
+        profile: player_profile.key(),
+        username: player_profile.username.clone(),
+    });
+
+    msg!("Username {} reserved for {}", player_profile.username, player);
+
+    Ok(())
+}
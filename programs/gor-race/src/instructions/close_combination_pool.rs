@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CloseCombinationPool<'info> {
+    #[account(
+        mut,
+        constraint = race.is_settled() @ GorRaceError::RaceNotSettled,
+        // Claims read the winning pools, so they outlive every entry
+        constraint = race.all_entries_closed() @ GorRaceError::RaceHasOpenAccounts
+    )]
+    pub race: Box<Account<'info, Race>>,
+    
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            COMBINATION_POOL_SEED,
+            race.key().as_ref(),
+            &[combination_pool.bet_kind as u8],
+            &combination_pool.selection,
+        ],
+        bump = combination_pool.bump,
+        has_one = race,
+        has_one = payer
+    )]
+    pub combination_pool: Account<'info, CombinationPool>,
+    
+    /// Receives the pool rent, anyone may close a pool once the race is settled
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CloseCombinationPool>) -> Result<()> {
+    let race = &mut ctx.accounts.race;
+    
+    race.open_combination_pools = race.open_combination_pools
+        .checked_sub(1)
+        .ok_or(GorRaceError::MathOverflow)?;
+    
+    emit!(CombinationPoolClosed {
+        race: race.key(),
+        combination_pool: ctx.accounts.combination_pool.key(),
+        open_combination_pools: race.open_combination_pools,
+    });
+    
+    Ok(())
+}
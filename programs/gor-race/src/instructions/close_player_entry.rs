@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct ClosePlayerEntry<'info> {
+    #[account(
+        mut,
+        constraint = race.is_settled() @ GorRaceError::RaceNotSettled
+    )]
+    pub race: Box<Account<'info, Race>>,
+    
+    #[account(
+        mut,
+        close = player,
+        seeds = [PLAYER_ENTRY_SEED, race.key().as_ref(), player.key().as_ref()],
+        bump = player_entry.bump,
+        constraint = player_entry.player == player.key()
+    )]
+    pub player_entry: Account<'info, PlayerEntry>,
+    
+    /// Receives the entry rent, anyone may close a settled entry
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+    
+    /// Required when the entry named a referrer that settle_referral has not credited
+    #[account(
+        mut,
+        seeds = [REWARD_ACCOUNT_SEED, player_entry.referrer.unwrap_or_default().as_ref()],
+        bump = referrer_rewards.bump
+    )]
+    pub referrer_rewards: Option<Account<'info, RewardAccount>>,
+}
+
+pub fn handler(ctx: Context<ClosePlayerEntry>) -> Result<()> {
+    let race = &mut ctx.accounts.race;
+    let player_entry = &ctx.accounts.player_entry;
+    
+    // Unclaimed entries may only close once they are known to be owed nothing,
+    // and once the player counted the race or had a week to do so.
+    // The winning combination pools of any exotic bets are passed as remaining accounts.
+    if player_entry.claim_status == ClaimStatus::Unclaimed {
+        require!(race.status == RaceStatus::Completed, GorRaceError::EntryNotSettled);
+        let now = Clock::get()?.unix_timestamp;
+        let stats_window_over = race.end_time.is_some_and(|end_time| now >= end_time + STATS_GRACE_PERIOD);
+        require!(player_entry.stats_updated || stats_window_over, GorRaceError::EntryNotSettled);
+        
+        let prize_amount = entry_payout(race, &race.key(), &player_entry.bets, ctx.remaining_accounts, ctx.program_id)?;
+        require!(prize_amount == 0, GorRaceError::EntryNotSettled);
+    }
+    
+    // Referrers earn their cut of the fee reserved when the race completed,
+    // unless settle_referral already credited it
+    let mut referrer_reward = 0;
+    if player_entry.referrer.is_some() && !player_entry.referral_settled {
+        referrer_reward = race.pay_referral(player_entry.entry_amount)?;
+        if referrer_reward > 0 {
+            ctx.accounts.referrer_rewards
+                .as_mut()
+                .ok_or(GorRaceError::MissingRewardAccount)?
+                .accrue(referrer_reward)?;
+        }
+    }
+    
+    race.closed_entries = race.closed_entries
+        .checked_add(1)
+        .ok_or(GorRaceError::MathOverflow)?;
+    
+    emit!(EntryClosed {
+        race: race.key(),
+        player: player_entry.player,
+        referrer_reward,
+        closed_entries: race.closed_entries,
+    });
+    
+    msg!("Closed entry of {} ({}/{} closed)", player_entry.player, race.closed_entries, race.entry_count);
+    
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct CloseRace<'info> {
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator,
+        constraint = race.is_settled() @ GorRaceError::RaceNotSettled,
+        constraint = race.all_entries_closed() && race.open_combination_pools == 0 @ GorRaceError::RaceHasOpenAccounts
+    )]
+    pub race: Box<Account<'info, Race>>,
+    
+    /// Receives the race, vault and vault token account rent, anyone may close a settled race
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [RACE_VAULT_SEED, race.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA that holds native GOR tokens
+    pub race_vault: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
+    #[account(mut)]
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = race_vault,
+        associated_token::token_program = token_program
+    )]
+    pub race_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = platform_vault,
+        associated_token::token_program = token_program
+    )]
+    pub platform_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CloseRace>) -> Result<()> {
+    let race = &ctx.accounts.race;
+    let race_key = race.key();
+    let race_vault_seeds = &[
+        RACE_VAULT_SEED,
+        race_key.as_ref(),
+        &[ctx.bumps.race_vault],
+    ];
+    
+    // Sweep whatever rounding dust is left in the vault to the platform
+    let dust = match race.mint {
+        None => {
+            // The rent reserve funded at create_race goes back to the creator, after the dust
+            // so the vault never sits below rent before it empties
+            let balance = ctx.accounts.race_vault.lamports();
+            let vault_rent = Rent::get()?.minimum_balance(0).min(balance);
+            let dust = balance - vault_rent;
+            if dust > 0 {
+                transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.system_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.race_vault.to_account_info(),
+                            to: ctx.accounts.platform_vault.to_account_info(),
+                        },
+                        &[&race_vault_seeds[..]],
+                    ),
+                    dust,
+                )?;
+            }
+            if vault_rent > 0 {
+                transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.system_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.race_vault.to_account_info(),
+                            to: ctx.accounts.creator.to_account_info(),
+                        },
+                        &[&race_vault_seeds[..]],
+                    ),
+                    vault_rent,
+                )?;
+            }
+            dust
+        }
+        Some(race_mint) => {
+            let mint = require_token_account(&ctx.accounts.mint)?;
+            require_keys_eq!(mint.key(), race_mint, GorRaceError::InvalidMint);
+            
+            let token_program = require_token_account(&ctx.accounts.token_program)?;
+            let vault_token_account = require_token_account(&ctx.accounts.race_vault_token_account)?;
+            let dust = vault_token_account.amount;
+            
+            if dust > 0 {
+                transfer_tokens(
+                    token_program,
+                    mint,
+                    vault_token_account,
+                    require_token_account(&ctx.accounts.platform_token_account)?,
+                    ctx.accounts.race_vault.to_account_info(),
+                    &[&race_vault_seeds[..]],
+                    dust,
+                )?;
+            }
+            
+            // The vault token account was funded by the creator at race creation
+            close_token_account(
+                token_program,
+                mint,
+                vault_token_account,
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.race_vault.to_account_info(),
+                &[&race_vault_seeds[..]],
+            )?;
+            dust
+        }
+    };
+    
+    // Referral rewards nobody was credited with, e.g. rounding, go back to the platform
+    let unpaid_rewards = race.referral_rewards.saturating_sub(race.referral_rewards_paid);
+    let platform_vault = &mut ctx.accounts.platform_vault;
+    platform_vault.total_rewards_owed = platform_vault.total_rewards_owed.saturating_sub(unpaid_rewards);
+    
+    // Native dust is withdrawable like any fee, so the fee ledger keeps matching the vault
+    if race.mint.is_none() {
+        platform_vault.total_fees_collected = platform_vault.total_fees_collected
+            .checked_add(dust)
+            .ok_or(GorRaceError::MathOverflow)?;
+    }
+    
+    emit!(RaceClosed {
+        race: race_key,
+        dust,
+        released_rewards: unpaid_rewards,
+    });
+    
+    msg!("Race {} closed, {} swept to the platform vault", race.race_id, dust);
+    
+    Ok(())
+}
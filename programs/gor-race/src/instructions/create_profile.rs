@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::PlayerProfile;
+use crate::state::{PlayerProfile, Username};
+use crate::constants::USERNAME_SEED;
 use crate::errors::GorRaceError;
+use crate::events::ProfileCreated;
 
 #[derive(Accounts)]
 #[instruction(username: String)]
@@ -14,6 +16,15 @@ pub struct CreateProfile<'info> {
     )]
     pub player_profile: Account<'info, PlayerProfile>,
 
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = Username::SIZE,
+        seeds = [USERNAME_SEED, Username::fold(&username).as_bytes()],
+        bump
+    )]
+    pub username_account: Account<'info, Username>,
+
     #[account(mut)]
     pub player: Signer<'info>,
 
@@ -28,18 +39,14 @@ pub fn handler(ctx: Context<CreateProfile>, username: String) -> Result<()> {
     let current_time = ctx.accounts.clock.unix_timestamp;
 
     // Validate username
-    if username.len() > 32 {
-        return Err(GorRaceError::UsernameTooLong.into());
-    }
-    
-    if username.trim().is_empty() {
-        return Err(GorRaceError::UsernameEmpty.into());
-    }
-
-    // Check for invalid characters (only alphanumeric and basic symbols allowed)
-    if !username.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.') {
-        return Err(GorRaceError::UsernameEmpty.into()); // Reuse error for simplicity
-    }
+    PlayerProfile::validate_username(&username)?;
+
+    // Claim the name in the registry, whatever its case
+    let username_account = &mut ctx.accounts.username_account;
+    require!(username_account.is_available_to(player), GorRaceError::UsernameAlreadyTaken);
+    username_account.player = player;
+    username_account.profile = player_profile.key();
+    username_account.bump = ctx.bumps.username_account;
 
     // Initialize the player profile
     let new_profile = PlayerProfile::new(player, username.clone(), current_time)?;
@@ -52,8 +59,14 @@ pub fn handler(ctx: Context<CreateProfile>, username: String) -> Result<()> {
     player_profile.created_at = new_profile.created_at;
     player_profile.updated_at = new_profile.updated_at;
 
+    emit!(ProfileCreated {
+        player,
+        profile: player_profile.key(),
+        username: username.clone(),
+    });
+
     msg!("Player profile created for: {}", username);
     msg!("Player address: {}", player);
 
     Ok(())
-}
\ No newline at end of file
+}
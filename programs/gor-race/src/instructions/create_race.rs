@@ -1,41 +1,199 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::constants::*;
 use crate::utils::*;
 use crate::errors::GorRaceError;
+use crate::events::*;
+
+/// Per-race overrides, anything left empty falls back to the platform defaults
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RaceConfig {
+    pub entry_fee: Option<u64>, // In the base units of the race's currency
+    pub max_players: Option<u32>,
+    pub min_players: Option<u32>, // Entries needed to start, the race is cancelled with fewer
+    pub prize_distribution: Option<Vec<u16>>, // Basis points for 1st, 2nd, ... place
+}
 
 #[derive(Accounts)]
-#[instruction(race_id: u64)]
 pub struct CreateRace<'info> {
+    /// Hands out the race id, two creations in one slot get consecutive ids
+    #[account(
+        mut,
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump,
+        constraint = !platform_vault.is_paused(PAUSE_CREATE_RACE) @ GorRaceError::Paused
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
     #[account(
         init,
         payer = creator,
         space = Race::SIZE,
-        seeds = [RACE_SEED, &race_id.to_le_bytes()],
+        seeds = [RACE_SEED, &platform_vault.next_race_id.to_le_bytes()],
         bump
     )]
-    pub race: Account<'info, Race>,
+    pub race: Box<Account<'info, Race>>,
     
     #[account(mut)]
     pub creator: Signer<'info>,
     
+    /// Accrues the creator's share of the platform fee
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RewardAccount::SIZE,
+        seeds = [REWARD_ACCOUNT_SEED, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_rewards: Account<'info, RewardAccount>,
+    
+    #[account(
+        mut,
+        seeds = [RACE_VAULT_SEED, race.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a PDA that holds the race funds, it owns the vault token account
+    pub race_vault: UncheckedAccount<'info>,
+    
+    /// Mint entries are paid in, leave empty for a native GOR race
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = race_vault,
+        associated_token::token_program = token_program
+    )]
+    pub race_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = platform_vault,
+        associated_token::token_program = token_program
+    )]
+    pub platform_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    
     pub system_program: Program<'info, System>,
     
     /// CHECK: Used for getting current time
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn handler(ctx: Context<CreateRace>, race_id: u64, wait_time: Option<i64>) -> Result<()> {
+pub fn handler(
+    ctx: Context<CreateRace>,
+    wait_time: Option<i64>,
+    seed_commitment: [u8; 32],
+    mode: RaceMode,
+    config: RaceConfig,
+) -> Result<()> {
+    // Only the creator or operator can see the result before revealing it. The bond makes
+    // withholding a losing reveal cost the creator, it goes to the entries if the reveal times out.
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: ctx.accounts.race.to_account_info(),
+            },
+        ),
+        CREATOR_BOND,
+    )?;
+    
+    // Payouts are rounded down, so a native vault keeps its rent-exempt minimum
+    // until close_race and the last claim never leaves it holding sub-rent dust
+    if ctx.accounts.mint.is_none() {
+        let vault_rent = Rent::get()?.minimum_balance(0);
+        let shortfall = vault_rent.saturating_sub(ctx.accounts.race_vault.lamports());
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: ctx.accounts.race_vault.to_account_info(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+    }
+    
     let race = &mut ctx.accounts.race;
     let clock = &ctx.accounts.clock;
     
     // Validate wait_time parameter
     let wait_time = wait_time.unwrap_or(DEFAULT_WAIT_TIME);
-    require!(wait_time >= MIN_WAIT_TIME && wait_time <= MAX_WAIT_TIME, GorRaceError::InvalidWaitTime);
+    require!((MIN_WAIT_TIME..=MAX_WAIT_TIME).contains(&wait_time), GorRaceError::InvalidWaitTime);
+    
+    // The seed itself stays with the creator (or operator) until execute_race
+    require!(seed_commitment != [0u8; 32], GorRaceError::InvalidSeedCommitment);
+    
+    // Token races settle through vault token accounts created alongside the race
+    // Rewards accrue in GOR only, so a token race cannot pay the creator and referrer
+    // shares the platform promises. Token races are refused while any share is set.
+    let mint = ctx.accounts.mint.as_ref().map(|mint| mint.key());
+    if let Some(race_mint) = &ctx.accounts.mint {
+        require!(
+            ctx.accounts.platform_vault.creator_share_bps == 0 && ctx.accounts.platform_vault.referrer_share_bps == 0,
+            GorRaceError::TokenRaceRewardsUnsupported
+        );
+        require_supported_mint(race_mint)?;
+        require_token_account(&ctx.accounts.race_vault_token_account)?;
+        require_token_account(&ctx.accounts.platform_token_account)?;
+    }
+    
+    // Validate the race config against the platform limits, which are set in
+    // GOR base units and scaled to the decimals of a token race's mint
+    let platform_vault = &ctx.accounts.platform_vault;
+    let decimals = ctx.accounts.mint.as_ref().map_or(NATIVE_DECIMALS, |mint| mint.decimals);
+    let entry_fee = config.entry_fee.unwrap_or(scale_to_decimals(ENTRY_FEE, decimals));
+    require!(
+        (scale_to_decimals(platform_vault.min_entry_fee, decimals)..=scale_to_decimals(platform_vault.max_entry_fee, decimals))
+            .contains(&entry_fee),
+        GorRaceError::InvalidEntryFee
+    );
+    
+    let max_players = config.max_players.unwrap_or(platform_vault.max_players_limit);
+    require!(
+        (MIN_PLAYERS_TO_START..=platform_vault.max_players_limit).contains(&max_players),
+        GorRaceError::InvalidMaxPlayers
+    );
+    
+    let min_players = config.min_players.unwrap_or(MIN_PLAYERS_TO_START);
+    require!(
+        (MIN_PLAYERS_TO_START..=max_players).contains(&min_players),
+        GorRaceError::InvalidMinPlayers
+    );
+    
+    let mut prize_distribution = [0u16; MAX_PRIZE_PLACES];
+    match config.prize_distribution {
+        Some(table) => {
+            require!(is_valid_prize_distribution(&table), GorRaceError::InvalidPrizeDistribution);
+            prize_distribution[..table.len()].copy_from_slice(&table);
+        }
+        None => prize_distribution = PRIZE_DISTRIBUTION,
+    }
+    
+    // The race address was derived from this id, so it is taken exactly once
+    let platform_vault = &mut ctx.accounts.platform_vault;
+    let race_id = platform_vault.next_race_id;
+    platform_vault.next_race_id = race_id
+        .checked_add(1)
+        .ok_or(GorRaceError::MathOverflow)?;
     
     // Generate random horse names for this race
     let horse_names = select_random_horses(clock.slot);
+    let horse_attributes = generate_horse_attributes(clock.slot, race_id);
     
     // Generate referral code from race_id
     let referral_code = generate_referral_code(race_id);
@@ -43,19 +201,64 @@ pub fn handler(ctx: Context<CreateRace>, race_id: u64, wait_time: Option<i64>) -
     race.race_id = race_id;
     race.creator = ctx.accounts.creator.key();
     race.status = RaceStatus::Pending;
+    race.mode = mode;
+    race.mint = mint;
     race.horse_names = horse_names;
+    race.horse_attributes = horse_attributes;
     race.total_pool = 0;
+    race.horse_pools = [0; MAX_HORSES];
+    race.horse_entry_counts = [0; MAX_HORSES];
     race.platform_fee = 0;
+    race.fees_collected = false;
+    race.referred_stake = 0;
+    race.referral_rewards = 0;
+    race.referral_rewards_paid = 0;
+    race.crank_rewards = 0;
     race.entry_count = 0;
-    race.max_players = MAX_PLAYERS_PER_RACE;
+    race.closed_entries = 0;
+    race.open_combination_pools = 0;
+    race.max_players = max_players;
+    race.min_players = min_players;
+    race.entry_fee = entry_fee;
+    race.prize_distribution = prize_distribution;
     race.wait_time = wait_time;
     race.referral_code = referral_code;
     race.start_time = clock.unix_timestamp;
     race.race_start_time = None;
     race.end_time = None;
     race.winning_horses = [0, 0, 0];
+    race.finishing_order = [0; MAX_HORSES];
+    race.checkpoints = [[0; MAX_HORSES]; RACE_CHECKPOINTS];
+    race.seed_commitment = seed_commitment;
+    race.seed_slot = 0;
+    race.revealed_seed = None;
+    race.race_seed = [0u8; 32];
+    race.creator_bond = CREATOR_BOND;
+    race.bond_forfeited = false;
     race.bump = ctx.bumps.race;
+    
+    let creator_rewards = &mut ctx.accounts.creator_rewards;
+    creator_rewards.beneficiary = race.creator;
+    creator_rewards.bump = ctx.bumps.creator_rewards;
+
+    emit!(RaceCreated {
+        race: race.key(),
+        race_id,
+        creator: race.creator,
+        mode,
+        mint,
+        entry_fee,
+        max_players,
+        min_players,
+        prize_distribution,
+        wait_time,
+        referral_code: race.referral_code.clone(),
+        horse_names: race.horse_names.clone(),
+        horse_attributes,
+        seed_commitment,
+        creator_bond: CREATOR_BOND,
+        start_time: race.start_time,
+    });
 
     Ok(())
 }
-
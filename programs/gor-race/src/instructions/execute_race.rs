@@ -1,19 +1,21 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
-use solana_program::sysvar::recent_blockhashes::RecentBlockhashes;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
 use crate::utils::*;
 
 #[derive(Accounts)]
 pub struct ExecuteRace<'info> {
     #[account(
         mut,
-        constraint = (race.status == RaceStatus::Pending && race.can_start_race(clock.unix_timestamp)) || 
+        constraint = race.can_execute(clock.unix_timestamp) || 
                     (race.status == RaceStatus::Racing && race.is_race_finished(clock.unix_timestamp)) @ GorRaceError::RaceNotReady
     )]
-    pub race: Account<'info, Race>,
+    pub race: Box<Account<'info, Race>>,
     
     #[account(
         mut,
@@ -26,38 +28,101 @@ pub struct ExecuteRace<'info> {
     #[account(
         mut,
         seeds = [PLATFORM_VAULT_SEED],
-        bump
+        bump,
+        constraint = !platform_vault.is_paused(PAUSE_EXECUTE_RACE) @ GorRaceError::Paused
     )]
     pub platform_vault: Account<'info, PlatformVault>,
     
-    /// CHECK: Used for randomness generation
-    pub recent_blockhashes: Sysvar<'info, RecentBlockhashes>,
+    #[account(
+        mut,
+        seeds = [REWARD_ACCOUNT_SEED, race.creator.as_ref()],
+        bump = creator_rewards.bump
+    )]
+    pub creator_rewards: Account<'info, RewardAccount>,
+    
+    /// Anyone may start a race or cancel an unrevealed one; only the race
+    /// creator or the platform operator may reveal the seed
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    
+    /// CHECK: SlotHashes is read in place, it is too large to deserialize
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
     
     /// CHECK: Used for getting current time
     pub clock: Sysvar<'info, Clock>,
     
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = race_vault,
+        associated_token::token_program = token_program
+    )]
+    pub race_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = platform_vault,
+        associated_token::token_program = token_program
+    )]
+    pub platform_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Receives the crank reward of token races, executors without one waive it
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = executor,
+        token::token_program = token_program
+    )]
+    pub executor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<ExecuteRace>) -> Result<()> {
+pub fn handler(ctx: Context<ExecuteRace>, seed: Option<[u8; 32]>) -> Result<()> {
+    // Every call that succeeds moves the race on, so a race pays at most two
+    // crank rewards and repeated no-op calls fail without being paid
+    pay_crank_reward(ctx.accounts, ctx.bumps.race_vault)?;
+    
     let race = &mut ctx.accounts.race;
     let clock = &ctx.accounts.clock;
     
     match race.status {
         RaceStatus::Pending => {
-            // Check if we can start the race
-            require!(race.can_start_race(clock.unix_timestamp), GorRaceError::RaceNotReady);
+            // Check if the wait time is over, a full race starts right away
+            require!(race.can_execute(clock.unix_timestamp), GorRaceError::RaceNotReady);
             
-            // Check minimum players
-            if race.entry_count < MIN_PLAYERS_TO_START {
-                // Cancel race - not enough players
+            // Check the creator's minimum players
+            if !race.can_start_race(clock.unix_timestamp) {
+                // Cancel race - not enough players, entries can claim a refund
                 race.status = RaceStatus::Cancelled;
+                race.end_time = Some(clock.unix_timestamp);
+                
+                emit!(RaceCancelled {
+                    race: race.key(),
+                    reason: CancelReason::Underfilled,
+                    end_time: clock.unix_timestamp,
+                });
                 return Ok(());
             }
             
             // Start the race simulation
             race.status = RaceStatus::Racing;
             race.race_start_time = Some(clock.unix_timestamp);
+            race.seed_slot = clock.slot;
+            
+            emit!(RaceStarted {
+                race: race.key(),
+                race_start_time: clock.unix_timestamp,
+                seed_slot: clock.slot,
+                entry_count: race.entry_count,
+                total_pool: race.total_pool,
+            });
             
             msg!("Race started! {} players racing for {} seconds", race.entry_count, RACE_DURATION);
         },
@@ -66,6 +131,44 @@ pub fn handler(ctx: Context<ExecuteRace>) -> Result<()> {
             // Check if race simulation is finished
             require!(race.is_race_finished(clock.unix_timestamp), GorRaceError::RaceNotReady);
             
+            let Some(seed) = seed else {
+                // The seed was never revealed, nobody can settle the race fairly
+                require!(race.is_reveal_expired(clock.unix_timestamp), GorRaceError::RevealWindowOpen);
+                
+                race.status = RaceStatus::Cancelled;
+                race.end_time = Some(clock.unix_timestamp);
+                
+                // A pause during the race or its reveal window may have kept the seed back,
+                // the bond is only forfeited when the creator had the whole window to reveal
+                let race_start = race.race_start_time.unwrap_or_default();
+                let reveal_deadline = race_start + RACE_DURATION + REVEAL_TIMEOUT;
+                race.bond_forfeited = !ctx.accounts.platform_vault.was_execute_paused_between(race_start, reveal_deadline);
+                
+                emit!(RaceCancelled {
+                    race: race.key(),
+                    reason: CancelReason::SeedNotRevealed,
+                    end_time: clock.unix_timestamp,
+                });
+                
+                msg!("Race cancelled! Seed was not revealed in time");
+                return Ok(());
+            };
+            
+            let executor = ctx.accounts.executor.key();
+            require!(
+                executor == race.creator || executor == ctx.accounts.platform_vault.operator,
+                GorRaceError::Unauthorized
+            );
+            require!(
+                verify_seed_commitment(&seed, &race.seed_commitment),
+                GorRaceError::InvalidSeedReveal
+            );
+            
+            // The start slot hash was unknown when the seed was committed
+            let slot_hash = find_slot_hash(&ctx.accounts.slot_hashes.try_borrow_data()?, race.seed_slot)
+                .ok_or(GorRaceError::SlotHashUnavailable)?;
+            let race_seed = mix_race_seed(&seed, &slot_hash, race.race_id);
+            
             // Calculate platform fee
             let platform_fee = {
                 let platform_vault = &ctx.accounts.platform_vault;
@@ -84,40 +187,96 @@ pub fn handler(ctx: Context<ExecuteRace>) -> Result<()> {
                 &[ctx.bumps.race_vault]
             ];
             
+            // Fees are settled exactly once per race, claims never move them again
+            require!(!race.fees_collected, GorRaceError::FeesAlreadyCollected);
+            
             // Transfer platform fee
+            let mut creator_reward = 0;
             if platform_fee > 0 {
-                let platform_vault_account = ctx.accounts.platform_vault.to_account_info();
-                
-                transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.system_program.to_account_info(),
-                        Transfer {
-                            from: ctx.accounts.race_vault.to_account_info(),
-                            to: platform_vault_account,
-                        },
-                        &[seeds]
-                    ),
-                    platform_fee,
-                )?;
-                
-                let platform_vault = &mut ctx.accounts.platform_vault;
-                platform_vault.total_fees_collected = platform_vault.total_fees_collected
-                    .checked_add(platform_fee)
-                    .ok_or(GorRaceError::MathOverflow)?;
+                match race.mint {
+                    None => {
+                        let platform_vault_account = ctx.accounts.platform_vault.to_account_info();
+                        
+                        transfer(
+                            CpiContext::new_with_signer(
+                                ctx.accounts.system_program.to_account_info(),
+                                Transfer {
+                                    from: ctx.accounts.race_vault.to_account_info(),
+                                    to: platform_vault_account,
+                                },
+                                &[seeds]
+                            ),
+                            platform_fee,
+                        )?;
+                        
+                        // Only native fees are tracked, token fees stay in the platform token accounts
+                        let platform_vault = &mut ctx.accounts.platform_vault;
+                        platform_vault.total_fees_collected = platform_vault.total_fees_collected
+                            .checked_add(platform_fee)
+                            .ok_or(GorRaceError::MathOverflow)?;
+                        
+                        // The creator is credited now, referrers as their entries close
+                        creator_reward = fee_share(platform_fee, platform_vault.creator_share_bps)
+                            .ok_or(GorRaceError::MathOverflow)?;
+                        let referral_rewards = referral_rewards(
+                            platform_fee,
+                            platform_vault.referrer_share_bps,
+                            race.referred_stake,
+                            race.total_pool,
+                        ).ok_or(GorRaceError::MathOverflow)?;
+                        
+                        platform_vault.total_rewards_owed = platform_vault.total_rewards_owed
+                            .checked_add(creator_reward)
+                            .and_then(|owed| owed.checked_add(referral_rewards))
+                            .ok_or(GorRaceError::MathOverflow)?;
+                        ctx.accounts.creator_rewards.accrue(creator_reward)?;
+                        race.referral_rewards = referral_rewards;
+                    }
+                    Some(race_mint) => {
+                        let mint = require_token_account(&ctx.accounts.mint)?;
+                        require_keys_eq!(mint.key(), race_mint, GorRaceError::InvalidMint);
+                        
+                        transfer_tokens(
+                            require_token_account(&ctx.accounts.token_program)?,
+                            mint,
+                            require_token_account(&ctx.accounts.race_vault_token_account)?,
+                            require_token_account(&ctx.accounts.platform_token_account)?,
+                            ctx.accounts.race_vault.to_account_info(),
+                            &[seeds],
+                            platform_fee,
+                        )?;
+                    }
+                }
             }
             
-            // Generate race results
-            let winning_horses = generate_race_results(
-                &ctx.accounts.recent_blockhashes,
-                clock.slot,
-                race.race_id,
-            );
+            // Simulate the race, the full timeline is stored so clients can replay it
+            let timeline = simulate_race(&race_seed, &race.horse_attributes);
+            let winning_horses = timeline.podium();
             
+            race.revealed_seed = Some(seed);
+            race.race_seed = race_seed;
             race.platform_fee = platform_fee;
+            race.fees_collected = true;
             race.winning_horses = winning_horses;
+            race.finishing_order = timeline.finishing_order;
+            race.checkpoints = timeline.checkpoints;
             race.status = RaceStatus::Completed;
             race.end_time = Some(clock.unix_timestamp);
             
+            emit!(RaceCompleted {
+                race: race.key(),
+                revealed_seed: seed,
+                race_seed,
+                winning_horses,
+                finishing_order: race.finishing_order,
+                checkpoints: race.checkpoints,
+                total_pool: race.total_pool,
+                platform_fee,
+                creator_reward,
+                referral_rewards: race.referral_rewards,
+                end_time: clock.unix_timestamp,
+            });
+            
             msg!("Race completed! Winners: 1st: {}, 2nd: {}, 3rd: {}", 
                  winning_horses[0], winning_horses[1], winning_horses[2]);
         },
@@ -127,5 +286,77 @@ pub fn handler(ctx: Context<ExecuteRace>) -> Result<()> {
         }
     }
 
+    Ok(())
+}
+
+/// Pay the executor its share of the race pool, which refunds and prizes are net of
+fn pay_crank_reward(accounts: &mut ExecuteRace, race_vault_bump: u8) -> Result<()> {
+    // The cap is set in GOR base units, token races scale it to their mint's decimals
+    let max_reward = match accounts.race.mint {
+        None => accounts.platform_vault.max_crank_reward,
+        Some(race_mint) => {
+            if accounts.executor_token_account.is_none() {
+                return Ok(());
+            }
+            let mint = require_token_account(&accounts.mint)?;
+            require_keys_eq!(mint.key(), race_mint, GorRaceError::InvalidMint);
+            scale_to_decimals(accounts.platform_vault.max_crank_reward, mint.decimals)
+        }
+    };
+    let reward = crank_reward(
+        accounts.race.total_pool,
+        accounts.platform_vault.crank_reward_bps,
+        max_reward,
+    ).ok_or(GorRaceError::MathOverflow)?;
+    if reward == 0 {
+        return Ok(());
+    }
+    
+    let race_key = accounts.race.key();
+    let seeds = &[
+        RACE_VAULT_SEED,
+        race_key.as_ref(),
+        &[race_vault_bump]
+    ];
+    
+    match accounts.race.mint {
+        None => {
+            transfer(
+                CpiContext::new_with_signer(
+                    accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: accounts.race_vault.to_account_info(),
+                        to: accounts.executor.to_account_info(),
+                    },
+                    &[seeds]
+                ),
+                reward,
+            )?;
+        }
+        Some(_) => {
+            transfer_tokens(
+                require_token_account(&accounts.token_program)?,
+                require_token_account(&accounts.mint)?,
+                require_token_account(&accounts.race_vault_token_account)?,
+                require_token_account(&accounts.executor_token_account)?,
+                accounts.race_vault.to_account_info(),
+                &[seeds],
+                reward,
+            )?;
+        }
+    }
+    
+    let race = &mut accounts.race;
+    race.crank_rewards = race.crank_rewards
+        .checked_add(reward)
+        .ok_or(GorRaceError::MathOverflow)?;
+    
+    emit!(CrankRewardPaid {
+        race: race_key,
+        executor: accounts.executor.key(),
+        amount: reward,
+        crank_rewards: race.crank_rewards,
+    });
+    
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct GetOdds<'info> {
+    pub race: Account<'info, Race>,
+    
+    #[account(
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
+    /// Required for token races, the crank reward cap scales to its decimals
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+}
+
+pub fn handler(ctx: Context<GetOdds>) -> Result<[u64; MAX_HORSES]> {
+    let race = &ctx.accounts.race;
+    let platform_vault = &ctx.accounts.platform_vault;
+    
+    // Odds quote what a win bet is paid, so they take out the fee and every crank
+    // reward the race has paid or will pay when it starts and finishes
+    let platform_fee = if race.fees_collected {
+        race.platform_fee
+    } else {
+        race.total_pool
+            .checked_mul(platform_vault.platform_fee_bps as u64)
+            .ok_or(GorRaceError::MathOverflow)?
+            / 10000
+    };
+    let max_reward = match race.mint {
+        None => platform_vault.max_crank_reward,
+        Some(race_mint) => {
+            let mint = require_token_account(&ctx.accounts.mint)?;
+            require_keys_eq!(mint.key(), race_mint, GorRaceError::InvalidMint);
+            scale_to_decimals(platform_vault.max_crank_reward, mint.decimals)
+        }
+    };
+    let pending_cranks = match race.status {
+        RaceStatus::Pending => 2,
+        RaceStatus::Racing => 1,
+        RaceStatus::Completed | RaceStatus::Cancelled => 0,
+    };
+    let crank_rewards = crank_reward(race.total_pool, platform_vault.crank_reward_bps, max_reward)
+        .and_then(|reward| reward.checked_mul(pending_cranks))
+        .and_then(|pending| pending.checked_add(race.crank_rewards))
+        .ok_or(GorRaceError::MathOverflow)?;
+    
+    let prize_pool = net_prize_pool(race.total_pool, platform_fee)
+        .and_then(|pool| pool.checked_sub(crank_rewards))
+        .unwrap_or(0);
+    let win_prize_pool = if race.total_pool == 0 {
+        0
+    } else {
+        ((race.bet_pool(BetKind::Win) as u128) * (prize_pool as u128) / (race.total_pool as u128)) as u64
+    };
+    
+    Ok(implied_odds(&race.horse_pools, win_prize_pool))
+}
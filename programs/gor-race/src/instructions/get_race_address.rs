@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct GetRaceAddress<'info> {
+    #[account(
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+}
+
+pub fn handler(ctx: Context<GetRaceAddress>, race_id: u64) -> Result<Pubkey> {
+    // Ids are handed out in order, every id below the counter was created (it may be closed since)
+    require!(
+        race_id > 0 && race_id < ctx.accounts.platform_vault.next_race_id,
+        GorRaceError::RaceNotFound
+    );
+    
+    let (race, _) = Pubkey::find_program_address(&[RACE_SEED, &race_id.to_le_bytes()], ctx.program_id);
+    Ok(race)
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -28,9 +29,33 @@ pub fn handler(ctx: Context<Initialize>, platform_fee_bps: u16) -> Result<()> {
 
     let platform_vault = &mut ctx.accounts.platform_vault;
     platform_vault.authority = ctx.accounts.authority.key();
+    platform_vault.pending_authority = None;
+    platform_vault.operator = ctx.accounts.authority.key();
     platform_vault.platform_fee_bps = platform_fee_bps;
     platform_vault.total_fees_collected = 0;
+    platform_vault.total_fees_withdrawn = 0;
+    platform_vault.creator_share_bps = 0;
+    platform_vault.referrer_share_bps = 0;
+    platform_vault.total_rewards_owed = 0;
+    platform_vault.total_rewards_paid = 0;
+    platform_vault.min_entry_fee = MIN_ENTRY_FEE;
+    platform_vault.max_entry_fee = MAX_ENTRY_FEE;
+    platform_vault.max_players_limit = MAX_PLAYERS_PER_RACE;
+    platform_vault.crank_reward_bps = 0;
+    platform_vault.max_crank_reward = 0;
+    platform_vault.next_race_id = 1;
+    platform_vault.paused = 0;
+    platform_vault.execute_paused_at = 0;
+    platform_vault.execute_resumed_at = 0;
     platform_vault.bump = ctx.bumps.platform_vault;
 
+    emit!(PlatformInitialized {
+        authority: platform_vault.authority,
+        platform_fee_bps,
+        min_entry_fee: platform_vault.min_entry_fee,
+        max_entry_fee: platform_vault.max_entry_fee,
+        max_players_limit: platform_vault.max_players_limit,
+    });
+
     Ok(())
 }
\ No newline at end of file
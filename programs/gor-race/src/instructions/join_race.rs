@@ -1,20 +1,23 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
 
 #[derive(Accounts)]
+#[instruction(bet_kind: BetKind, selection: [u8; 3], referral_code: String, amount: Option<u64>, referrer: Option<Pubkey>)]
 pub struct JoinRace<'info> {
     #[account(
         mut,
-        constraint = race.status == RaceStatus::Pending @ GorRaceError::RaceNotPending,
-        constraint = race.entry_count < race.max_players @ GorRaceError::RaceFull
+        constraint = race.status == RaceStatus::Pending @ GorRaceError::RaceNotPending
     )]
-    pub race: Account<'info, Race>,
+    pub race: Box<Account<'info, Race>>,
     
     #[account(
-        init,
+        init_if_needed,
         payer = player,
         space = PlayerEntry::SIZE,
         seeds = [PLAYER_ENTRY_SEED, race.key().as_ref(), player.key().as_ref()],
@@ -25,6 +28,26 @@ pub struct JoinRace<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
     
+    // Required for exacta, quinella and trifecta bets
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = CombinationPool::SIZE,
+        seeds = [COMBINATION_POOL_SEED, race.key().as_ref(), &[bet_kind as u8], &selection],
+        bump
+    )]
+    pub combination_pool: Option<Box<Account<'info, CombinationPool>>>,
+    
+    // Required when naming a referrer, accrues their share of the platform fee
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = RewardAccount::SIZE,
+        seeds = [REWARD_ACCOUNT_SEED, referrer.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub referrer_rewards: Option<Box<Account<'info, RewardAccount>>>,
+    
     #[account(
         mut,
         seeds = [RACE_VAULT_SEED, race.key().as_ref()],
@@ -33,14 +56,53 @@ pub struct JoinRace<'info> {
     /// CHECK: This is a PDA that holds native GOR tokens
     pub race_vault: SystemAccount<'info>,
     
+    #[account(
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump,
+        constraint = !platform_vault.is_paused(PAUSE_JOIN_RACE) @ GorRaceError::Paused
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = player,
+        token::token_program = token_program
+    )]
+    pub player_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = race_vault,
+        associated_token::token_program = token_program
+    )]
+    pub race_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<JoinRace>, horse_number: u8, referral_code: String) -> Result<()> {
+pub fn handler(
+    ctx: Context<JoinRace>,
+    bet_kind: BetKind,
+    selection: [u8; 3],
+    referral_code: String,
+    amount: Option<u64>,
+    referrer: Option<Pubkey>,
+) -> Result<()> {
     require!(
-        horse_number > 0 && horse_number <= MAX_HORSES as u8,
+        selection[0] > 0 && selection[0] <= MAX_HORSES as u8,
         GorRaceError::InvalidHorseNumber
     );
+    require!(is_valid_selection(bet_kind, &selection), GorRaceError::InvalidBetSelection);
+    require!(
+        bet_kind.is_combination() || ctx.accounts.combination_pool.is_none(),
+        GorRaceError::InvalidCombinationPool
+    );
     
     // Validate referral code matches the race
     let race = &mut ctx.accounts.race;
@@ -48,35 +110,178 @@ pub fn handler(ctx: Context<JoinRace>, horse_number: u8, referral_code: String)
         race.referral_code == referral_code,
         GorRaceError::InvalidReferralCode
     );
+    
+    // Whoever can reveal the seed learns the result first, so they never bet on the race
+    let player = ctx.accounts.player.key();
+    require!(
+        player != race.creator && player != ctx.accounts.platform_vault.operator,
+        GorRaceError::RevealerCannotBet
+    );
     let player_entry = &mut ctx.accounts.player_entry;
     
-    // Transfer entry fee to race vault
-    let cpi_context = CpiContext::new(
-        ctx.accounts.system_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.player.to_account_info(),
-            to: ctx.accounts.race_vault.to_account_info(),
+    // Only new players count towards the race's player cap
+    let is_new_player = player_entry.bets.is_empty();
+    if is_new_player {
+        require!(!race.is_full(), GorRaceError::RaceFull);
+    }
+    
+    // A referrer is fixed by the first bet and earns on the whole entry, native races only
+    if let Some(referrer) = referrer {
+        require!(race.mint.is_none(), GorRaceError::TokenRaceRewardsUnsupported);
+        require!(referrer != player, GorRaceError::InvalidReferrer);
+        require!(
+            is_new_player || player_entry.referrer == Some(referrer),
+            GorRaceError::InvalidReferrer
+        );
+        
+        let referrer_rewards = ctx.accounts.referrer_rewards
+            .as_mut()
+            .ok_or(GorRaceError::MissingRewardAccount)?;
+        referrer_rewards.beneficiary = referrer;
+        referrer_rewards.bump = ctx.bumps.referrer_rewards;
+    }
+    
+    // Fixed races charge the entry fee on win bets, pari-mutuel races and
+    // every other bet kind take any stake above it
+    let stake = match (race.mode, bet_kind) {
+        (RaceMode::Fixed, BetKind::Win) => {
+            require!(amount.is_none() || amount == Some(race.entry_fee), GorRaceError::InvalidStakeAmount);
+            // Places are shared per backer, so a horse can only be backed once
+            require!(
+                !player_entry.bets.iter().any(|bet| bet.bet_kind == BetKind::Win && bet.selection == selection),
+                GorRaceError::AlreadyJoined
+            );
+            race.entry_fee
         }
-    );
-    transfer(cpi_context, ENTRY_FEE)?;
+        _ => {
+            let stake = amount.unwrap_or(race.entry_fee);
+            require!(stake >= race.entry_fee, GorRaceError::InvalidStakeAmount);
+            stake
+        }
+    };
+    
+    // Transfer stake to race vault, token races record what actually arrived
+    let stake = match race.mint {
+        None => {
+            let cpi_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: ctx.accounts.race_vault.to_account_info(),
+                }
+            );
+            transfer(cpi_context, stake)?;
+            stake
+        }
+        Some(race_mint) => {
+            let mint = require_token_account(&ctx.accounts.mint)?;
+            require_keys_eq!(mint.key(), race_mint, GorRaceError::InvalidMint);
+            
+            let vault_token_account = ctx.accounts.race_vault_token_account
+                .as_mut()
+                .ok_or(GorRaceError::MissingTokenAccounts)?;
+            let balance_before = vault_token_account.amount;
+            
+            transfer_tokens(
+                require_token_account(&ctx.accounts.token_program)?,
+                mint,
+                require_token_account(&ctx.accounts.player_token_account)?,
+                vault_token_account,
+                ctx.accounts.player.to_account_info(),
+                &[],
+                stake,
+            )?;
+            received_amount(vault_token_account, balance_before)?
+        }
+    };
     
     // Update race state
     race.total_pool = race.total_pool
-        .checked_add(ENTRY_FEE)
+        .checked_add(stake)
         .ok_or(GorRaceError::MathOverflow)?;
-    race.entry_count = race.entry_count
-        .checked_add(1)
+    let kind_index = bet_kind as usize;
+    race.bet_pools[kind_index] = race.bet_pools[kind_index]
+        .checked_add(stake)
         .ok_or(GorRaceError::MathOverflow)?;
     
-    // Initialize player entry
-    player_entry.player = ctx.accounts.player.key();
-    player_entry.race = race.key();
-    player_entry.horse_number = horse_number;
-    player_entry.entry_amount = ENTRY_FEE;
-    player_entry.claim_status = ClaimStatus::Unclaimed;
-    player_entry.prize_amount = 0;
-    player_entry.stats_updated = false;
-    player_entry.bump = ctx.bumps.player_entry;
+    let horse_index = (selection[0] - 1) as usize;
+    match bet_kind {
+        BetKind::Win => {
+            race.horse_pools[horse_index] = race.horse_pools[horse_index]
+                .checked_add(stake)
+                .ok_or(GorRaceError::MathOverflow)?;
+            race.horse_entry_counts[horse_index] = race.horse_entry_counts[horse_index]
+                .checked_add(1)
+                .ok_or(GorRaceError::MathOverflow)?;
+        }
+        BetKind::Show => {
+            race.show_pools[horse_index] = race.show_pools[horse_index]
+                .checked_add(stake)
+                .ok_or(GorRaceError::MathOverflow)?;
+        }
+        BetKind::Exacta | BetKind::Quinella | BetKind::Trifecta => {
+            let combination_pool = ctx.accounts.combination_pool
+                .as_mut()
+                .ok_or(GorRaceError::MissingCombinationPool)?;
+            
+            if combination_pool.bet_count == 0 {
+                race.open_combination_pools = race.open_combination_pools
+                    .checked_add(1)
+                    .ok_or(GorRaceError::MathOverflow)?;
+                
+                combination_pool.race = race.key();
+                combination_pool.payer = ctx.accounts.player.key();
+                combination_pool.bet_kind = bet_kind;
+                combination_pool.selection = selection;
+                combination_pool.bump = ctx.bumps.combination_pool;
+            }
+            combination_pool.total_stake = combination_pool.total_stake
+                .checked_add(stake)
+                .ok_or(GorRaceError::MathOverflow)?;
+            combination_pool.bet_count = combination_pool.bet_count
+                .checked_add(1)
+                .ok_or(GorRaceError::MathOverflow)?;
+        }
+    }
+    
+    // Initialize player entry on the first bet
+    if is_new_player {
+        player_entry.referrer = referrer;
+        race.entry_count = race.entry_count
+            .checked_add(1)
+            .ok_or(GorRaceError::MathOverflow)?;
+        
+        player_entry.player = ctx.accounts.player.key();
+        player_entry.race = race.key();
+        player_entry.claim_status = ClaimStatus::Unclaimed;
+        player_entry.prize_amount = 0;
+        player_entry.stats_updated = false;
+        player_entry.referral_settled = false;
+        player_entry.bump = ctx.bumps.player_entry;
+    }
+    player_entry.add_bet(bet_kind, selection, stake)?;
+    
+    if player_entry.referrer.is_some() {
+        race.referred_stake = race.referred_stake
+            .checked_add(stake)
+            .ok_or(GorRaceError::MathOverflow)?;
+    }
+    
+    emit!(PlayerJoined {
+        race: race.key(),
+        player: player_entry.player,
+        bet_kind,
+        selection,
+        stake,
+        referrer: player_entry.referrer,
+        new_player: is_new_player,
+        total_pool: race.total_pool,
+        entry_count: race.entry_count,
+    });
+    
+    if is_new_player && race.is_full() {
+        msg!("Race is full, execute_race can start it now");
+    }
 
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct MigratePlatformVault<'info> {
+    /// CHECK: Still in the first release's layout, which no longer deserializes as PlatformVault.
+    /// The handler checks its owner, discriminator and size before rewriting it.
+    #[account(
+        mut,
+        seeds = [PLATFORM_VAULT_SEED],
+        bump
+    )]
+    pub platform_vault: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigratePlatformVault>) -> Result<()> {
+    let vault_info = ctx.accounts.platform_vault.to_account_info();
+    require_keys_eq!(*vault_info.owner, crate::ID, GorRaceError::PlatformVaultMigrated);
+    require!(vault_info.data_len() == PlatformVault::LEGACY_SIZE, GorRaceError::PlatformVaultMigrated);
+    
+    // authority (32), platform_fee_bps (2), total_fees_collected (8), fees_transferred (1), bump (1)
+    let (authority, platform_fee_bps, total_fees_collected) = {
+        let data = vault_info.try_borrow_data()?;
+        require!(data[..8] == PlatformVault::DISCRIMINATOR, GorRaceError::PlatformVaultMigrated);
+        (
+            Pubkey::try_from(&data[8..40]).unwrap(),
+            u16::from_le_bytes(data[40..42].try_into().unwrap()),
+            u64::from_le_bytes(data[42..50].try_into().unwrap()),
+        )
+    };
+    require_keys_eq!(authority, ctx.accounts.authority.key(), GorRaceError::Unauthorized);
+    
+    // The authority pays for the larger account, fees held above rent stay withdrawable
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(PlatformVault::SIZE)
+        .saturating_sub(Rent::get()?.minimum_balance(PlatformVault::LEGACY_SIZE));
+    if rent_shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: vault_info.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+    vault_info.realloc(PlatformVault::SIZE, true)?;
+    
+    // Everything the first release did not track starts as initialize would set it
+    let platform_vault = PlatformVault {
+        authority,
+        pending_authority: None,
+        operator: authority,
+        platform_fee_bps,
+        total_fees_collected,
+        total_fees_withdrawn: 0,
+        creator_share_bps: 0,
+        referrer_share_bps: 0,
+        total_rewards_owed: 0,
+        total_rewards_paid: 0,
+        min_entry_fee: MIN_ENTRY_FEE,
+        max_entry_fee: MAX_ENTRY_FEE,
+        max_players_limit: MAX_PLAYERS_PER_RACE,
+        crank_reward_bps: 0,
+        max_crank_reward: 0,
+        next_race_id: 1,
+        paused: 0,
+        execute_paused_at: 0,
+        execute_resumed_at: 0,
+        bump: ctx.bumps.platform_vault,
+    };
+    platform_vault.try_serialize(&mut &mut vault_info.try_borrow_mut_data()?[..])?;
+    
+    emit!(PlatformVaultMigrated {
+        authority,
+        platform_fee_bps,
+        total_fees_collected,
+    });
+    
+    Ok(())
+}
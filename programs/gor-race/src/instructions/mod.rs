@@ -1,19 +1,64 @@
+// Every instruction module exposes its own `handler`; lib.rs calls them by path.
+#![allow(ambiguous_glob_reexports)]
+
+pub mod accept_authority;
+pub mod cancel_authority_transfer;
+pub mod cancel_race;
 pub mod claim_prize;
+pub mod claim_refund;
+pub mod claim_rewards;
+pub mod claim_username;
+pub mod close_combination_pool;
+pub mod close_player_entry;
+pub mod close_race;
 pub mod create_profile;
 pub mod create_race;
 pub mod execute_race;
+pub mod get_odds;
+pub mod get_race_address;
 pub mod initialize;
 pub mod join_race;
+pub mod migrate_platform_vault;
+pub mod propose_authority;
+pub mod reconcile_fees;
+pub mod rename_profile;
+pub mod set_pause;
+pub mod settle_referral;
+pub mod update_crank_reward;
+pub mod update_operator;
 pub mod update_platform_fee;
+pub mod update_race_limits;
+pub mod update_revenue_split;
 pub mod update_stats;
 pub mod withdraw_platform_fees;
 
+pub use accept_authority::*;
+pub use cancel_authority_transfer::*;
+pub use cancel_race::*;
 pub use claim_prize::*;
+pub use claim_refund::*;
+pub use claim_rewards::*;
+pub use claim_username::*;
+pub use close_combination_pool::*;
+pub use close_player_entry::*;
+pub use close_race::*;
 pub use create_profile::*;
 pub use create_race::*;
 pub use execute_race::*;
+pub use get_odds::*;
+pub use get_race_address::*;
 pub use initialize::*;
 pub use join_race::*;
+pub use migrate_platform_vault::*;
+pub use propose_authority::*;
+pub use reconcile_fees::*;
+pub use rename_profile::*;
+pub use set_pause::*;
+pub use settle_referral::*;
+pub use update_crank_reward::*;
+pub use update_operator::*;
 pub use update_platform_fee::*;
+pub use update_race_limits::*;
+pub use update_revenue_split::*;
 pub use update_stats::*;
 pub use withdraw_platform_fees::*;
\ No newline at end of file
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump,
+        constraint = platform_vault.authority == authority.key() @ GorRaceError::Unauthorized
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    let platform_vault = &mut ctx.accounts.platform_vault;
+    
+    // Nothing changes until the new authority accepts, proposing again replaces the pending one
+    platform_vault.pending_authority = Some(new_authority);
+    
+    emit!(AuthorityProposed {
+        authority: platform_vault.authority,
+        pending_authority: new_authority,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ReconcileFees<'info> {
+    #[account(
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+}
+
+/// Native fee ledger compared with the lamports the platform vault holds
+/// above its rent-exempt minimum
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FeeReconciliation {
+    pub total_fees_collected: u64,
+    pub total_fees_withdrawn: u64,
+    pub total_rewards_paid: u64,
+    pub fees_held: u64,
+    pub surplus: u64, // Held but never recorded, e.g. lamports sent straight to the vault
+    pub shortfall: u64, // Recorded and never paid out, yet missing from the vault
+}
+
+pub fn handler(ctx: Context<ReconcileFees>) -> Result<FeeReconciliation> {
+    let platform_vault = &ctx.accounts.platform_vault;
+    let vault_info = platform_vault.to_account_info();
+    
+    let rent_exempt = Rent::get()?.minimum_balance(vault_info.data_len());
+    let fees_held = vault_info.lamports().saturating_sub(rent_exempt);
+    let expected = platform_vault.total_fees_collected
+        .saturating_sub(platform_vault.total_fees_withdrawn)
+        .saturating_sub(platform_vault.total_rewards_paid);
+    
+    let report = FeeReconciliation {
+        total_fees_collected: platform_vault.total_fees_collected,
+        total_fees_withdrawn: platform_vault.total_fees_withdrawn,
+        total_rewards_paid: platform_vault.total_rewards_paid,
+        fees_held,
+        surplus: fees_held.saturating_sub(expected),
+        shortfall: expected.saturating_sub(fees_held),
+    };
+    
+    msg!("Fees collected: {}, withdrawn: {}, rewards paid: {}, held: {}, surplus: {}, shortfall: {}",
+         report.total_fees_collected, report.total_fees_withdrawn, report.total_rewards_paid,
+         report.fees_held, report.surplus, report.shortfall);
+    
+    Ok(report)
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlayerProfile, Username};
+use crate::constants::USERNAME_SEED;
+use crate::errors::GorRaceError;
+use crate::events::ProfileRenamed;
+
+#[derive(Accounts)]
+#[instruction(new_username: String)]
+pub struct RenameProfile<'info> {
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// CHECK: Registry entry of the current name, released by the rename when the player holds it.
+    /// Profiles created before the registry may have no entry, or share it with a case variant.
+    #[account(
+        mut,
+        seeds = [USERNAME_SEED, Username::fold(&player_profile.username).as_bytes()],
+        bump
+    )]
+    pub old_username_account: UncheckedAccount<'info>,
+
+    /// Same account as the old entry when only the case of the name changes
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = Username::SIZE,
+        seeds = [USERNAME_SEED, Username::fold(&new_username).as_bytes()],
+        bump
+    )]
+    pub new_username_account: Account<'info, Username>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<RenameProfile>, new_username: String) -> Result<()> {
+    let player = ctx.accounts.player.key();
+
+    PlayerProfile::validate_username(&new_username)?;
+    require!(
+        ctx.accounts.new_username_account.is_available_to(player),
+        GorRaceError::UsernameAlreadyTaken
+    );
+
+    // Release the old name, its rent goes back to the player
+    let old_username_info = ctx.accounts.old_username_account.to_account_info();
+    if old_username_info.key() != ctx.accounts.new_username_account.key() && old_username_info.owner == &crate::ID {
+        let old_username_account = Username::try_deserialize(&mut &old_username_info.try_borrow_data()?[..])?;
+        if old_username_account.player == player {
+            let player_info = ctx.accounts.player.to_account_info();
+            **player_info.try_borrow_mut_lamports()? += old_username_info.lamports();
+            **old_username_info.try_borrow_mut_lamports()? = 0;
+            old_username_info.assign(&System::id());
+            old_username_info.realloc(0, false)?;
+        }
+    }
+
+    let new_username_account = &mut ctx.accounts.new_username_account;
+    new_username_account.player = player;
+    new_username_account.profile = ctx.accounts.player_profile.key();
+    new_username_account.bump = ctx.bumps.new_username_account;
+
+    let player_profile = &mut ctx.accounts.player_profile;
+    msg!("Player {} renamed from {} to {}", player, player_profile.username, new_username);
+    let previous_username = std::mem::replace(&mut player_profile.username, new_username.clone());
+    player_profile.updated_at = ctx.accounts.clock.unix_timestamp;
+
+    emit!(ProfileRenamed {
+        player,
+        profile: player_profile.key(),
+        previous_username,
+        username: new_username,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump,
+        constraint = platform_vault.authority == authority.key() @ GorRaceError::Unauthorized
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetPause>, paused: u8) -> Result<()> {
+    require!(paused & !PAUSE_ALL == 0, GorRaceError::InvalidPauseFlags);
+    
+    let platform_vault = &mut ctx.accounts.platform_vault;
+    
+    // Remember when reveals were blocked, a creator cannot be blamed for a seed it could not reveal
+    let was_execute_paused = platform_vault.is_paused(PAUSE_EXECUTE_RACE);
+    let is_execute_paused = paused & PAUSE_EXECUTE_RACE != 0;
+    if is_execute_paused && !was_execute_paused {
+        platform_vault.execute_paused_at = Clock::get()?.unix_timestamp;
+    } else if was_execute_paused && !is_execute_paused {
+        platform_vault.execute_resumed_at = Clock::get()?.unix_timestamp;
+    }
+    
+    platform_vault.paused = paused;
+    
+    emit!(PauseUpdated {
+        authority: platform_vault.authority,
+        paused,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct SettleReferral<'info> {
+    #[account(
+        mut,
+        constraint = race.status == RaceStatus::Completed @ GorRaceError::RaceNotCompleted
+    )]
+    pub race: Box<Account<'info, Race>>,
+    
+    /// Anyone may settle, so a winner who never claims cannot hold back the referrer's reward
+    #[account(
+        mut,
+        seeds = [PLAYER_ENTRY_SEED, race.key().as_ref(), player_entry.player.as_ref()],
+        bump = player_entry.bump,
+        constraint = player_entry.referrer.is_some() && !player_entry.referral_settled @ GorRaceError::NoReferralToSettle
+    )]
+    pub player_entry: Account<'info, PlayerEntry>,
+    
+    #[account(
+        mut,
+        seeds = [REWARD_ACCOUNT_SEED, player_entry.referrer.unwrap_or_default().as_ref()],
+        bump = referrer_rewards.bump
+    )]
+    pub referrer_rewards: Account<'info, RewardAccount>,
+}
+
+pub fn handler(ctx: Context<SettleReferral>) -> Result<()> {
+    let race = &mut ctx.accounts.race;
+    let player_entry = &mut ctx.accounts.player_entry;
+    
+    let referrer_reward = race.pay_referral(player_entry.entry_amount)?;
+    ctx.accounts.referrer_rewards.accrue(referrer_reward)?;
+    player_entry.referral_settled = true;
+    
+    emit!(ReferralSettled {
+        race: race.key(),
+        player: player_entry.player,
+        referrer: ctx.accounts.referrer_rewards.beneficiary,
+        referrer_reward,
+    });
+    
+    msg!("Credited {} to the referrer of {}", referrer_reward, player_entry.player);
+    
+    Ok(())
+}
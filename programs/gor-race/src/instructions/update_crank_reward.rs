@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct UpdateCrankReward<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump,
+        constraint = platform_vault.authority == authority.key() @ GorRaceError::Unauthorized
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateCrankReward>,
+    crank_reward_bps: u16,
+    max_crank_reward: u64,
+) -> Result<()> {
+    // Paid out of the pool up to twice per race, on start and on finish or cancel
+    require!(crank_reward_bps <= MAX_CRANK_REWARD_BPS, GorRaceError::InvalidCrankReward);
+    
+    let platform_vault = &mut ctx.accounts.platform_vault;
+    platform_vault.crank_reward_bps = crank_reward_bps;
+    platform_vault.max_crank_reward = max_crank_reward;
+    
+    emit!(CrankRewardUpdated {
+        authority: ctx.accounts.authority.key(),
+        crank_reward_bps,
+        max_crank_reward,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct UpdateOperator<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump,
+        constraint = platform_vault.authority == authority.key() @ GorRaceError::Unauthorized
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateOperator>, new_operator: Pubkey) -> Result<()> {
+    let platform_vault = &mut ctx.accounts.platform_vault;
+    let previous_operator = platform_vault.operator;
+    platform_vault.operator = new_operator;
+    
+    emit!(OperatorUpdated {
+        authority: ctx.accounts.authority.key(),
+        previous_operator,
+        operator: new_operator,
+    });
+    
+    Ok(())
+}
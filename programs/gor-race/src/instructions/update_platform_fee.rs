@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct UpdatePlatformFee<'info> {
@@ -23,7 +24,14 @@ pub fn handler(ctx: Context<UpdatePlatformFee>, new_fee_bps: u16) -> Result<()>
     );
     
     let platform_vault = &mut ctx.accounts.platform_vault;
+    let previous_fee_bps = platform_vault.platform_fee_bps;
     platform_vault.platform_fee_bps = new_fee_bps;
     
+    emit!(FeeUpdated {
+        authority: ctx.accounts.authority.key(),
+        previous_fee_bps,
+        platform_fee_bps: new_fee_bps,
+    });
+    
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct UpdateRaceLimits<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump,
+        constraint = platform_vault.authority == authority.key() @ GorRaceError::Unauthorized
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateRaceLimits>,
+    min_entry_fee: u64,
+    max_entry_fee: u64,
+    max_players_limit: u32,
+) -> Result<()> {
+    require!(
+        min_entry_fee > 0 && min_entry_fee <= max_entry_fee,
+        GorRaceError::InvalidRaceLimits
+    );
+    require!(max_players_limit >= MIN_PLAYERS_TO_START, GorRaceError::InvalidRaceLimits);
+    
+    let platform_vault = &mut ctx.accounts.platform_vault;
+    platform_vault.min_entry_fee = min_entry_fee;
+    platform_vault.max_entry_fee = max_entry_fee;
+    platform_vault.max_players_limit = max_players_limit;
+    
+    emit!(RaceLimitsUpdated {
+        authority: ctx.accounts.authority.key(),
+        min_entry_fee,
+        max_entry_fee,
+        max_players_limit,
+    });
+    
+    Ok(())
+}
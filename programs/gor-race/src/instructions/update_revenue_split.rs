@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct UpdateRevenueSplit<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_VAULT_SEED],
+        bump = platform_vault.bump,
+        constraint = platform_vault.authority == authority.key() @ GorRaceError::Unauthorized
+    )]
+    pub platform_vault: Account<'info, PlatformVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateRevenueSplit>,
+    creator_share_bps: u16,
+    referrer_share_bps: u16,
+) -> Result<()> {
+    // Both shares are carved out of the platform fee
+    require!(
+        creator_share_bps as u32 + referrer_share_bps as u32 <= 10000,
+        GorRaceError::InvalidRevenueSplit
+    );
+    
+    let platform_vault = &mut ctx.accounts.platform_vault;
+    platform_vault.creator_share_bps = creator_share_bps;
+    platform_vault.referrer_share_bps = referrer_share_bps;
+    
+    emit!(RevenueSplitUpdated {
+        authority: ctx.accounts.authority.key(),
+        creator_share_bps,
+        referrer_share_bps,
+    });
+    
+    Ok(())
+}
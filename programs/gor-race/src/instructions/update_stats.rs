@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::{PlayerProfile, Race, PlayerEntry};
+use crate::state::{ClaimStatus, PlayerProfile, Race, PlayerEntry};
+use crate::errors::GorRaceError;
+use crate::events::StatsUpdated;
+use crate::utils::{best_finish, entry_payout};
 
 #[derive(Accounts)]
 pub struct UpdateStats<'info> {
@@ -24,8 +27,7 @@ pub struct UpdateStats<'info> {
     )]
     pub player_entry: Account<'info, PlayerEntry>,
 
-    /// CHECK: This is the player whose stats are being updated
-    pub player: UncheckedAccount<'info>,
+    pub player: Signer<'info>,
 
     pub clock: Sysvar<'info, Clock>,
 }
@@ -42,30 +44,34 @@ pub fn handler(ctx: Context<UpdateStats>) -> Result<()> {
         return Ok(()); // Already updated, do nothing
     }
 
-    // Add race participation
-    player_profile.add_race_participation(current_time);
-
-    // Check if player won any position
-    let horse_number = player_entry.horse_number;
-    if let Some(position) = race.winning_horses.iter().position(|&h| h == horse_number) {
-        let prize_amount = player_entry.prize_amount;
-        
-        if position == 0 {
-            // First place
-            player_profile.add_win(prize_amount, current_time);
-        } else {
-            // 2nd or 3rd place
-            player_profile.add_podium(prize_amount, current_time);
-        }
+    // Earnings are only known once the prize is claimed. The winning
+    // combination pools of any exotic bets are passed as remaining accounts.
+    if player_entry.claim_status == ClaimStatus::Unclaimed {
+        let prize_amount = entry_payout(race, &race.key(), &player_entry.bets, ctx.remaining_accounts, ctx.program_id)?;
+        require!(prize_amount == 0, GorRaceError::PrizeNotClaimed);
     }
 
+    let best_finish = best_finish(&player_entry.bets, &race.winning_horses);
+    player_profile.record_race(best_finish, player_entry.prize_amount, current_time);
+
     // Mark stats as updated
     player_entry.stats_updated = true;
 
+    emit!(StatsUpdated {
+        race: race.key(),
+        player: player_entry.player,
+        best_finish: best_finish.map(|place| place as u8),
+        prize_amount: player_entry.prize_amount,
+        total_races: player_profile.total_races,
+        total_wins: player_profile.total_wins,
+        total_podiums: player_profile.total_podiums,
+        total_earnings: player_profile.total_earnings,
+    });
+
     msg!("Stats updated for player: {}", player_profile.username);
     msg!("Total races: {}", player_profile.total_races);
     msg!("Total wins: {}", player_profile.total_wins);
     msg!("Total podiums: {}", player_profile.total_podiums);
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
+use crate::utils::*;
 
 #[derive(Accounts)]
 pub struct WithdrawPlatformFees<'info> {
@@ -17,18 +19,97 @@ pub struct WithdrawPlatformFees<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    /// Receives native fees, leave empty to pay the authority
+    #[account(mut)]
+    pub destination: Option<SystemAccount<'info>>,
+    
+    /// Mint to withdraw fees in, leave empty to withdraw native GOR
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = platform_vault,
+        associated_token::token_program = token_program
+    )]
+    pub platform_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Receives token fees, any token account of the mint
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub destination_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<WithdrawPlatformFees>, amount: u64) -> Result<()> {
-    require!(
-        **ctx.accounts.platform_vault.to_account_info().lamports.borrow() >= amount,
-        GorRaceError::InsufficientFunds
-    );
+    let authority = ctx.accounts.authority.key();
+    
+    if let Some(mint) = &ctx.accounts.mint {
+        let platform_token_account = require_token_account(&ctx.accounts.platform_token_account)?;
+        let destination_token_account = require_token_account(&ctx.accounts.destination_token_account)?;
+        require!(platform_token_account.amount >= amount, GorRaceError::InsufficientFunds);
+        
+        // Token fees are held by an associated token account owned by the platform vault
+        let platform_vault_seeds = &[
+            PLATFORM_VAULT_SEED,
+            &[ctx.accounts.platform_vault.bump],
+        ];
+        
+        transfer_tokens(
+            require_token_account(&ctx.accounts.token_program)?,
+            mint,
+            platform_token_account,
+            destination_token_account,
+            ctx.accounts.platform_vault.to_account_info(),
+            &[&platform_vault_seeds[..]],
+            amount,
+        )?;
+        
+        emit!(FeesWithdrawn {
+            authority,
+            destination: destination_token_account.key(),
+            mint: Some(mint.key()),
+            amount,
+        });
+        
+        return Ok(());
+    }
+    
+    // The platform vault is also the config account, it must stay rent exempt,
+    // and rewards owed to creators and referrers are not the platform's to withdraw
+    let platform_vault_info = ctx.accounts.platform_vault.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(platform_vault_info.data_len());
+    let withdrawable = platform_vault_info.lamports()
+        .saturating_sub(rent_exempt)
+        .saturating_sub(ctx.accounts.platform_vault.total_rewards_owed);
+    require!(amount <= withdrawable, GorRaceError::InsufficientFunds);
     
-    // Transfer native GOR from platform vault to authority
-    **ctx.accounts.platform_vault.to_account_info().try_borrow_mut_lamports()? -= amount;
-    **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += amount;
+    let destination = match &ctx.accounts.destination {
+        Some(destination) => destination.to_account_info(),
+        None => ctx.accounts.authority.to_account_info(),
+    };
+    
+    // Transfer native GOR from platform vault to the destination
+    **platform_vault_info.try_borrow_mut_lamports()? -= amount;
+    **destination.try_borrow_mut_lamports()? += amount;
+    
+    let platform_vault = &mut ctx.accounts.platform_vault;
+    platform_vault.total_fees_withdrawn = platform_vault.total_fees_withdrawn
+        .checked_add(amount)
+        .ok_or(GorRaceError::MathOverflow)?;
+    
+    emit!(FeesWithdrawn {
+        authority,
+        destination: destination.key(),
+        mint: None,
+        amount,
+    });
     
     Ok(())
-}
\ No newline at end of file
+}
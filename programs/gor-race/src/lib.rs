@@ -2,11 +2,14 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 pub mod utils;
 
+use constants::MAX_HORSES;
 use instructions::*;
+use state::{BetKind, RaceMode};
 
 declare_id!("2Xov1MEbY8DdK3MDci83RDJmAK1SHJxg4HtoiCLcBUen");
 #[program]
@@ -18,32 +21,113 @@ pub mod gor_race {
         instructions::initialize::handler(ctx, platform_fee_bps)
     }
 
-    /// Create a player profile with username
+    /// Rewrite a platform vault left in the first release's layout to the current one,
+    /// keeping its authority, fee and fee ledger (authority only)
+    pub fn migrate_platform_vault(ctx: Context<MigratePlatformVault>) -> Result<()> {
+        instructions::migrate_platform_vault::handler(ctx)
+    }
+
+    /// Create a player profile with a username no other player holds
     pub fn create_profile(ctx: Context<CreateProfile>, username: String) -> Result<()> {
         instructions::create_profile::handler(ctx, username)
     }
 
-    /// Create a new race that players can join
-    pub fn create_race(ctx: Context<CreateRace>, race_id: u64, wait_time: Option<i64>) -> Result<()> {
-        instructions::create_race::handler(ctx, race_id, wait_time)
+    /// Change a profile's username, releasing the old name for others
+    pub fn rename_profile(ctx: Context<RenameProfile>, new_username: String) -> Result<()> {
+        instructions::rename_profile::handler(ctx, new_username)
+    }
+
+    /// Reserve the username of a profile created before the username registry (permissionless)
+    pub fn claim_username(ctx: Context<ClaimUsername>) -> Result<()> {
+        instructions::claim_username::handler(ctx)
+    }
+
+    /// Create a new race that players can join, numbered by the platform's race counter.
+    /// The creator posts CREATOR_BOND, forfeited to the entries if the seed is never revealed
+    pub fn create_race(
+        ctx: Context<CreateRace>,
+        wait_time: Option<i64>,
+        seed_commitment: [u8; 32],
+        mode: RaceMode,
+        config: RaceConfig,
+    ) -> Result<()> {
+        instructions::create_race::handler(ctx, wait_time, seed_commitment, mode, config)
+    }
+
+    /// Join a race by placing a win, show, exacta, quinella or trifecta bet,
+    /// paying the entry fee or staking any amount above it, optionally naming a referrer
+    pub fn join_race(
+        ctx: Context<JoinRace>,
+        bet_kind: BetKind,
+        selection: [u8; 3],
+        referral_code: String,
+        amount: Option<u64>,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::join_race::handler(ctx, bet_kind, selection, referral_code, amount, referrer)
+    }
+
+    /// Address of the race with `race_id`, which must already be created (read-only)
+    pub fn get_race_address(ctx: Context<GetRaceAddress>, race_id: u64) -> Result<Pubkey> {
+        instructions::get_race_address::handler(ctx, race_id)
     }
 
-    /// Join a race by selecting a horse and paying entry fee
-    pub fn join_race(ctx: Context<JoinRace>, horse_number: u8, referral_code: String) -> Result<()> {
-        instructions::join_race::handler(ctx, horse_number, referral_code)
+    /// Implied decimal odds for each horse, scaled by ODDS_PRECISION (read-only)
+    pub fn get_odds(ctx: Context<GetOdds>) -> Result<[u64; MAX_HORSES]> {
+        instructions::get_odds::handler(ctx)
     }
 
-    /// Execute the race after timeout or when conditions are met
-    pub fn execute_race(ctx: Context<ExecuteRace>) -> Result<()> {
-        instructions::execute_race::handler(ctx)
+    /// Execute the race after timeout or when conditions are met,
+    /// revealing the committed seed to finish it
+    pub fn execute_race(ctx: Context<ExecuteRace>, seed: Option<[u8; 32]>) -> Result<()> {
+        instructions::execute_race::handler(ctx, seed)
     }
 
-    /// Claim prize after race completion
+    /// Claim prize after race completion, counting the race on the player's profile if passed
     pub fn claim_prize(ctx: Context<ClaimPrize>) -> Result<()> {
         instructions::claim_prize::handler(ctx)
     }
 
-    /// Update player statistics after race completion
+    /// Claim back the entry amount from a cancelled race, plus a share of the creator bond
+    /// when the seed was never revealed
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        instructions::claim_refund::handler(ctx)
+    }
+
+    /// Claim creator and referrer rewards accrued from platform fees
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::claim_rewards::handler(ctx)
+    }
+
+    /// Emergency cancel a stuck race so entries can be refunded (authority only). A racing
+    /// race can only be cancelled once its seed can no longer be revealed
+    pub fn cancel_race(ctx: Context<CancelRace>) -> Result<()> {
+        instructions::cancel_race::handler(ctx)
+    }
+
+    /// Credit an entry's referrer once the race completes, without waiting for the entry
+    /// to close (permissionless)
+    pub fn settle_referral(ctx: Context<SettleReferral>) -> Result<()> {
+        instructions::settle_referral::handler(ctx)
+    }
+
+    /// Close a claimed, refunded or prize-less entry, returning its rent to the player
+    pub fn close_player_entry(ctx: Context<ClosePlayerEntry>) -> Result<()> {
+        instructions::close_player_entry::handler(ctx)
+    }
+
+    /// Close an exotic bet pool once every entry is closed, returning its rent to the payer
+    pub fn close_combination_pool(ctx: Context<CloseCombinationPool>) -> Result<()> {
+        instructions::close_combination_pool::handler(ctx)
+    }
+
+    /// Close a fully settled race, sweeping vault dust to the platform
+    /// and returning rent to the creator
+    pub fn close_race(ctx: Context<CloseRace>) -> Result<()> {
+        instructions::close_race::handler(ctx)
+    }
+
+    /// Count a completed race on the player's profile when claim_prize did not
     pub fn update_stats(ctx: Context<UpdateStats>) -> Result<()> {
         instructions::update_stats::handler(ctx)
     }
@@ -56,6 +140,67 @@ pub mod gor_race {
         instructions::update_platform_fee::handler(ctx, new_fee_bps)
     }
 
+    /// Update the entry fee and player limits races are validated against (authority only).
+    /// Entry fees are in GOR base units, token races scale them to the mint's decimals.
+    pub fn update_race_limits(
+        ctx: Context<UpdateRaceLimits>,
+        min_entry_fee: u64,
+        max_entry_fee: u64,
+        max_players_limit: u32,
+    ) -> Result<()> {
+        instructions::update_race_limits::handler(ctx, min_entry_fee, max_entry_fee, max_players_limit)
+    }
+
+    /// Set the creator and referrer shares of the platform fee in basis points (authority only).
+    /// Rewards are paid in GOR, token races can only be created while both are 0.
+    pub fn update_revenue_split(
+        ctx: Context<UpdateRevenueSplit>,
+        creator_share_bps: u16,
+        referrer_share_bps: u16,
+    ) -> Result<()> {
+        instructions::update_revenue_split::handler(ctx, creator_share_bps, referrer_share_bps)
+    }
+
+    /// Set the share of a race pool paid to whoever executes it, capped per call (authority only).
+    /// The cap is in GOR base units, token races scale it to the mint's decimals.
+    pub fn update_crank_reward(
+        ctx: Context<UpdateCrankReward>,
+        crank_reward_bps: u16,
+        max_crank_reward: u64,
+    ) -> Result<()> {
+        instructions::update_crank_reward::handler(ctx, crank_reward_bps, max_crank_reward)
+    }
+
+    /// Set the operator allowed to reveal race seeds (authority only)
+    pub fn update_operator(ctx: Context<UpdateOperator>, new_operator: Pubkey) -> Result<()> {
+        instructions::update_operator::handler(ctx, new_operator)
+    }
+
+    /// Pause or resume instructions with a bitmask of PAUSE_* flags (authority only)
+    pub fn set_pause(ctx: Context<SetPause>, paused: u8) -> Result<()> {
+        instructions::set_pause::handler(ctx, paused)
+    }
+
+    /// Propose a new platform authority, which takes over once it accepts (authority only)
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    /// Accept a pending authority transfer (pending authority only)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    /// Drop a pending authority transfer (authority only)
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        instructions::cancel_authority_transfer::handler(ctx)
+    }
+
+    /// Compare recorded native platform fees with the platform vault balance (read-only)
+    pub fn reconcile_fees(ctx: Context<ReconcileFees>) -> Result<FeeReconciliation> {
+        instructions::reconcile_fees::handler(ctx)
+    }
+
     /// Withdraw platform fees (authority only)
     pub fn withdraw_platform_fees(
         ctx: Context<WithdrawPlatformFees>,
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use super::BetKind;
+
+#[account]
+pub struct CombinationPool {
+    pub race: Pubkey,
+    pub payer: Pubkey, // Player who funded the account rent, refunded when it is closed
+    pub bet_kind: BetKind,
+    pub selection: [u8; 3],
+    pub total_stake: u64, // Stake on this exact combination
+    pub bet_count: u32,
+    pub bump: u8,
+}
+
+impl CombinationPool {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // race
+        32 + // payer
+        1 + // bet_kind enum
+        3 + // selection
+        8 + // total_stake
+        4 + // bet_count
+        1; // bump
+}
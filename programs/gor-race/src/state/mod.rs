@@ -1,9 +1,15 @@
+pub mod combination_pool;
 pub mod platform_vault;
 pub mod player_entry;
 pub mod player_profile;
 pub mod race;
+pub mod reward_account;
+pub mod username;
 
+pub use combination_pool::*;
 pub use platform_vault::*;
 pub use player_entry::*;
 pub use player_profile::*;
-pub use race::*;
\ No newline at end of file
+pub use race::*;
+pub use reward_account::*;
+pub use username::*;
@@ -3,17 +3,65 @@ use anchor_lang::prelude::*;
 #[account]
 pub struct PlatformVault {
     pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>, // Proposed authority, takes over once it accepts
+    pub operator: Pubkey, // May commit and reveal race seeds on behalf of creators
     pub platform_fee_bps: u16, // Basis points (100 = 1%)
     pub total_fees_collected: u64,
-    pub fees_transferred: bool, // Track if fees were transferred for current race
+    pub total_fees_withdrawn: u64, // Native fees paid out by withdraw_platform_fees
+    pub creator_share_bps: u16, // Share of a race's platform fee owed to its creator
+    pub referrer_share_bps: u16, // Share of a referred stake's platform fee owed to the referrer
+    pub total_rewards_owed: u64, // Reserved for creators and referrers, never withdrawable
+    pub total_rewards_paid: u64,
+    pub min_entry_fee: u64, // Entry fee bounds in GOR base units, scaled to the mint for token races
+    pub max_entry_fee: u64,
+    pub max_players_limit: u32, // Highest max_players a race may set
+    pub crank_reward_bps: u16, // Share of a race pool paid to whoever moves it forward with execute_race
+    pub max_crank_reward: u64, // Cap on one crank reward in GOR base units, scaled to the mint for token races, 0 for no cap
+    pub next_race_id: u64, // Taken by the next create_race, races are numbered from 1
+    pub paused: u8, // Bitmask of PAUSE_* flags
+    pub execute_paused_at: i64, // When PAUSE_EXECUTE_RACE was last set, 0 if never
+    pub execute_resumed_at: i64, // When PAUSE_EXECUTE_RACE was last cleared, 0 if never
     pub bump: u8,
 }
 
 impl PlatformVault {
     pub const SIZE: usize = 8 + // discriminator
+        32 + // authority
+        1 + 32 + // pending_authority Option
+        32 + // operator
+        2 + // platform_fee_bps
+        8 + // total_fees_collected
+        8 + // total_fees_withdrawn
+        2 + // creator_share_bps
+        2 + // referrer_share_bps
+        8 + // total_rewards_owed
+        8 + // total_rewards_paid
+        8 + // min_entry_fee
+        8 + // max_entry_fee
+        4 + // max_players_limit
+        2 + // crank_reward_bps
+        8 + // max_crank_reward
+        8 + // next_race_id
+        1 + // paused
+        8 + // execute_paused_at
+        8 + // execute_resumed_at
+        1; // bump
+    
+    /// Size of the first release's layout, before anything after `total_fees_collected`
+    /// but `bump` was added and `fees_transferred` was retired
+    pub const LEGACY_SIZE: usize = 8 + // discriminator
         32 + // authority
         2 + // platform_fee_bps
         8 + // total_fees_collected
         1 + // fees_transferred
         1; // bump
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
+    
+    /// Whether execute_race was paused at any point between `start` and `end`
+    pub fn was_execute_paused_between(&self, start: i64, end: i64) -> bool {
+        self.execute_paused_at != 0 && self.execute_paused_at < end && self.execute_resumed_at > start
+    }
 }
@@ -1,20 +1,69 @@
 use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum ClaimStatus {
     Unclaimed,
     Claimed,
+    Refunded, // Entry amount returned from a cancelled race
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BetKind {
+    Win,      // Horse finishes first
+    Show,     // Horse finishes in the top three
+    Exacta,   // First and second in order
+    Quinella, // First and second in either order
+    Trifecta, // First, second and third in order
+}
+
+impl BetKind {
+    /// Number of horses a selection of this kind names
+    pub fn selection_len(&self) -> usize {
+        match self {
+            BetKind::Win | BetKind::Show => 1,
+            BetKind::Exacta | BetKind::Quinella => 2,
+            BetKind::Trifecta => 3,
+        }
+    }
+
+    /// Exotic bets are settled from a per-combination pool account
+    pub fn is_combination(&self) -> bool {
+        self.selection_len() > 1
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Bet {
+    pub bet_kind: BetKind,
+    pub selection: [u8; 3], // Horses backed, unused places are 0
+    pub stake: u64,
+}
+
+impl Bet {
+    pub const SIZE: usize = 1 + // bet_kind enum
+        3 + // selection
+        8; // stake
+
+    /// First horse of the selection
+    pub fn horse_number(&self) -> u8 {
+        self.selection[0]
+    }
+}
+
+/// A player's ticket for one race, holding every bet they placed on it
 #[account]
 pub struct PlayerEntry {
     pub player: Pubkey,
     pub race: Pubkey,
-    pub horse_number: u8,
-    pub entry_amount: u64,
+    pub bets: Vec<Bet>,
+    pub entry_amount: u64, // Total stake across all bets
+    pub referrer: Option<Pubkey>, // Earns a share of the fee on this entry's stake
     pub claim_status: ClaimStatus,
     pub prize_amount: u64,
     pub stats_updated: bool,
+    pub referral_settled: bool, // Referrer already credited for this entry
     pub bump: u8,
 }
 
@@ -22,10 +71,33 @@ impl PlayerEntry {
     pub const SIZE: usize = 8 + // discriminator
         32 + // player
         32 + // race
-        1 + // horse_number
+        4 + (Bet::SIZE * MAX_BETS_PER_ENTRY) + // bets
         8 + // entry_amount
+        1 + 32 + // referrer Option
         1 + // claim_status
         8 + // prize_amount
         1 + // stats_updated
+        1 + // referral_settled
         1; // bump
-}
\ No newline at end of file
+
+    /// Add stake to a matching bet or place a new one
+    pub fn add_bet(&mut self, bet_kind: BetKind, selection: [u8; 3], stake: u64) -> Result<()> {
+        match self.bets.iter_mut().find(|bet| bet.bet_kind == bet_kind && bet.selection == selection) {
+            Some(bet) => {
+                bet.stake = bet.stake
+                    .checked_add(stake)
+                    .ok_or(GorRaceError::MathOverflow)?;
+            }
+            None => {
+                require!(self.bets.len() < MAX_BETS_PER_ENTRY, GorRaceError::TooManyBets);
+                self.bets.push(Bet { bet_kind, selection, stake });
+            }
+        }
+        
+        self.entry_amount = self.entry_amount
+            .checked_add(stake)
+            .ok_or(GorRaceError::MathOverflow)?;
+        
+        Ok(())
+    }
+}
@@ -32,13 +32,7 @@ impl PlayerProfile {
         8; // updated_at
 
     pub fn new(player: Pubkey, username: String, current_time: i64) -> Result<Self> {
-        if username.len() > 32 {
-            return Err(crate::errors::GorRaceError::UsernameTooLong.into());
-        }
-        
-        if username.trim().is_empty() {
-            return Err(crate::errors::GorRaceError::UsernameEmpty.into());
-        }
+        Self::validate_username(&username)?;
 
         Ok(Self {
             player,
@@ -52,6 +46,37 @@ impl PlayerProfile {
         })
     }
 
+    /// Usernames are 1-32 ASCII letters, digits, `_`, `-` or `.`, so case
+    /// folding never changes their length
+    pub fn validate_username(username: &str) -> Result<()> {
+        if username.len() > 32 {
+            return Err(crate::errors::GorRaceError::UsernameTooLong.into());
+        }
+        
+        if username.trim().is_empty() {
+            return Err(crate::errors::GorRaceError::UsernameEmpty.into());
+        }
+
+        // Check for invalid characters (only alphanumeric and basic symbols allowed)
+        if !username.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.') {
+            return Err(crate::errors::GorRaceError::UsernameEmpty.into()); // Reuse error for simplicity
+        }
+
+        Ok(())
+    }
+
+    /// Count a settled race, `best_finish` is the best podium place (0 for 1st)
+    pub fn record_race(&mut self, best_finish: Option<usize>, prize_amount: u64, current_time: i64) {
+        self.add_race_participation(current_time);
+        
+        match best_finish {
+            Some(0) => self.add_win(prize_amount, current_time),
+            Some(_) => self.add_podium(prize_amount, current_time),
+            // Lower Fixed places, returned stakes and exotic no-hit returns still pay
+            None => self.add_earnings(prize_amount, current_time),
+        }
+    }
+
     pub fn add_race_participation(&mut self, current_time: i64) {
         self.total_races += 1;
         self.updated_at = current_time;
@@ -70,6 +95,11 @@ impl PlayerProfile {
         self.updated_at = current_time;
     }
 
+    pub fn add_earnings(&mut self, prize_amount: u64, current_time: i64) {
+        self.total_earnings += prize_amount;
+        self.updated_at = current_time;
+    }
+
     pub fn win_rate(&self) -> f64 {
         if self.total_races == 0 {
             0.0
@@ -85,4 +115,28 @@ impl PlayerProfile {
             (self.total_podiums as f64) / (self.total_races as f64)
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_race() {
+        let mut profile = PlayerProfile::new(Pubkey::default(), "rider".to_string(), 0).unwrap();
+        
+        profile.record_race(Some(0), 500, 1);
+        profile.record_race(Some(2), 100, 2);
+        assert_eq!((profile.total_races, profile.total_wins, profile.total_podiums), (2, 1, 2));
+        assert_eq!(profile.total_earnings, 600);
+        
+        // A paid entry off the podium, e.g. a 4th place prize, still earns
+        profile.record_race(None, 40, 3);
+        assert_eq!((profile.total_races, profile.total_wins, profile.total_podiums), (3, 1, 2));
+        assert_eq!(profile.total_earnings, 640);
+        assert_eq!(profile.updated_at, 3);
+        
+        profile.record_race(None, 0, 4);
+        assert_eq!(profile.total_earnings, 640);
+    }
+}
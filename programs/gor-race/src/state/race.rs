@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
+use crate::errors::GorRaceError;
+use crate::utils::{net_prize_pool, referral_reward_share};
+use super::BetKind;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum RaceStatus {
@@ -9,22 +12,73 @@ pub enum RaceStatus {
     Cancelled,  // Not enough players
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReason {
+    Underfilled,     // Wait time ran out with fewer than the race's minimum players
+    SeedNotRevealed, // Nobody revealed the seed before the reveal window closed
+    Authority,       // Cancelled by the platform authority
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RaceMode {
+    Fixed,      // Flat entry fee, podium paid from PRIZE_DISTRIBUTION
+    PariMutuel, // Free stakes, winning horse's backers split the pool
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct HorseAttributes {
+    pub speed: u8,   // Distance gained on every leg
+    pub stamina: u8, // Resistance to tiring over the second half of the race
+    pub form: u8,    // Recent form, a smaller boost on every leg
+}
+
+impl HorseAttributes {
+    pub const SIZE: usize = 1 + // speed
+        1 + // stamina
+        1; // form
+}
+
 #[account]
 pub struct Race {
     pub race_id: u64,
     pub creator: Pubkey,
     pub status: RaceStatus,
+    pub mode: RaceMode,
+    pub mint: Option<Pubkey>, // SPL or Token-2022 mint entries are paid in, None for native GOR
     pub horse_names: [String; MAX_HORSES],
+    pub horse_attributes: [HorseAttributes; MAX_HORSES], // Public before betting opens
     pub total_pool: u64,
+    pub horse_pools: [u64; MAX_HORSES], // Win stake on each horse
+    pub horse_entry_counts: [u32; MAX_HORSES], // Number of win bets on each horse
+    pub show_pools: [u64; MAX_HORSES], // Show stake on each horse
+    pub bet_pools: [u64; BET_KINDS], // Total stake per bet kind, indexed by BetKind
     pub platform_fee: u64,
+    pub fees_collected: bool, // Set once platform_fee has moved to the platform vault
+    pub referred_stake: u64, // Stake placed by entries that named a referrer
+    pub referral_rewards: u64, // Reserved for referrers when the fee is collected
+    pub referral_rewards_paid: u64, // Credited to referrers as their entries settle
+    pub crank_rewards: u64, // Paid out of the pool to whoever executed the race
     pub entry_count: u32,
+    pub closed_entries: u32, // Player entries closed after settling, the race closes once all are
+    pub open_combination_pools: u32,
     pub max_players: u32,
+    pub min_players: u32, // Entries needed to start, set by the creator
+    pub entry_fee: u64, // Fixed race entry fee, minimum stake in pari-mutuel races
+    pub prize_distribution: [u16; MAX_PRIZE_PLACES], // Basis points per place, 0 pays nothing
     pub wait_time: i64, // in seconds
     pub referral_code: String,
     pub start_time: i64, // When race was created (for wait time)
     pub race_start_time: Option<i64>, // When actual race simulation begins
     pub end_time: Option<i64>,
     pub winning_horses: [u8; 3], // 1st, 2nd, 3rd place horse numbers
+    pub finishing_order: [u8; MAX_HORSES], // Every horse number from 1st to last
+    pub checkpoints: [[u8; MAX_HORSES]; RACE_CHECKPOINTS], // Running order at each checkpoint
+    pub seed_commitment: [u8; 32], // sha256 of the seed revealed in execute_race
+    pub seed_slot: u64, // Slot the race started in, its slot hash is mixed into the result
+    pub revealed_seed: Option<[u8; 32]>,
+    pub race_seed: [u8; 32], // Final randomness the results were derived from
+    pub creator_bond: u64, // Lamports held on the race account, returned with its rent at close_race
+    pub bond_forfeited: bool, // Seed never revealed, refunds share the bond
     pub bump: u8,
 }
 
@@ -33,19 +87,94 @@ impl Race {
         8 + // race_id
         32 + // creator
         1 + // status enum
+        1 + // mode enum
+        1 + 32 + // mint Option
         (32 * MAX_HORSES) + // horse_names (assuming max 32 chars each)
+        (HorseAttributes::SIZE * MAX_HORSES) + // horse_attributes
         8 + // total_pool
+        (8 * MAX_HORSES) + // horse_pools
+        (4 * MAX_HORSES) + // horse_entry_counts
+        (8 * MAX_HORSES) + // show_pools
+        (8 * BET_KINDS) + // bet_pools
         8 + // platform_fee
+        1 + // fees_collected
+        8 + // referred_stake
+        8 + // referral_rewards
+        8 + // referral_rewards_paid
+        8 + // crank_rewards
         4 + // entry_count
+        4 + // closed_entries
+        4 + // open_combination_pools
         4 + // max_players
+        4 + // min_players
+        8 + // entry_fee
+        (2 * MAX_PRIZE_PLACES) + // prize_distribution
         8 + // wait_time
         16 + // referral_code (up to 16 chars)
         8 + // start_time
         1 + 8 + // race_start_time Option
         1 + 8 + // end_time Option
         3 + // winning_horses
+        MAX_HORSES + // finishing_order
+        (MAX_HORSES * RACE_CHECKPOINTS) + // checkpoints
+        32 + // seed_commitment
+        8 + // seed_slot
+        1 + 32 + // revealed_seed Option
+        32 + // race_seed
+        8 + // creator_bond
+        1 + // bond_forfeited
         1; // bump
 
+    pub fn horse_pool(&self, horse_number: u8) -> u64 {
+        self.horse_pools[(horse_number - 1) as usize]
+    }
+
+    pub fn horse_entry_count(&self, horse_number: u8) -> u32 {
+        self.horse_entry_counts[(horse_number - 1) as usize]
+    }
+
+    pub fn bet_pool(&self, bet_kind: BetKind) -> u64 {
+        self.bet_pools[bet_kind as usize]
+    }
+
+    /// Share of a bet kind's pool left for bettors once the platform fee and
+    /// crank rewards, which are charged on the whole race, have been taken proportionally
+    pub fn net_pool(&self, bet_kind: BetKind) -> Option<u64> {
+        if self.total_pool == 0 {
+            return Some(0);
+        }
+        
+        let prize_pool = net_prize_pool(self.total_pool, self.platform_fee)?.checked_sub(self.crank_rewards)?;
+        let net = (self.bet_pool(bet_kind) as u128)
+            .checked_mul(prize_pool as u128)?
+            .checked_div(self.total_pool as u128)?;
+        
+        u64::try_from(net).ok()
+    }
+
+    /// Take an entry's cut of the reserved referral rewards, pro rata to its stake
+    pub fn pay_referral(&mut self, entry_amount: u64) -> Result<u64> {
+        if self.referral_rewards == 0 {
+            return Ok(0);
+        }
+        
+        let reward = referral_reward_share(self.referral_rewards, entry_amount, self.referred_stake)
+            .ok_or(GorRaceError::MathOverflow)?;
+        self.referral_rewards_paid = self.referral_rewards_paid
+            .checked_add(reward)
+            .ok_or(GorRaceError::MathOverflow)?;
+        
+        Ok(reward)
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.status == RaceStatus::Completed || self.status == RaceStatus::Cancelled
+    }
+
+    pub fn all_entries_closed(&self) -> bool {
+        self.closed_entries >= self.entry_count
+    }
+
     pub fn is_expired(&self, current_time: i64) -> bool {
         current_time >= self.start_time + self.wait_time
     }
@@ -54,16 +183,15 @@ impl Race {
         self.entry_count >= self.max_players
     }
 
+    /// A pending race may be executed once its wait time is over, or as soon as it fills up
     pub fn can_execute(&self, current_time: i64) -> bool {
         self.status == RaceStatus::Pending && 
-        self.is_expired(current_time) && 
-        self.entry_count >= MIN_PLAYERS_TO_START
+        (self.is_expired(current_time) || self.is_full())
     }
     
     pub fn can_start_race(&self, current_time: i64) -> bool {
-        self.status == RaceStatus::Pending && 
-        self.is_expired(current_time) && 
-        self.entry_count >= MIN_PLAYERS_TO_START
+        self.can_execute(current_time) && 
+        self.entry_count >= self.min_players
     }
     
     pub fn is_race_finished(&self, current_time: i64) -> bool {
@@ -73,4 +201,12 @@ impl Race {
             false
         }
     }
+    
+    pub fn is_reveal_expired(&self, current_time: i64) -> bool {
+        if let Some(race_start) = self.race_start_time {
+            current_time >= race_start + RACE_DURATION + REVEAL_TIMEOUT
+        } else {
+            false
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::errors::GorRaceError;
+
+/// Creator and referrer earnings, paid out of the platform vault by claim_rewards.
+/// Only native races accrue them, token race fees stay whole with the platform
+#[account]
+pub struct RewardAccount {
+    pub beneficiary: Pubkey,
+    pub owed: u64, // Accrued and not yet claimed
+    pub total_earned: u64,
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+impl RewardAccount {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // beneficiary
+        8 + // owed
+        8 + // total_earned
+        8 + // total_claimed
+        1; // bump
+
+    /// Credit a reward to the beneficiary
+    pub fn accrue(&mut self, amount: u64) -> Result<()> {
+        self.owed = self.owed
+            .checked_add(amount)
+            .ok_or(GorRaceError::MathOverflow)?;
+        self.total_earned = self.total_earned
+            .checked_add(amount)
+            .ok_or(GorRaceError::MathOverflow)?;
+        
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// Registry entry reserving a username, seeded by its lowercase form so
+/// names that differ only in case cannot both be taken
+#[account]
+pub struct Username {
+    pub player: Pubkey, // Wallet that holds the name
+    pub profile: Pubkey,
+    pub bump: u8,
+}
+
+impl Username {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // player
+        32 + // profile
+        1; // bump
+
+    /// Case-folded form of a username, used as its registry seed
+    pub fn fold(username: &str) -> String {
+        username.to_ascii_lowercase()
+    }
+
+    /// Whether the name is free for this player, or already theirs
+    pub fn is_available_to(&self, player: Pubkey) -> bool {
+        self.player == Pubkey::default() || self.player == player
+    }
+}
@@ -0,0 +1,241 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::{Bet, BetKind, CombinationPool, Race, RaceMode};
+use super::{pari_mutuel_payout, position_prize_share};
+
+/// Check a bet selection: distinct horses in range for the places the bet
+/// kind names and 0 elsewhere. Quinella pairs must be given in ascending
+/// order so every backer of the same pair shares one combination pool.
+pub fn is_valid_selection(bet_kind: BetKind, selection: &[u8; 3]) -> bool {
+    let (picked, unused) = selection.split_at(bet_kind.selection_len());
+    
+    let in_range = picked.iter().all(|&horse| horse > 0 && horse as usize <= MAX_HORSES);
+    let distinct = picked.iter().enumerate().all(|(i, horse)| !picked[..i].contains(horse));
+    let ordered = bet_kind != BetKind::Quinella || picked[0] < picked[1];
+    
+    in_range && distinct && ordered && unused.iter().all(|&horse| horse == 0)
+}
+
+/// The selection a bet of this kind needed to win, in the same form
+/// `is_valid_selection` accepts
+pub fn winning_selection(bet_kind: BetKind, winning_horses: &[u8; 3]) -> [u8; 3] {
+    let [first, second, third] = *winning_horses;
+    
+    match bet_kind {
+        BetKind::Win | BetKind::Show => [first, 0, 0],
+        BetKind::Exacta => [first, second, 0],
+        BetKind::Quinella => [first.min(second), first.max(second), 0],
+        BetKind::Trifecta => [first, second, third],
+    }
+}
+
+/// Payout for a show bet.
+///
+/// The net show pool is split evenly between the placed horses that were
+/// backed to show, then pari-mutuel within each horse. If none of the top
+/// three was backed every show stake is returned net of fees.
+pub fn show_payout(
+    stake: u64,
+    horse_number: u8,
+    show_pools: &[u64; MAX_HORSES],
+    winning_horses: &[u8; 3],
+    show_pool: u64,
+    net_show_pool: u64,
+) -> Option<u64> {
+    let backed_places = winning_horses
+        .iter()
+        .filter(|&&horse| show_pools[(horse - 1) as usize] > 0)
+        .count() as u64;
+    
+    if backed_places == 0 {
+        return pari_mutuel_payout(stake, show_pool, net_show_pool);
+    }
+    if !winning_horses.contains(&horse_number) {
+        return Some(0);
+    }
+    
+    pari_mutuel_payout(
+        stake,
+        show_pools[(horse_number - 1) as usize],
+        net_show_pool / backed_places,
+    )
+}
+
+/// Winnings for a single bet, each bet kind is settled from its own share of the net pool.
+///
+/// Exotic bets look up the winning combination's pool among `combination_pools`,
+/// an empty account there means nobody hit the combination.
+pub fn bet_payout(
+    race: &Race,
+    race_key: &Pubkey,
+    bet: &Bet,
+    combination_pools: &[AccountInfo],
+    program_id: &Pubkey,
+) -> Result<u64> {
+    let payout = match bet.bet_kind {
+        BetKind::Win => {
+            let prize_pool = race.net_pool(BetKind::Win).ok_or(GorRaceError::MathOverflow)?;
+            
+            match race.mode {
+                RaceMode::Fixed => {
+                    // Check if player won any position, sharing it with everyone on the same horse
+                    let mut prize_amount = 0u64;
+                    for (&winning_horse, &share_bps) in race.finishing_order.iter().zip(race.prize_distribution.iter()) {
+                        if winning_horse == bet.horse_number() {
+                            prize_amount = position_prize_share(prize_pool, share_bps, race.horse_entry_count(winning_horse))
+                                .ok_or(GorRaceError::MathOverflow)?;
+                            break;
+                        }
+                    }
+                    prize_amount
+                }
+                RaceMode::PariMutuel => {
+                    let winning_horse = race.winning_horses[0];
+                    let winning_pool = race.horse_pool(winning_horse);
+                    
+                    if winning_pool == 0 {
+                        // Nobody backed the winner, every stake is returned net of fees
+                        pari_mutuel_payout(bet.stake, race.bet_pool(BetKind::Win), prize_pool)
+                            .ok_or(GorRaceError::MathOverflow)?
+                    } else if bet.horse_number() == winning_horse {
+                        pari_mutuel_payout(bet.stake, winning_pool, prize_pool)
+                            .ok_or(GorRaceError::MathOverflow)?
+                    } else {
+                        0
+                    }
+                }
+            }
+        }
+        BetKind::Show => show_payout(
+            bet.stake,
+            bet.horse_number(),
+            &race.show_pools,
+            &race.winning_horses,
+            race.bet_pool(BetKind::Show),
+            race.net_pool(BetKind::Show).ok_or(GorRaceError::MathOverflow)?,
+        ).ok_or(GorRaceError::MathOverflow)?,
+        bet_kind => {
+            let winning = winning_selection(bet_kind, &race.winning_horses);
+            let (expected_pool, _) = Pubkey::find_program_address(
+                &[COMBINATION_POOL_SEED, race_key.as_ref(), &[bet_kind as u8], &winning],
+                program_id,
+            );
+            let combination_pool = combination_pools
+                .iter()
+                .find(|account| account.key() == expected_pool)
+                .ok_or(GorRaceError::MissingCombinationPool)?;
+            
+            let prize_pool = race.net_pool(bet_kind).ok_or(GorRaceError::MathOverflow)?;
+            
+            if combination_pool.data_is_empty() {
+                // Nobody hit the combination, every stake is returned net of fees
+                pari_mutuel_payout(bet.stake, race.bet_pool(bet_kind), prize_pool)
+                    .ok_or(GorRaceError::MathOverflow)?
+            } else if bet.selection == winning {
+                let winning_pool = CombinationPool::try_deserialize(&mut &combination_pool.data.borrow()[..])?;
+                pari_mutuel_payout(bet.stake, winning_pool.total_stake, prize_pool)
+                    .ok_or(GorRaceError::MathOverflow)?
+            } else {
+                0
+            }
+        }
+    };
+    
+    Ok(payout)
+}
+
+/// Total winnings across every bet on an entry
+pub fn entry_payout(
+    race: &Race,
+    race_key: &Pubkey,
+    bets: &[Bet],
+    combination_pools: &[AccountInfo],
+    program_id: &Pubkey,
+) -> Result<u64> {
+    bets.iter().try_fold(0u64, |total, bet| {
+        let payout = bet_payout(race, race_key, bet, combination_pools, program_id)?;
+        total.checked_add(payout).ok_or(error!(GorRaceError::MathOverflow))
+    })
+}
+
+/// Best podium place (0 for 1st) across an entry's bets for player stats.
+/// Exotic bets count as a win when the whole combination came in.
+pub fn best_finish(bets: &[Bet], winning_horses: &[u8; 3]) -> Option<usize> {
+    bets.iter()
+        .filter_map(|bet| {
+            if bet.bet_kind.is_combination() {
+                (bet.selection == winning_selection(bet.bet_kind, winning_horses)).then_some(0)
+            } else {
+                winning_horses.iter().position(|&h| h == bet.horse_number())
+            }
+        })
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_selection() {
+        assert!(is_valid_selection(BetKind::Win, &[3, 0, 0]));
+        assert!(is_valid_selection(BetKind::Show, &[10, 0, 0]));
+        assert!(is_valid_selection(BetKind::Exacta, &[5, 2, 0]));
+        assert!(is_valid_selection(BetKind::Quinella, &[2, 5, 0]));
+        assert!(is_valid_selection(BetKind::Trifecta, &[4, 1, 7]));
+        
+        assert!(!is_valid_selection(BetKind::Win, &[0, 0, 0]));
+        assert!(!is_valid_selection(BetKind::Win, &[11, 0, 0]));
+        assert!(!is_valid_selection(BetKind::Win, &[1, 2, 0]));
+        assert!(!is_valid_selection(BetKind::Exacta, &[2, 2, 0]));
+        assert!(!is_valid_selection(BetKind::Quinella, &[5, 2, 0]));
+        assert!(!is_valid_selection(BetKind::Trifecta, &[4, 1, 4]));
+        assert!(!is_valid_selection(BetKind::Trifecta, &[4, 1, 0]));
+    }
+
+    #[test]
+    fn test_winning_selection() {
+        let winning_horses = [7, 2, 9];
+        
+        assert_eq!(winning_selection(BetKind::Win, &winning_horses), [7, 0, 0]);
+        assert_eq!(winning_selection(BetKind::Exacta, &winning_horses), [7, 2, 0]);
+        assert_eq!(winning_selection(BetKind::Quinella, &winning_horses), [2, 7, 0]);
+        assert_eq!(winning_selection(BetKind::Trifecta, &winning_horses), [7, 2, 9]);
+        
+        for bet_kind in [BetKind::Exacta, BetKind::Quinella, BetKind::Trifecta] {
+            assert!(is_valid_selection(bet_kind, &winning_selection(bet_kind, &winning_horses)));
+        }
+    }
+
+    #[test]
+    fn test_show_payout() {
+        let mut show_pools = [0u64; MAX_HORSES];
+        show_pools[0] = 2_000_000_000;
+        show_pools[1] = 1_000_000_000;
+        show_pools[4] = 1_000_000_000;
+        let show_pool = 4_000_000_000;
+        let net_show_pool = 3_800_000_000;
+        
+        // Horses 1 and 2 placed and were backed, each place gets half the net pool
+        let winning_horses = [2, 3, 1];
+        assert_eq!(show_payout(1_000_000_000, 1, &show_pools, &winning_horses, show_pool, net_show_pool), Some(950_000_000));
+        assert_eq!(show_payout(1_000_000_000, 2, &show_pools, &winning_horses, show_pool, net_show_pool), Some(1_900_000_000));
+        assert_eq!(show_payout(1_000_000_000, 5, &show_pools, &winning_horses, show_pool, net_show_pool), Some(0));
+        
+        // Nobody backed a placed horse, stakes come back net of fees
+        let winning_horses = [3, 4, 6];
+        assert_eq!(show_payout(2_000_000_000, 1, &show_pools, &winning_horses, show_pool, net_show_pool), Some(1_900_000_000));
+    }
+
+    #[test]
+    fn test_best_finish() {
+        let winning_horses = [7, 2, 9];
+        let bet = |bet_kind, selection| Bet { bet_kind, selection, stake: 1 };
+        
+        assert_eq!(best_finish(&[bet(BetKind::Win, [9, 0, 0]), bet(BetKind::Show, [2, 0, 0])], &winning_horses), Some(1));
+        assert_eq!(best_finish(&[bet(BetKind::Win, [4, 0, 0]), bet(BetKind::Exacta, [7, 2, 0])], &winning_horses), Some(0));
+        assert_eq!(best_finish(&[bet(BetKind::Quinella, [2, 9, 0])], &winning_horses), None);
+        assert_eq!(best_finish(&[], &winning_horses), None);
+    }
+}
@@ -1,14 +1,12 @@
+pub mod bets;
+pub mod payout;
 pub mod random;
+pub mod token;
 
+pub use bets::*;
+pub use payout::*;
 pub use random::*;
-
-use anchor_lang::solana_program::clock::Clock;
-use anchor_lang::prelude::SolanaSysvar;
-
-pub fn get_race_id() -> u64 {
-    let clock = Clock::get().unwrap();
-    clock.unix_timestamp as u64
-}
+pub use token::*;
 
 pub fn generate_referral_code(race_id: u64) -> String {
     // Generate a simple referral code from race_id
@@ -0,0 +1,345 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Pool left for bettors once the platform fee is taken
+pub fn net_prize_pool(total_pool: u64, platform_fee: u64) -> Option<u64> {
+    total_pool.checked_sub(platform_fee)
+}
+
+/// Check a race prize table: at least one paid place, no place paying more
+/// than the one above it, and never more than the whole prize pool
+pub fn is_valid_prize_distribution(prize_distribution: &[u16]) -> bool {
+    if prize_distribution.is_empty() || prize_distribution.len() > MAX_PRIZE_PLACES {
+        return false;
+    }
+    
+    let total: u32 = prize_distribution.iter().map(|&bps| bps as u32).sum();
+    let descending = prize_distribution.windows(2).all(|pair| pair[0] >= pair[1]);
+    
+    prize_distribution[0] > 0 && descending && total <= 10000
+}
+
+/// Prize for one backer of a horse that finished in a paid place.
+///
+/// The place's share of the prize pool is split evenly between everyone
+/// who backed that horse, so a podium place never pays out more than its share.
+pub fn position_prize_share(prize_pool: u64, share_bps: u16, backers: u32) -> Option<u64> {
+    if backers == 0 {
+        return None;
+    }
+    
+    prize_pool
+        .checked_mul(share_bps as u64)?
+        .checked_div(10000)?
+        .checked_div(backers as u64)
+}
+
+/// Calculate prize distribution for winners
+pub fn calculate_winner_shares(
+    entries: &[(Pubkey, u8)],
+    winning_horses: &[u8; 3],
+    prize_distribution: &[u16],
+    prize_pool: u64,
+) -> Vec<(Pubkey, u64)> {
+    let mut winner_shares = Vec::new();
+    
+    for (&winning_horse, &share_bps) in winning_horses.iter().zip(prize_distribution.iter()) {
+        let position_winners: Vec<Pubkey> = entries
+            .iter()
+            .filter(|(_, horse)| *horse == winning_horse)
+            .map(|(player, _)| *player)
+            .collect();
+        
+        if let Some(share_per_winner) =
+            position_prize_share(prize_pool, share_bps, position_winners.len() as u32)
+        {
+            for winner in position_winners {
+                winner_shares.push((winner, share_per_winner));
+            }
+        }
+    }
+    
+    winner_shares
+}
+
+/// Pari-mutuel payout for a winning stake: the bettor's share of the
+/// winning horse's pool, applied to the whole prize pool
+pub fn pari_mutuel_payout(stake: u64, winning_pool: u64, prize_pool: u64) -> Option<u64> {
+    if winning_pool == 0 {
+        return None;
+    }
+    
+    let payout = (prize_pool as u128)
+        .checked_mul(stake as u128)?
+        .checked_div(winning_pool as u128)?;
+    
+    u64::try_from(payout).ok()
+}
+
+/// Implied decimal odds for every horse, scaled by `ODDS_PRECISION`, from the
+/// win pool left once the platform fee and crank rewards are taken.
+///
+/// A horse nobody has backed yet has no odds and is reported as 0.
+pub fn implied_odds(horse_pools: &[u64; MAX_HORSES], prize_pool: u64) -> [u64; MAX_HORSES] {
+    let mut odds = [0u64; MAX_HORSES];
+    for (odd, &pool) in odds.iter_mut().zip(horse_pools.iter()) {
+        if pool > 0 {
+            let scaled = (prize_pool as u128) * (ODDS_PRECISION as u128) / (pool as u128);
+            *odd = u64::try_from(scaled).unwrap_or(u64::MAX);
+        }
+    }
+    
+    odds
+}
+
+/// Share of a race's platform fee, in basis points of the fee
+pub fn fee_share(platform_fee: u64, share_bps: u16) -> Option<u64> {
+    let share = (platform_fee as u128)
+        .checked_mul(share_bps as u128)?
+        .checked_div(10000)?;
+    
+    u64::try_from(share).ok()
+}
+
+/// Referrer share of the platform fee on the stake that named a referrer
+pub fn referral_rewards(
+    platform_fee: u64,
+    referrer_share_bps: u16,
+    referred_stake: u64,
+    total_pool: u64,
+) -> Option<u64> {
+    if total_pool == 0 {
+        return Some(0);
+    }
+    
+    let rewards = (fee_share(platform_fee, referrer_share_bps)? as u128)
+        .checked_mul(referred_stake as u128)?
+        .checked_div(total_pool as u128)?;
+    
+    u64::try_from(rewards).ok()
+}
+
+/// One entry's cut of a race's referral rewards, pro rata to its stake
+pub fn referral_reward_share(referral_rewards: u64, entry_amount: u64, referred_stake: u64) -> Option<u64> {
+    if referred_stake == 0 {
+        return Some(0);
+    }
+    
+    let share = (referral_rewards as u128)
+        .checked_mul(entry_amount as u128)?
+        .checked_div(referred_stake as u128)?;
+    
+    u64::try_from(share).ok()
+}
+
+/// Convert an amount in native GOR base units to the base units of a mint with
+/// `decimals`, one GOR becoming one whole token. Rounds up and saturates, so a
+/// non-zero bound never scales down to zero or wraps around.
+pub fn scale_to_decimals(amount: u64, decimals: u8) -> u64 {
+    if amount == 0 {
+        return 0;
+    }
+    
+    if decimals >= NATIVE_DECIMALS {
+        10u64
+            .checked_pow((decimals - NATIVE_DECIMALS) as u32)
+            .map_or(u64::MAX, |factor| amount.saturating_mul(factor))
+    } else {
+        amount.div_ceil(10u64.pow((NATIVE_DECIMALS - decimals) as u32))
+    }
+}
+
+/// Reward for the executor of one execute_race, a share of the race pool
+/// capped at `max_reward` when that is set
+pub fn crank_reward(total_pool: u64, reward_bps: u16, max_reward: u64) -> Option<u64> {
+    let reward = u64::try_from((total_pool as u128).checked_mul(reward_bps as u128)? / 10000).ok()?;
+    
+    Some(if max_reward > 0 { reward.min(max_reward) } else { reward })
+}
+
+/// Refund for an entry of a cancelled race, which shares the crank rewards
+/// already paid out of the pool pro rata to its stake. Rounded down, the dust
+/// stays on top of the native vault's rent reserve until close_race sweeps it.
+pub fn refund_amount(entry_amount: u64, total_pool: u64, crank_rewards: u64) -> Option<u64> {
+    if total_pool == 0 {
+        return Some(entry_amount);
+    }
+    
+    let refund = (entry_amount as u128)
+        .checked_mul(total_pool.checked_sub(crank_rewards)? as u128)?
+        .checked_div(total_pool as u128)?;
+    
+    u64::try_from(refund).ok()
+}
+
+/// An entry's cut of a forfeited creator bond, pro rata to its stake
+pub fn bond_share(creator_bond: u64, entry_amount: u64, total_pool: u64) -> Option<u64> {
+    if total_pool == 0 {
+        return Some(0);
+    }
+    
+    let share = (creator_bond as u128)
+        .checked_mul(entry_amount as u128)?
+        .checked_div(total_pool as u128)?;
+    
+    u64::try_from(share).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_winner_shares() {
+        let entries = vec![
+            (Pubkey::new_unique(), 1),
+            (Pubkey::new_unique(), 1),
+            (Pubkey::new_unique(), 2),
+            (Pubkey::new_unique(), 3),
+        ];
+        
+        let winning_horses = [1, 2, 3];
+        let prize_pool = 1_000_000_000; // 1 GOR
+        
+        let shares = calculate_winner_shares(&entries, &winning_horses, &PRIZE_DISTRIBUTION, prize_pool);
+        
+        // First place (horse 1) has 2 winners, each gets 25%
+        assert_eq!(shares.len(), 4);
+        assert_eq!(shares[0].1, 250_000_000); // 0.25 GOR each
+        assert_eq!(shares[1].1, 250_000_000);
+        
+        // Second place (horse 2) has 1 winner, gets 30%
+        assert_eq!(shares[2].1, 300_000_000); // 0.3 GOR
+        
+        // Third place (horse 3) has 1 winner, gets 15%
+        assert_eq!(shares[3].1, 150_000_000); // 0.15 GOR
+    }
+
+    #[test]
+    fn test_position_prize_share_never_overcommits() {
+        let prize_pool = 950_000_000;
+        
+        // Three backers of the winner share the 50% first place prize
+        let share = position_prize_share(prize_pool, 5000, 3).unwrap();
+        assert_eq!(share, 158_333_333);
+        assert!(share * 3 <= prize_pool / 2);
+        
+        assert_eq!(position_prize_share(prize_pool, 3000, 1), Some(285_000_000));
+        assert_eq!(position_prize_share(prize_pool, 5000, 0), None);
+    }
+
+    #[test]
+    fn test_is_valid_prize_distribution() {
+        assert!(is_valid_prize_distribution(&PRIZE_DISTRIBUTION));
+        assert!(is_valid_prize_distribution(&[10000]));
+        assert!(is_valid_prize_distribution(&[6000, 4000]));
+        assert!(is_valid_prize_distribution(&[4000, 4000, 2000]));
+        
+        assert!(!is_valid_prize_distribution(&[]));
+        assert!(!is_valid_prize_distribution(&[0]));
+        assert!(!is_valid_prize_distribution(&[3000, 5000]));
+        assert!(!is_valid_prize_distribution(&[6000, 5000]));
+        assert!(is_valid_prize_distribution(&[4000, 3000, 2000, 1000]));
+        assert!(!is_valid_prize_distribution(&[4000, 2000, 1500, 1000, 1000, 500]));
+    }
+
+    #[test]
+    fn test_pari_mutuel_payout() {
+        // 10 GOR pool after fees, 4 GOR on the winner
+        let prize_pool = 10_000_000_000;
+        let winning_pool = 4_000_000_000;
+        
+        assert_eq!(pari_mutuel_payout(1_000_000_000, winning_pool, prize_pool), Some(2_500_000_000));
+        assert_eq!(pari_mutuel_payout(3_000_000_000, winning_pool, prize_pool), Some(7_500_000_000));
+        assert_eq!(pari_mutuel_payout(1_000_000_000, 0, prize_pool), None);
+        
+        // Large pools do not overflow the intermediate product
+        assert_eq!(pari_mutuel_payout(u64::MAX, u64::MAX, u64::MAX), Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_implied_odds() {
+        let mut horse_pools = [0u64; MAX_HORSES];
+        horse_pools[0] = 1_000_000_000;
+        horse_pools[1] = 3_000_000_000;
+        
+        // 4 GOR pool, 5% fee leaves 3.8 GOR
+        let odds = implied_odds(&horse_pools, 3_800_000_000);
+        
+        assert_eq!(odds[0], 38_000); // 3.8x
+        assert_eq!(odds[1], 12_666); // ~1.27x
+        assert!(odds[2..].iter().all(|&o| o == 0));
+        
+        // Crank rewards come out of the same pool and shorten the odds
+        let odds = implied_odds(&horse_pools, 3_700_000_000);
+        assert_eq!(odds[0], 37_000);
+    }
+
+    #[test]
+    fn test_referral_rewards() {
+        // 5 GOR fee on a 100 GOR pool, 10% of it to referrers
+        let platform_fee = 5_000_000_000;
+        let total_pool = 100_000_000_000;
+        
+        assert_eq!(fee_share(platform_fee, 2000), Some(1_000_000_000));
+        assert_eq!(referral_rewards(platform_fee, 1000, total_pool, total_pool), Some(500_000_000));
+        
+        // Only 40 GOR of the pool was referred
+        let rewards = referral_rewards(platform_fee, 1000, 40_000_000_000, total_pool).unwrap();
+        assert_eq!(rewards, 200_000_000);
+        assert_eq!(referral_rewards(platform_fee, 1000, 0, 0), Some(0));
+        
+        // Entries split the reserve by stake and never exceed it
+        let first = referral_reward_share(rewards, 30_000_000_000, 40_000_000_000).unwrap();
+        let second = referral_reward_share(rewards, 10_000_000_000, 40_000_000_000).unwrap();
+        assert_eq!(first, 150_000_000);
+        assert_eq!(second, 50_000_000);
+        assert!(first + second <= rewards);
+        assert_eq!(referral_reward_share(rewards, 1, 0), Some(0));
+    }
+
+    #[test]
+    fn test_crank_reward() {
+        // 0.1% of a 100 GOR pool
+        let total_pool = 100_000_000_000;
+        assert_eq!(crank_reward(total_pool, 10, 0), Some(100_000_000));
+        assert_eq!(crank_reward(total_pool, 10, 50_000_000), Some(50_000_000));
+        assert_eq!(crank_reward(total_pool, 0, 50_000_000), Some(0));
+        assert_eq!(crank_reward(0, 10, 0), Some(0));
+        
+        // Entries of a cancelled race share the rewards paid when it started
+        let rewards = 100_000_000;
+        let first = refund_amount(70_000_000_000, total_pool, rewards).unwrap();
+        let second = refund_amount(30_000_000_000, total_pool, rewards).unwrap();
+        assert_eq!(first, 69_930_000_000);
+        assert_eq!(second, 29_970_000_000);
+        assert!(first + second + rewards <= total_pool);
+        assert_eq!(refund_amount(1_000, 0, 0), Some(1_000));
+    }
+
+    #[test]
+    fn test_bond_share() {
+        // Token races share the lamport bond by stake too, whatever the mint's base units
+        let first = bond_share(CREATOR_BOND, 3_000_000, 4_000_000).unwrap();
+        let second = bond_share(CREATOR_BOND, 1_000_000, 4_000_000).unwrap();
+        assert_eq!(first, 75_000_000);
+        assert_eq!(second, 25_000_000);
+        assert_eq!(bond_share(CREATOR_BOND, 1, 3).unwrap() * 3, CREATOR_BOND - 1);
+        assert_eq!(bond_share(CREATOR_BOND, 0, 0), Some(0));
+    }
+
+    #[test]
+    fn test_scale_to_decimals() {
+        assert_eq!(scale_to_decimals(ENTRY_FEE, NATIVE_DECIMALS), ENTRY_FEE);
+        
+        // 0.1 GOR becomes 0.1 of a 6 or 12 decimal token
+        assert_eq!(scale_to_decimals(ENTRY_FEE, 6), 100_000);
+        assert_eq!(scale_to_decimals(ENTRY_FEE, 12), 100_000_000_000);
+        
+        // Bounds round up instead of collapsing to zero, and saturate instead of wrapping
+        assert_eq!(scale_to_decimals(MIN_ENTRY_FEE, 0), 1);
+        assert_eq!(scale_to_decimals(1_500_000_000, 0), 2);
+        assert_eq!(scale_to_decimals(MAX_ENTRY_FEE, 30), u64::MAX);
+        assert_eq!(scale_to_decimals(0, 30), 0);
+    }
+}
@@ -1,6 +1,6 @@
-use anchor_lang::prelude::*;
-use anchor_lang::solana_program::sysvar::recent_blockhashes::RecentBlockhashes;
+use anchor_lang::solana_program::hash::{hash, hashv};
 use crate::constants::*;
+use crate::state::HorseAttributes;
 
 /// Select random horse names from the pool for a race
 pub fn select_random_horses(slot: u64) -> [String; MAX_HORSES] {
@@ -25,79 +25,127 @@ pub fn select_random_horses(slot: u64) -> [String; MAX_HORSES] {
 }
 
 
-/// Generate race results using on-chain randomness
-pub fn generate_race_results(
-    recent_blockhashes: &Sysvar<RecentBlockhashes>,
-    slot: u64,
-    race_id: u64,
-) -> [u8; 3] {
-    let recent_blockhash = recent_blockhashes
-        .iter()
-        .next()
-        .map(|entry| entry.blockhash)
-        .unwrap_or_default();
+/// Roll speed, stamina and form for every horse when a race is created.
+///
+/// These are public from the start so bettors can weigh them up, only the
+/// seed revealed at execution decides how the race actually plays out.
+pub fn generate_horse_attributes(slot: u64, race_id: u64) -> [HorseAttributes; MAX_HORSES] {
+    let digest = hashv(&[&slot.to_le_bytes(), &race_id.to_le_bytes()]).to_bytes();
+    let mut seed = u64::from_le_bytes(digest[..8].try_into().unwrap());
+    let mut roll = || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let range = (MAX_HORSE_ATTRIBUTE - MIN_HORSE_ATTRIBUTE) as u64 + 1;
+        MIN_HORSE_ATTRIBUTE + ((seed >> 33) % range) as u8
+    };
     
-    let mut seed = 0u64;
-    for (i, byte) in recent_blockhash.to_bytes().iter().enumerate() {
-        seed = seed.wrapping_add(*byte as u64)
-            .wrapping_mul(i as u64 + 1)
-            .wrapping_add(slot)
-            .wrapping_add(race_id);
-    }
+    core::array::from_fn(|_| HorseAttributes {
+        speed: roll(),
+        stamina: roll(),
+        form: roll(),
+    })
+}
+
+/// Check a revealed seed against the commitment recorded at race creation
+pub fn verify_seed_commitment(seed: &[u8; 32], commitment: &[u8; 32]) -> bool {
+    hash(seed).to_bytes() == *commitment
+}
+
+/// Look up the hash of `slot` in raw `SlotHashes` sysvar data.
+///
+/// The sysvar is a bincode `Vec<(u64, [u8; 32])>` sorted by slot in descending
+/// order, so it is searched in place instead of deserializing all 512 entries.
+pub fn find_slot_hash(slot_hashes_data: &[u8], slot: u64) -> Option<[u8; 32]> {
+    const ENTRY_SIZE: usize = 8 + 32;
     
-    let mut winners = [0u8; 3];
-    let mut used_horses = Vec::new();
+    let len = u64::from_le_bytes(slot_hashes_data.get(..8)?.try_into().ok()?) as usize;
+    let entries = slot_hashes_data.get(8..)?;
     
-    for i in 0..3 {
-        loop {
-            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
-            let horse = ((seed % MAX_HORSES as u64) + 1) as u8;
-            
-            if !used_horses.contains(&horse) {
-                used_horses.push(horse);
-                winners[i] = horse;
-                break;
-            }
+    let (mut low, mut high) = (0usize, len);
+    while low < high {
+        let mid = (low + high) / 2;
+        let entry = entries.get(mid * ENTRY_SIZE..(mid + 1) * ENTRY_SIZE)?;
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().ok()?);
+        
+        if entry_slot == slot {
+            return entry[8..].try_into().ok();
+        } else if entry_slot > slot {
+            low = mid + 1;
+        } else {
+            high = mid;
         }
     }
     
-    winners
+    None
 }
 
-/// Calculate prize distribution for winners
-pub fn calculate_winner_shares(
-    entries: &[(Pubkey, u8)],
-    winning_horses: &[u8; 3],
-    prize_pool: u64,
-) -> Vec<(Pubkey, u64)> {
-    let mut winner_shares = Vec::new();
+/// Mix the revealed seed with the slot hash of the race start slot
+pub fn mix_race_seed(revealed_seed: &[u8; 32], slot_hash: &[u8; 32], race_id: u64) -> [u8; 32] {
+    hashv(&[revealed_seed, slot_hash, &race_id.to_le_bytes()]).to_bytes()
+}
+
+/// Running order at every checkpoint and the final finishing order of a simulated race
+pub struct RaceTimeline {
+    pub finishing_order: [u8; MAX_HORSES],
+    pub checkpoints: [[u8; MAX_HORSES]; RACE_CHECKPOINTS],
+}
+
+impl RaceTimeline {
+    /// 1st, 2nd and 3rd place horse numbers
+    pub fn podium(&self) -> [u8; 3] {
+        [self.finishing_order[0], self.finishing_order[1], self.finishing_order[2]]
+    }
+}
+
+/// Simulate a race from the committed race seed.
+///
+/// Every horse covers a random distance between checkpoints, plus a bonus for
+/// speed and form and, over the second half, a penalty for low stamina. The
+/// running order is recorded after each leg, so any client holding the seed
+/// can replay the race exactly. Horses level on distance are split by a
+/// random draw made at the start.
+pub fn simulate_race(race_seed: &[u8; 32], attributes: &[HorseAttributes; MAX_HORSES]) -> RaceTimeline {
+    let mut seed = u64::from_le_bytes(race_seed[..8].try_into().unwrap());
+    let mut next_random = || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        // High bits of an LCG are far better distributed than the low ones
+        seed >> 33
+    };
+    
+    let tiebreaks: [u64; MAX_HORSES] = core::array::from_fn(|_| next_random());
+    let mut distances = [0u64; MAX_HORSES];
+    let mut running_order: [u8; MAX_HORSES] = core::array::from_fn(|i| (i + 1) as u8);
+    let mut checkpoints = [[0u8; MAX_HORSES]; RACE_CHECKPOINTS];
     
-    for (position, &winning_horse) in winning_horses.iter().enumerate() {
-        let position_winners: Vec<Pubkey> = entries
-            .iter()
-            .filter(|(_, horse)| *horse == winning_horse)
-            .map(|(player, _)| *player)
-            .collect();
+    for (leg, checkpoint) in checkpoints.iter_mut().enumerate() {
+        let tiring = leg >= RACE_CHECKPOINTS / 2;
         
-        if !position_winners.is_empty() {
-            let position_prize = prize_pool
-                .checked_mul(PRIZE_DISTRIBUTION[position] as u64)
-                .unwrap()
-                .checked_div(10000)
-                .unwrap();
-            
-            let share_per_winner = position_prize
-                .checked_div(position_winners.len() as u64)
-                .unwrap();
+        for (distance, horse) in distances.iter_mut().zip(attributes.iter()) {
+            let fatigue = if tiring {
+                (MAX_HORSE_ATTRIBUTE.saturating_sub(horse.stamina)) as u64 / FATIGUE_DIVISOR
+            } else {
+                0
+            };
             
-            for winner in position_winners {
-                winner_shares.push((winner, share_per_winner));
-            }
+            *distance += MIN_LEG_DISTANCE
+                + next_random() % (MAX_LEG_DISTANCE - MIN_LEG_DISTANCE + 1)
+                + horse.speed as u64 / SPEED_DIVISOR
+                + horse.form as u64 / FORM_DIVISOR
+                - fatigue;
         }
+        
+        running_order.sort_by_key(|&horse| {
+            let index = (horse - 1) as usize;
+            core::cmp::Reverse((distances[index], tiebreaks[index]))
+        });
+        *checkpoint = running_order;
     }
     
-    winner_shares
+    RaceTimeline {
+        finishing_order: running_order,
+        checkpoints,
+    }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,28 +163,92 @@ mod tests {
     }
 
     #[test]
-    fn test_calculate_winner_shares() {
-        let entries = vec![
-            (Pubkey::new_unique(), 1),
-            (Pubkey::new_unique(), 1),
-            (Pubkey::new_unique(), 2),
-            (Pubkey::new_unique(), 3),
-        ];
+    fn test_verify_seed_commitment() {
+        let seed = [7u8; 32];
+        let commitment = hash(&seed).to_bytes();
         
-        let winning_horses = [1, 2, 3];
-        let prize_pool = 1_000_000_000; // 1 GOR
+        assert!(verify_seed_commitment(&seed, &commitment));
+        assert!(!verify_seed_commitment(&[8u8; 32], &commitment));
+    }
+
+    #[test]
+    fn test_find_slot_hash() {
+        // Slots are stored newest first, as in the sysvar
+        let slots = [105u64, 104, 102, 101, 98];
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+        for slot in slots {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[slot as u8; 32]);
+        }
         
-        let shares = calculate_winner_shares(&entries, &winning_horses, prize_pool);
+        for slot in slots {
+            assert_eq!(find_slot_hash(&data, slot), Some([slot as u8; 32]));
+        }
+        assert_eq!(find_slot_hash(&data, 103), None);
+        assert_eq!(find_slot_hash(&data, 97), None);
+        assert_eq!(find_slot_hash(&data, 200), None);
+        assert_eq!(find_slot_hash(&data[..20], 105), None);
+    }
+
+    #[test]
+    fn test_simulate_race() {
+        let race_seed = mix_race_seed(&[1u8; 32], &[2u8; 32], 42);
+        let attributes = generate_horse_attributes(100, 42);
+        let timeline = simulate_race(&race_seed, &attributes);
         
-        // First place (horse 1) has 2 winners, each gets 25%
-        assert_eq!(shares.len(), 4);
-        assert_eq!(shares[0].1, 250_000_000); // 0.25 GOR each
-        assert_eq!(shares[1].1, 250_000_000);
+        // Deterministic for the same seed
+        assert_eq!(timeline.finishing_order, simulate_race(&race_seed, &attributes).finishing_order);
+        assert_eq!(timeline.checkpoints, simulate_race(&race_seed, &attributes).checkpoints);
         
-        // Second place (horse 2) has 1 winner, gets 30%
-        assert_eq!(shares[2].1, 300_000_000); // 0.3 GOR
+        // Every checkpoint is a full running order and the last one is the result
+        for order in timeline.checkpoints.iter().chain([&timeline.finishing_order]) {
+            let mut horses = order.to_vec();
+            horses.sort();
+            assert_eq!(horses, (1..=MAX_HORSES as u8).collect::<Vec<_>>());
+        }
+        assert_eq!(timeline.checkpoints[RACE_CHECKPOINTS - 1], timeline.finishing_order);
+        assert_eq!(timeline.podium(), [timeline.finishing_order[0], timeline.finishing_order[1], timeline.finishing_order[2]]);
         
-        // Third place (horse 3) has 1 winner, gets 15%
-        assert_eq!(shares[3].1, 150_000_000); // 0.15 GOR
+        // A different race id changes the mixed seed
+        let other_seed = mix_race_seed(&[1u8; 32], &[2u8; 32], 43);
+        assert_ne!(race_seed, other_seed);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_generate_horse_attributes() {
+        let attributes = generate_horse_attributes(100, 42);
+        
+        assert!(attributes.iter().all(|horse| {
+            [horse.speed, horse.stamina, horse.form]
+                .iter()
+                .all(|&value| (MIN_HORSE_ATTRIBUTE..=MAX_HORSE_ATTRIBUTE).contains(&value))
+        }));
+        assert_eq!(attributes, generate_horse_attributes(100, 42));
+        assert_ne!(attributes, generate_horse_attributes(100, 43));
+    }
+
+    #[test]
+    fn test_simulate_race_favours_stronger_horses() {
+        let weakest = HorseAttributes {
+            speed: MIN_HORSE_ATTRIBUTE,
+            stamina: MIN_HORSE_ATTRIBUTE,
+            form: MIN_HORSE_ATTRIBUTE,
+        };
+        let mut attributes = [weakest; MAX_HORSES];
+        attributes[0] = HorseAttributes {
+            speed: MAX_HORSE_ATTRIBUTE,
+            stamina: MAX_HORSE_ATTRIBUTE,
+            form: MAX_HORSE_ATTRIBUTE,
+        };
+        
+        let wins = (0..200u64)
+            .filter(|&race_id| {
+                let race_seed = mix_race_seed(&[1u8; 32], &[2u8; 32], race_id);
+                simulate_race(&race_seed, &attributes).finishing_order[0] == 1
+            })
+            .count();
+        
+        // Uniform odds would give the strongest horse about 20 wins
+        assert!(wins > 100, "strongest horse only won {} of 200", wins);
+    }
+}
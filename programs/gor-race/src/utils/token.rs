@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::{instruction::harvest_withheld_tokens_to_mint, TransferFeeAmount}, BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::errors::GorRaceError;
+
+/// Unwrap an optional account that token races must pass
+pub fn require_token_account<T>(account: &Option<T>) -> Result<&T> {
+    account.as_ref().ok_or(error!(GorRaceError::MissingTokenAccounts))
+}
+
+/// Token-2022 extensions that let someone other than the vault move, block or
+/// lock its tokens, so a race paid in such a mint could never be settled
+const UNSUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::PermanentDelegate,
+    ExtensionType::TransferHook,
+    ExtensionType::NonTransferable,
+];
+
+/// Reject Token-2022 mints whose extensions could take or freeze the race vault's
+/// tokens. Transfer fees are fine, stakes are recorded from what the vault received.
+pub fn require_supported_mint(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let mint_info = mint.to_account_info();
+    if mint_info.owner != &spl_token_2022::ID {
+        return Ok(());
+    }
+    
+    let data = mint_info.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    let extensions = state.get_extension_types()?;
+    require!(
+        !extensions.iter().any(|extension| UNSUPPORTED_MINT_EXTENSIONS.contains(extension)),
+        GorRaceError::UnsupportedMintExtension
+    );
+    
+    Ok(())
+}
+
+/// Transfer `amount` of a race mint, signing with `signer_seeds` when the
+/// authority is one of the program's PDAs
+pub fn transfer_tokens<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+        mint.decimals,
+    )
+}
+
+/// Amount that actually landed in `account` since `balance_before`.
+///
+/// Token-2022 mints with a transfer fee deliver less than was sent, so stakes
+/// are recorded from the vault balance rather than the requested amount.
+pub fn received_amount(
+    account: &mut InterfaceAccount<TokenAccount>,
+    balance_before: u64,
+) -> Result<u64> {
+    account.reload()?;
+    account
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(error!(GorRaceError::MathOverflow))
+}
+
+/// Close a token account owned by one of the program's PDAs, sending its rent to `destination`.
+///
+/// Token-2022 accounts still holding withheld transfer fees cannot be closed,
+/// so those are harvested to the mint first, which needs no signer.
+pub fn close_token_account<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    account: &InterfaceAccount<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if token_program.key() == spl_token_2022::ID && has_withheld_fees(&account.to_account_info())? {
+        invoke(
+            &harvest_withheld_tokens_to_mint(&token_program.key(), &mint.key(), &[&account.key()])?,
+            &[mint.to_account_info(), account.to_account_info()],
+        )?;
+    }
+    
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: account.to_account_info(),
+            destination,
+            authority,
+        },
+        signer_seeds,
+    ))
+}
+
+fn has_withheld_fees(account: &AccountInfo) -> Result<bool> {
+    let data = account.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    
+    Ok(state
+        .get_extension::<TransferFeeAmount>()
+        .map(|fee| u64::from(fee.withheld_amount) > 0)
+        .unwrap_or(false))
+}
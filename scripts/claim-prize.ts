@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { GorRace } from "../target/types/gor_race";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
 import * as dotenv from "dotenv";
 
 // Load environment variables from .env file
@@ -91,7 +92,7 @@ async function main() {
       } else if (raceStatus === 'racing') {
         console.error("   Race simulation is in progress. Wait for it to finish.");
       } else if (raceStatus === 'cancelled') {
-        console.error("   Race was cancelled due to insufficient players.");
+        console.error("   Race was cancelled. Get your entry back with: npm run claim-refund", input);
       }
       process.exit(1);
     }
@@ -111,21 +112,39 @@ async function main() {
     console.log("🥈 2nd place: Horse", race.winningHorses[1], "-", race.horseNames[race.winningHorses[1] - 1]);
     console.log("🥉 3rd place: Horse", race.winningHorses[2], "-", race.horseNames[race.winningHorses[2] - 1]);
     
-    console.log("\\n🐎 Your Horse:");
-    console.log("You selected: Horse", playerEntry.horseNumber, "-", race.horseNames[playerEntry.horseNumber - 1]);
+    console.log("\\n🐎 Your Bets:");
+    const betKinds = playerEntry.bets.map(bet => Object.keys(bet.betKind)[0]);
+    playerEntry.bets.forEach((bet, i) => {
+      const selection = bet.selection.filter(h => h > 0);
+      console.log(`${betKinds[i]}:`, selection.map(h => `${h} - ${race.horseNames[h - 1]}`).join(", "),
+        "|", bet.stake.toNumber() / anchor.web3.LAMPORTS_PER_SOL);
+    });
     
-    // Check if player won
-    const wonPosition = race.winningHorses.findIndex(h => h === playerEntry.horseNumber);
-    if (wonPosition === -1) {
-      console.log("\\n❌ Sorry, your horse didn't win any position this time.");
-      console.log("💔 Better luck next race!");
-      console.log("\\n💡 Tip: Join more races to increase your chances of winning!");
-      console.log("💡 Tip: Update your stats with: npm run update-stats", input);
-      return;
-    }
+    // Exotic bets are settled on chain against the winning combination's pool,
+    // including refunds when nobody hit it
+    const combinationPools = [...new Set(betKinds.filter(kind => COMBINATION_BET_KINDS.includes(kind)))]
+      .map(kind => ({
+        pubkey: winningCombinationPool(program.programId, racePda, kind, race.winningHorses),
+        isWritable: false,
+        isSigner: false,
+      }));
+    
+    if (combinationPools.length === 0) {
+      // Check if any horse the player backed placed
+      const wonPosition = Math.min(...playerEntry.bets
+        .map(bet => race.winningHorses.findIndex(h => h === bet.selection[0]))
+        .filter(position => position !== -1));
+      if (!isFinite(wonPosition)) {
+        console.log("\\n❌ Sorry, your horses didn't win any position this time.");
+        console.log("💔 Better luck next race!");
+        console.log("\\n💡 Tip: Join more races to increase your chances of winning!");
+        console.log("💡 Tip: Update your stats with: npm run update-stats", input);
+        return;
+      }
 
-    const positionNames = ["🥇 1st", "🥈 2nd", "🥉 3rd"];
-    console.log(`\\n🎉 Congratulations! Your horse won ${positionNames[wonPosition]} place!`);
+      const positionNames = ["🥇 1st", "🥈 2nd", "🥉 3rd"];
+      console.log(`\\n🎉 Congratulations! Your horse won ${positionNames[wonPosition]} place!`);
+    }
     
     // Check if already claimed
     if (Object.keys(playerEntry.claimStatus)[0] === 'claimed') {
@@ -135,6 +154,16 @@ async function main() {
 
     console.log("\\n💰 Claiming your prize...");
 
+    // Token races settle through associated token accounts, native races pass none
+    const tokenAccounts = await raceTokenAccounts(provider.connection, race.mint, raceVaultPda, player.publicKey);
+
+    // Passing the profile counts the race in the same transaction
+    const [playerProfilePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("player_profile"), player.publicKey.toBuffer()],
+      program.programId
+    );
+    const playerProfile = await program.account.playerProfile.fetchNullable(playerProfilePda);
+
     // Claim prize
     const tx = await program.methods
       .claimPrize()
@@ -144,8 +173,11 @@ async function main() {
         player: player.publicKey,
         raceVault: raceVaultPda,
         platformVault: platformVaultPda,
+        playerProfile: playerProfile ? playerProfilePda : null,
+        ...tokenAccounts,
         systemProgram: SystemProgram.programId,
       })
+      .remainingAccounts(combinationPools)
       .rpc();
 
     console.log("✅ Prize claimed successfully!");
@@ -160,8 +192,14 @@ async function main() {
     const balance = await provider.connection.getBalance(player.publicKey);
     console.log("💳 Updated GOR balance:", balance / anchor.web3.LAMPORTS_PER_SOL);
     
+    if (updatedPlayerEntry.statsUpdated) {
+      console.log("📊 Race counted on your profile");
+    }
+    
     console.log("\\n💡 Next Steps:");
-    console.log("- Update your stats: npm run update-stats", input);
+    if (!updatedPlayerEntry.statsUpdated) {
+      console.log("- Create a profile to track your stats: npm run create-profile <USERNAME>");
+    }
     console.log("- View your profile: npm run profile");
     console.log("- Check leaderboard: npm run leaderboard");
 
@@ -175,6 +213,48 @@ async function main() {
   }
 }
 
+const BET_KINDS = ["win", "show", "exacta", "quinella", "trifecta"];
+const COMBINATION_BET_KINDS = ["exacta", "quinella", "trifecta"];
+
+function winningCombinationPool(
+  programId: PublicKey,
+  race: PublicKey,
+  betKind: string,
+  winningHorses: number[]
+) {
+  const [first, second, third] = winningHorses;
+  const selection = {
+    exacta: [first, second, 0],
+    quinella: [Math.min(first, second), Math.max(first, second), 0],
+    trifecta: [first, second, third],
+  }[betKind];
+
+  const [combinationPoolPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("combination_pool"), race.toBuffer(), Buffer.from([BET_KINDS.indexOf(betKind)]), Buffer.from(selection)],
+    programId
+  );
+  return combinationPoolPda;
+}
+
+async function raceTokenAccounts(
+  connection: anchor.web3.Connection,
+  mint: PublicKey | null,
+  raceVault: PublicKey,
+  owner: PublicKey
+) {
+  if (!mint) {
+    return { mint: null, playerTokenAccount: null, raceVaultTokenAccount: null, tokenProgram: null };
+  }
+
+  const tokenProgram = (await connection.getAccountInfo(mint)).owner;
+  return {
+    mint,
+    playerTokenAccount: getAssociatedTokenAddressSync(mint, owner, false, tokenProgram),
+    raceVaultTokenAccount: getAssociatedTokenAddressSync(mint, raceVault, true, tokenProgram),
+    tokenProgram,
+  };
+}
+
 function decodeReferralCode(referralCode: string): number | null {
   // Decode referral code back to race_id
   if (referralCode.length !== 8) {
@@ -0,0 +1,152 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { GorRace } from "../target/types/gor_race";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import * as dotenv from "dotenv";
+
+// Load environment variables from .env file
+dotenv.config();
+
+async function main() {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.GorRace as Program<GorRace>;
+  const player = provider.wallet as anchor.Wallet;
+
+  // Get referral code or race ID from command line
+  const input = process.argv[2];
+  let raceId: number;
+
+  if (!input) {
+    console.error("Usage: npm run claim-refund <REFERRAL_CODE_OR_RACE_ID>");
+    console.error("Example: npm run claim-refund XYVSYS00");
+    console.error("Example: npm run claim-refund 1751510633");
+    process.exit(1);
+  }
+
+  console.log("Claiming refund...");
+  console.log("Player:", player.publicKey.toString());
+
+  if (input.length === 8 && isNaN(Number(input))) {
+    // Assume it's a referral code
+    raceId = decodeReferralCode(input);
+    if (!raceId) {
+      console.error("❌ Invalid referral code format");
+      process.exit(1);
+    }
+    console.log("Decoded Race ID:", raceId);
+  } else {
+    // Assume it's a race ID
+    raceId = parseInt(input);
+    if (isNaN(raceId)) {
+      console.error("❌ Invalid race ID format");
+      process.exit(1);
+    }
+    console.log("Race ID:", raceId);
+  }
+
+  // Derive PDAs
+  const [racePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("race"), new anchor.BN(raceId).toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+
+  const [playerEntryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("player_entry"), racePda.toBuffer(), player.publicKey.toBuffer()],
+    program.programId
+  );
+
+  const [raceVaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("race_vault"), racePda.toBuffer()],
+    program.programId
+  );
+
+  try {
+    const race = await program.account.race.fetch(racePda);
+    const raceStatus = Object.keys(race.status)[0];
+    if (raceStatus !== 'cancelled') {
+      console.error(`❌ Only cancelled races can be refunded. Current status: ${raceStatus}`);
+      process.exit(1);
+    }
+
+    const playerEntry = await program.account.playerEntry.fetch(playerEntryPda);
+    if (Object.keys(playerEntry.claimStatus)[0] !== 'unclaimed') {
+      console.log("⚠️  Your entry for this race has already been settled.");
+      return;
+    }
+
+    // Token races settle through associated token accounts, native races pass none
+    const tokenAccounts = await raceTokenAccounts(provider.connection, race.mint, raceVaultPda, player.publicKey);
+
+    const tx = await program.methods
+      .claimRefund()
+      .accounts({
+        race: racePda,
+        playerEntry: playerEntryPda,
+        player: player.publicKey,
+        raceVault: raceVaultPda,
+        ...tokenAccounts,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("✅ Refund claimed successfully!");
+    console.log("📋 Transaction signature:", tx);
+    console.log("💰 Refund amount:", playerEntry.entryAmount.toNumber() / anchor.web3.LAMPORTS_PER_SOL, "GOR");
+  } catch (error) {
+    console.error("❌ Error claiming refund:", error);
+    process.exit(1);
+  }
+}
+
+async function raceTokenAccounts(
+  connection: anchor.web3.Connection,
+  mint: PublicKey | null,
+  raceVault: PublicKey,
+  owner: PublicKey
+) {
+  if (!mint) {
+    return { mint: null, playerTokenAccount: null, raceVaultTokenAccount: null, tokenProgram: null };
+  }
+
+  const tokenProgram = (await connection.getAccountInfo(mint)).owner;
+  return {
+    mint,
+    playerTokenAccount: getAssociatedTokenAddressSync(mint, owner, false, tokenProgram),
+    raceVaultTokenAccount: getAssociatedTokenAddressSync(mint, raceVault, true, tokenProgram),
+    tokenProgram,
+  };
+}
+
+function decodeReferralCode(referralCode: string): number | null {
+  // Decode referral code back to race_id
+  if (referralCode.length !== 8) {
+    return null;
+  }
+  
+  const chars = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+  let raceId = 0;
+  let multiplier = 1;
+  
+  for (let i = 0; i < referralCode.length; i++) {
+    const c = referralCode[i];
+    const idx = chars.indexOf(c);
+    if (idx === -1) {
+      return null;
+    }
+    raceId += idx * multiplier;
+    multiplier *= 36;
+  }
+  
+  return raceId;
+}
+
+
+main()
+  .then(() => process.exit(0))
+  .catch((error) => {
+    console.error(error);
+    process.exit(1);
+  });
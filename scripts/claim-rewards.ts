@@ -0,0 +1,69 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { GorRace } from "../target/types/gor_race";
+import { PublicKey } from "@solana/web3.js";
+import * as dotenv from "dotenv";
+
+// Load environment variables from .env file
+dotenv.config();
+
+async function main() {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.GorRace as Program<GorRace>;
+  const beneficiary = provider.wallet as anchor.Wallet;
+
+  console.log("Claiming creator and referrer rewards...");
+  console.log("Beneficiary:", beneficiary.publicKey.toString());
+
+  // Derive PDAs
+  const [rewardAccountPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("reward_account"), beneficiary.publicKey.toBuffer()],
+    program.programId
+  );
+
+  const [platformVaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("platform_vault")],
+    program.programId
+  );
+
+  try {
+    const rewardAccount = await program.account.rewardAccount.fetchNullable(rewardAccountPda);
+    if (!rewardAccount) {
+      console.log("⚠️  No rewards yet. Create races or refer players to start earning.");
+      return;
+    }
+
+    console.log("Total earned:", rewardAccount.totalEarned.toNumber() / anchor.web3.LAMPORTS_PER_SOL, "GOR");
+    console.log("Total claimed:", rewardAccount.totalClaimed.toNumber() / anchor.web3.LAMPORTS_PER_SOL, "GOR");
+
+    if (rewardAccount.owed.isZero()) {
+      console.log("⚠️  Nothing to claim right now.");
+      return;
+    }
+
+    const tx = await program.methods
+      .claimRewards()
+      .accounts({
+        rewardAccount: rewardAccountPda,
+        beneficiary: beneficiary.publicKey,
+        platformVault: platformVaultPda,
+      })
+      .rpc();
+
+    console.log("✅ Rewards claimed successfully!");
+    console.log("📋 Transaction signature:", tx);
+    console.log("💰 Amount:", rewardAccount.owed.toNumber() / anchor.web3.LAMPORTS_PER_SOL, "GOR");
+  } catch (error) {
+    console.error("❌ Error claiming rewards:", error);
+    process.exit(1);
+  }
+}
+
+main()
+  .then(() => process.exit(0))
+  .catch((error) => {
+    console.error(error);
+    process.exit(1);
+  });
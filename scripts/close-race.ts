@@ -0,0 +1,239 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { GorRace } from "../target/types/gor_race";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import * as dotenv from "dotenv";
+
+// Load environment variables from .env file
+dotenv.config();
+
+const BET_KINDS = ["win", "show", "exacta", "quinella", "trifecta"];
+const COMBINATION_BET_KINDS = ["exacta", "quinella", "trifecta"];
+
+async function main() {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.GorRace as Program<GorRace>;
+
+  // Get referral code or race ID from command line
+  const input = process.argv[2];
+  let raceId: number;
+
+  if (!input) {
+    console.error("Usage: npm run close-race <REFERRAL_CODE_OR_RACE_ID>");
+    console.error("Example: npm run close-race XYVSYS00");
+    console.error("Example: npm run close-race 1751510633");
+    process.exit(1);
+  }
+
+  if (input.length === 8 && isNaN(Number(input))) {
+    // Assume it's a referral code
+    raceId = decodeReferralCode(input);
+    if (!raceId) {
+      console.error("❌ Invalid referral code format");
+      process.exit(1);
+    }
+    console.log("Decoded Race ID:", raceId);
+  } else {
+    // Assume it's a race ID
+    raceId = parseInt(input);
+    if (isNaN(raceId)) {
+      console.error("❌ Invalid race ID format");
+      process.exit(1);
+    }
+    console.log("Race ID:", raceId);
+  }
+
+  // Derive PDAs
+  const [racePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("race"), new anchor.BN(raceId).toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+
+  const [raceVaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("race_vault"), racePda.toBuffer()],
+    program.programId
+  );
+
+  const [platformVaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("platform_vault")],
+    program.programId
+  );
+
+  try {
+    const race = await program.account.race.fetch(racePda);
+    const raceStatus = Object.keys(race.status)[0];
+    if (raceStatus !== "completed" && raceStatus !== "cancelled") {
+      console.error(`❌ Only completed or cancelled races can be closed. Current status: ${raceStatus}`);
+      process.exit(1);
+    }
+
+    // 1. Close every settled entry, rent goes back to each player
+    const entries = await program.account.playerEntry.all([
+      { memcmp: { offset: 8 + 32, bytes: racePda.toBase58() } },
+    ]);
+    console.log(`\n🎟️  Closing ${entries.length} entries...`);
+
+    for (const { publicKey, account } of entries) {
+      const combinationPools = [...new Set(account.bets
+        .map(bet => Object.keys(bet.betKind)[0])
+        .filter(kind => COMBINATION_BET_KINDS.includes(kind)))]
+        .map(kind => ({
+          pubkey: winningCombinationPool(program.programId, racePda, kind, race.winningHorses),
+          isWritable: false,
+          isSigner: false,
+        }));
+
+      try {
+        await program.methods
+          .closePlayerEntry()
+          .accounts({
+            race: racePda,
+            playerEntry: publicKey,
+            player: account.player,
+            referrerRewards: account.referrer
+              ? PublicKey.findProgramAddressSync(
+                  [Buffer.from("reward_account"), account.referrer.toBuffer()],
+                  program.programId
+                )[0]
+              : null,
+          })
+          .remainingAccounts(combinationPools)
+          .rpc();
+        console.log("✅ Closed entry of", account.player.toString());
+      } catch (error) {
+        console.log("⏳ Entry of", account.player.toString(), "is not settled yet:", error.error?.errorCode?.code ?? error.message);
+
+        // Credit the referrer now rather than when the winner gets round to claiming
+        if (account.referrer && !account.referralSettled && raceStatus === "completed") {
+          await program.methods
+            .settleReferral()
+            .accounts({
+              race: racePda,
+              playerEntry: publicKey,
+              referrerRewards: PublicKey.findProgramAddressSync(
+                [Buffer.from("reward_account"), account.referrer.toBuffer()],
+                program.programId
+              )[0],
+            })
+            .rpc();
+          console.log("✅ Credited the referrer of", account.player.toString());
+        }
+      }
+    }
+
+    // 2. Close the exotic bet pools once no entry can read them any more
+    const pools = await program.account.combinationPool.all([
+      { memcmp: { offset: 8, bytes: racePda.toBase58() } },
+    ]);
+    const updatedRace = await program.account.race.fetch(racePda);
+    if (updatedRace.closedEntries < updatedRace.entryCount) {
+      console.log(`\n⏳ ${updatedRace.entryCount - updatedRace.closedEntries} entries still open, the race cannot be closed yet.`);
+      return;
+    }
+
+    for (const { publicKey, account } of pools) {
+      await program.methods
+        .closeCombinationPool()
+        .accounts({
+          race: racePda,
+          combinationPool: publicKey,
+          payer: account.payer,
+        })
+        .rpc();
+      console.log("✅ Closed combination pool", publicKey.toString());
+    }
+
+    // 3. Close the race itself, sweeping vault dust to the platform
+    const tokenAccounts = await raceTokenAccounts(provider.connection, race.mint, raceVaultPda, platformVaultPda);
+
+    const tx = await program.methods
+      .closeRace()
+      .accounts({
+        race: racePda,
+        creator: race.creator,
+        raceVault: raceVaultPda,
+        platformVault: platformVaultPda,
+        ...tokenAccounts,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("\n✅ Race closed, rent returned to the creator");
+    console.log("📋 Transaction signature:", tx);
+  } catch (error) {
+    console.error("❌ Error closing race:", error);
+    process.exit(1);
+  }
+}
+
+function winningCombinationPool(
+  programId: PublicKey,
+  race: PublicKey,
+  betKind: string,
+  winningHorses: number[]
+) {
+  const [first, second, third] = winningHorses;
+  const selection = {
+    exacta: [first, second, 0],
+    quinella: [Math.min(first, second), Math.max(first, second), 0],
+    trifecta: [first, second, third],
+  }[betKind];
+
+  const [combinationPoolPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("combination_pool"), race.toBuffer(), Buffer.from([BET_KINDS.indexOf(betKind)]), Buffer.from(selection)],
+    programId
+  );
+  return combinationPoolPda;
+}
+
+async function raceTokenAccounts(
+  connection: anchor.web3.Connection,
+  mint: PublicKey | null,
+  raceVault: PublicKey,
+  platformVault: PublicKey
+) {
+  if (!mint) {
+    return { mint: null, raceVaultTokenAccount: null, platformTokenAccount: null, tokenProgram: null };
+  }
+
+  const tokenProgram = (await connection.getAccountInfo(mint)).owner;
+  return {
+    mint,
+    raceVaultTokenAccount: getAssociatedTokenAddressSync(mint, raceVault, true, tokenProgram),
+    platformTokenAccount: getAssociatedTokenAddressSync(mint, platformVault, true, tokenProgram),
+    tokenProgram,
+  };
+}
+
+function decodeReferralCode(referralCode: string): number | null {
+  // Decode referral code back to race_id
+  if (referralCode.length !== 8) {
+    return null;
+  }
+  
+  const chars = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+  let raceId = 0;
+  let multiplier = 1;
+  
+  for (let i = 0; i < referralCode.length; i++) {
+    const c = referralCode[i];
+    const idx = chars.indexOf(c);
+    if (idx === -1) {
+      return null;
+    }
+    raceId += idx * multiplier;
+    multiplier *= 36;
+  }
+  
+  return raceId;
+}
+
+main()
+  .then(() => process.exit(0))
+  .catch((error) => {
+    console.error(error);
+    process.exit(1);
+  });
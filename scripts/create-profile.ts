@@ -37,6 +37,12 @@ async function main() {
     program.programId
   );
 
+  // Usernames are unique regardless of case
+  const [usernamePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("username"), Buffer.from(username.toLowerCase())],
+    program.programId
+  );
+
   try {
     // Check if profile already exists
     try {
@@ -67,11 +73,18 @@ async function main() {
       process.exit(1);
     }
 
+    const existingUsername = await program.account.username.fetchNullable(usernamePda);
+    if (existingUsername) {
+      console.error(`❌ Username "${username}" is already taken`);
+      process.exit(1);
+    }
+
     // Create profile
     const tx = await program.methods
       .createProfile(username)
       .accounts({
         playerProfile: playerProfilePda,
+        usernameAccount: usernamePda,
         player: player.publicKey,
         systemProgram: SystemProgram.programId,
         clock: SYSVAR_CLOCK_PUBKEY,
@@ -2,8 +2,10 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { GorRace } from "../target/types/gor_race";
 import { PublicKey, SystemProgram, SYSVAR_CLOCK_PUBKEY } from "@solana/web3.js";
+import { ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
 import * as fs from "fs";
 import * as path from "path";
+import * as crypto from "crypto";
 import * as dotenv from "dotenv";
 
 // Load environment variables from .env file
@@ -22,6 +24,17 @@ async function main() {
   // Get wait time from command line args or use default
   const args = process.argv.slice(2);
   const waitTime = args.length > 0 ? parseInt(args[0]) : null;
+  // Pass "parimutuel" as the second argument for a pool betting race
+  const mode = args[1] === "parimutuel" ? { pariMutuel: {} } : { fixed: {} };
+  // Optional SPL or Token-2022 mint the race is paid in, "native" or empty for GOR
+  const mint = args[2] && args[2] !== "native" ? new PublicKey(args[2]) : null;
+  // Optional entry fee (in base units), max and min players, platform defaults otherwise
+  const raceConfig = {
+    entryFee: args[3] ? new anchor.BN(args[3]) : null,
+    maxPlayers: args[4] ? parseInt(args[4]) : null,
+    minPlayers: args[5] ? parseInt(args[5]) : null,
+    prizeDistribution: null,
+  };
   
   if (waitTime !== null) {
     if (waitTime < 30 || waitTime > 180) {
@@ -33,8 +46,13 @@ async function main() {
     console.log("Using default wait time: 60 seconds");
   }
 
-  // Generate race ID using same method as program (unix timestamp)
-  const raceId = new anchor.BN(Math.floor(Date.now() / 1000));
+  const [platformVaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("platform_vault")],
+    program.programId
+  );
+
+  // The program numbers races with its own counter, this race takes the next id
+  const raceId = (await program.account.platformVault.fetch(platformVaultPda)).nextRaceId;
   
   // Derive race PDA
   const [racePda] = PublicKey.findProgramAddressSync(
@@ -48,6 +66,34 @@ async function main() {
     program.programId
   );
 
+  // Commit to a secret seed now, it is revealed by execute-race to finish the race
+  const seed = crypto.randomBytes(32);
+  const seedCommitment = crypto.createHash("sha256").update(seed).digest();
+
+  // Save the seed before sending, a race whose seed is lost can only be cancelled
+  const racesPath = path.join(__dirname, "..", "races");
+  if (!fs.existsSync(racesPath)) {
+    fs.mkdirSync(racesPath);
+  }
+  const pendingFilePath = path.join(racesPath, `pending_${seedCommitment.toString("hex")}.json`);
+  fs.writeFileSync(pendingFilePath, JSON.stringify({ seed: seed.toString("hex") }, null, 2));
+
+  // Token races hold entries in an associated token account owned by the race vault
+  let tokenProgram: PublicKey | null = null;
+  let raceVaultTokenAccount: PublicKey | null = null;
+  let platformTokenAccount: PublicKey | null = null;
+  if (mint) {
+    const mintInfo = await provider.connection.getAccountInfo(mint);
+    if (!mintInfo) {
+      console.error("Mint not found:", mint.toString());
+      process.exit(1);
+    }
+    tokenProgram = mintInfo.owner;
+    raceVaultTokenAccount = getAssociatedTokenAddressSync(mint, raceVaultPda, true, tokenProgram);
+    platformTokenAccount = getAssociatedTokenAddressSync(mint, platformVaultPda, true, tokenProgram);
+    console.log("Mint:", mint.toString());
+  }
+
   console.log("Race ID:", raceId.toString());
   console.log("Race PDA:", racePda.toString());
   console.log("Race Vault PDA:", raceVaultPda.toString());
@@ -55,10 +101,21 @@ async function main() {
   try {
     // Create race
     const tx = await program.methods
-      .createRace(raceId, waitTime ? new anchor.BN(waitTime) : null)
+      .createRace(waitTime ? new anchor.BN(waitTime) : null, Array.from(seedCommitment), mode, raceConfig)
       .accounts({
         race: racePda,
         creator: creator.publicKey,
+        creatorRewards: PublicKey.findProgramAddressSync(
+          [Buffer.from("reward_account"), creator.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        raceVault: raceVaultPda,
+        platformVault: platformVaultPda,
+        mint,
+        raceVaultTokenAccount,
+        platformTokenAccount,
+        tokenProgram,
+        associatedTokenProgram: mint ? ASSOCIATED_TOKEN_PROGRAM_ID : null,
         systemProgram: SystemProgram.programId,
         clock: SYSVAR_CLOCK_PUBKEY,
       })
@@ -71,15 +128,20 @@ async function main() {
     const race = await program.account.race.fetch(racePda);
     console.log("\nRace Details:");
     console.log("Status:", Object.keys(race.status)[0]);
+    console.log("Mode:", Object.keys(race.mode)[0]);
     console.log("Creator:", race.creator.toString());
     console.log("Max Players:", race.maxPlayers);
+    console.log("Min Players:", race.minPlayers);
+    console.log("Entry Fee:", race.entryFee.toString());
+    console.log("Prize Distribution (bps):", race.prizeDistribution.join(", "));
     console.log("Wait Time:", race.waitTime.toNumber(), "seconds");
     console.log("Referral Code:", race.referralCode);
     console.log("Start time:", new Date(race.startTime.toNumber() * 1000).toLocaleString());
     console.log("Race will expire at:", new Date((race.startTime.toNumber() + race.waitTime.toNumber()) * 1000).toLocaleString());
-    console.log("\nHorse names:");
+    console.log("\nHorses (speed / stamina / form):");
     race.horseNames.forEach((name, index) => {
-      console.log(`  ${index + 1}. ${name}`);
+      const { speed, stamina, form } = race.horseAttributes[index];
+      console.log(`  ${index + 1}. ${name} - ${speed} / ${stamina} / ${form}`);
     });
     
     console.log("\n🎯 Share this referral code with players:");
@@ -92,22 +154,24 @@ async function main() {
       raceId: raceId.toString(),
       racePda: racePda.toString(),
       raceVaultPda: raceVaultPda.toString(),
+      mint: mint ? mint.toString() : null,
       creator: creator.publicKey.toString(),
       maxPlayers: race.maxPlayers,
+      minPlayers: race.minPlayers,
+      entryFee: race.entryFee.toString(),
       waitTime: race.waitTime.toNumber(),
       referralCode: race.referralCode,
       startTime: race.startTime.toNumber(),
       horseNames: race.horseNames,
+      horseAttributes: race.horseAttributes,
+      // Keep this secret until the race finishes, it is needed to reveal the results
+      seed: seed.toString("hex"),
       timestamp: new Date().toISOString(),
     };
 
-    const racesPath = path.join(__dirname, "..", "races");
-    if (!fs.existsSync(racesPath)) {
-      fs.mkdirSync(racesPath);
-    }
-
     const raceFilePath = path.join(racesPath, `race_${raceId}.json`);
     fs.writeFileSync(raceFilePath, JSON.stringify(raceInfo, null, 2));
+    fs.unlinkSync(pendingFilePath);
     console.log("\nRace info saved to:", raceFilePath);
 
   } catch (error) {
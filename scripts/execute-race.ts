@@ -1,7 +1,10 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { GorRace } from "../target/types/gor_race";
-import { PublicKey, SYSVAR_CLOCK_PUBKEY, SystemProgram } from "@solana/web3.js";
+import { PublicKey, SYSVAR_CLOCK_PUBKEY, SYSVAR_SLOT_HASHES_PUBKEY, SystemProgram } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import * as fs from "fs";
+import * as path from "path";
 import * as dotenv from "dotenv";
 
 // Load environment variables from .env file
@@ -81,17 +84,20 @@ async function main() {
     
     // Check if we can execute
     const status = Object.keys(race.status)[0];
+    let seed: number[] | null = null;
     if (status === 'pending') {
-      if (currentTime < waitEndTime) {
+      // A full race starts without waiting out the wait time
+      const isFull = race.entryCount >= race.maxPlayers;
+      if (currentTime < waitEndTime && !isFull) {
         console.log("\\n❌ Cannot start race yet. Wait time not expired.");
         console.log("Time remaining:", waitEndTime - currentTime, "seconds");
         return;
       }
-      if (race.entryCount < 1) { // MIN_PLAYERS_TO_START = 1 (changed from 3)
-        console.log("\\n❌ Cannot start race. Not enough players. Need at least 1 player.");
-        return;
+      if (race.entryCount < race.minPlayers) {
+        console.log("\\n⚠️ Not enough players, need at least", race.minPlayers, "- cancelling the race...");
+      } else {
+        console.log("\\n✅ Starting race simulation...");
       }
-      console.log("\\n✅ Starting race simulation...");
     } else if (status === 'racing') {
       if (!race.raceStartTime) {
         console.log("\\n❌ Race is in racing status but no start time found.");
@@ -103,21 +109,53 @@ async function main() {
         console.log("Time remaining:", raceEndTime - currentTime, "seconds");
         return;
       }
-      console.log("\\n✅ Finishing race and determining winners...");
+      seed = loadRaceSeed(raceId, race.seedCommitment);
+      if (!seed) {
+        console.log("\\n⚠️  No seed found in races/race_" + raceId + ".json, cancelling race if the reveal window expired...");
+      } else {
+        console.log("\\n✅ Revealing seed, finishing race and determining winners...");
+      }
     } else {
       console.log("\\n❌ Race cannot be executed. Status:", status);
       return;
     }
 
+    // Token races pay the platform fee into the platform vault's token account,
+    // and the crank reward to the wallet's token account when it has one
+    let tokenAccounts = {
+      mint: null,
+      raceVaultTokenAccount: null,
+      platformTokenAccount: null,
+      executorTokenAccount: null,
+      tokenProgram: null,
+    };
+    if (race.mint) {
+      const tokenProgram = (await provider.connection.getAccountInfo(race.mint)).owner;
+      const executorTokenAccount = getAssociatedTokenAddressSync(race.mint, provider.wallet.publicKey, false, tokenProgram);
+      tokenAccounts = {
+        mint: race.mint,
+        raceVaultTokenAccount: getAssociatedTokenAddressSync(race.mint, raceVaultPda, true, tokenProgram),
+        platformTokenAccount: getAssociatedTokenAddressSync(race.mint, platformVaultPda, true, tokenProgram),
+        executorTokenAccount: (await provider.connection.getAccountInfo(executorTokenAccount)) ? executorTokenAccount : null,
+        tokenProgram,
+      };
+    }
+
     // Execute race
     const tx = await program.methods
-      .executeRace()
+      .executeRace(seed)
       .accounts({
         race: racePda,
         raceVault: raceVaultPda,
         platformVault: platformVaultPda,
-        recentBlockhashes: anchor.web3.SYSVAR_RECENT_BLOCKHASHES_PUBKEY,
+        creatorRewards: PublicKey.findProgramAddressSync(
+          [Buffer.from("reward_account"), race.creator.toBuffer()],
+          program.programId
+        )[0],
+        executor: provider.wallet.publicKey,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
         clock: SYSVAR_CLOCK_PUBKEY,
+        ...tokenAccounts,
         systemProgram: SystemProgram.programId,
       })
       .rpc();
@@ -144,7 +182,7 @@ async function main() {
       console.log("💸 Platform fee:", updatedRace.platformFee.toNumber() / anchor.web3.LAMPORTS_PER_SOL, "GOR");
       console.log("🏁 Race completed at:", new Date(updatedRace.endTime.toNumber() * 1000).toLocaleString());
     } else if (Object.keys(updatedRace.status)[0] === 'cancelled') {
-      console.log("❌ Race was cancelled - not enough players joined.");
+      console.log("❌ Race was cancelled - not enough players joined or the seed was never revealed.");
     }
 
   } catch (error) {
@@ -153,6 +191,22 @@ async function main() {
   }
 }
 
+function loadRaceSeed(raceId: number, seedCommitment: number[]): number[] | null {
+  // Seeds are saved by create-race alongside the rest of the race info, or under
+  // their commitment when create-race never saw the race confirmed
+  const racesPath = path.join(__dirname, "..", "races");
+  const raceFilePath = [
+    path.join(racesPath, `race_${raceId}.json`),
+    path.join(racesPath, `pending_${Buffer.from(seedCommitment).toString("hex")}.json`),
+  ].find((file) => fs.existsSync(file));
+  if (!raceFilePath) {
+    return null;
+  }
+
+  const raceInfo = JSON.parse(fs.readFileSync(raceFilePath, "utf8"));
+  return raceInfo.seed ? Array.from(Buffer.from(raceInfo.seed, "hex")) : null;
+}
+
 function decodeReferralCode(referralCode: string): number | null {
   // Decode referral code back to race_id
   if (referralCode.length !== 8) {
@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { GorRace } from "../target/types/gor_race";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
 import * as dotenv from "dotenv";
 
 // Load environment variables from .env file
@@ -14,13 +15,21 @@ async function main() {
   const program = anchor.workspace.GorRace as Program<GorRace>;
   const player = provider.wallet as anchor.Wallet;
 
-  // Get referral code and horse number from command line
+  // Get referral code and bet from command line, a bet is a horse number
+  // or <win|show|exacta|quinella|trifecta>:<horse>[,<horse>...]
   const referralCode = process.argv[2];
-  const horseNumber = parseInt(process.argv[3] || "1");
+  const { betKind, selection } = parseBet(process.argv[3] || "1");
+  // Optional stake in GOR, fixed race win bets always pay the entry fee
+  const stake = process.argv[4] ? new anchor.BN(parseFloat(process.argv[4]) * anchor.web3.LAMPORTS_PER_SOL) : null;
+  // Optional referrer wallet, earns a share of the platform fee on this entry
+  const referrer = process.argv[5] ? new PublicKey(process.argv[5]) : null;
   
   if (!referralCode) {
-    console.error("Usage: npm run join-race <REFERRAL_CODE> [HORSE_NUMBER]");
+    console.error("Usage: npm run join-race <REFERRAL_CODE> [BET] [STAKE_GOR] [REFERRER]");
     console.error("Example: npm run join-race XYVSYS00 3");
+    console.error("Example: npm run join-race XYVSYS00 3 0.5");
+    console.error("Example: npm run join-race XYVSYS00 exacta:3,7 0.5");
+    console.error("Example: npm run join-race XYVSYS00 3 0.1 <REFERRER_WALLET>");
     process.exit(1);
   }
   
@@ -29,15 +38,23 @@ async function main() {
     process.exit(1);
   }
   
-  if (horseNumber < 1 || horseNumber > 10) {
-    console.error("Horse number must be between 1 and 10");
+  if (selection.some(h => h < 1 || h > 10)) {
+    console.error("Horse numbers must be between 1 and 10");
+    process.exit(1);
+  }
+  
+  if (new Set(selection).size !== selection.length) {
+    console.error("A bet cannot name the same horse twice");
     process.exit(1);
   }
 
   console.log("Joining race...");
   console.log("Player:", player.publicKey.toString());
   console.log("Referral Code:", referralCode);
-  console.log("Horse Number:", horseNumber);
+  console.log("Bet:", betKind, selection.join(", "));
+  if (referrer) {
+    console.log("Referrer:", referrer.toString());
+  }
 
   // Check player GOR balance (native token)
   const balance = await provider.connection.getBalance(player.publicKey);
@@ -72,6 +89,27 @@ async function main() {
     program.programId
   );
 
+  const [platformVaultPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("platform_vault")],
+    program.programId
+  );
+
+  // Exotic bets share a pool with everyone backing the same combination
+  const selectionBytes = [...selection, 0, 0].slice(0, 3);
+  const combinationPool = selection.length > 1
+    ? PublicKey.findProgramAddressSync(
+        [Buffer.from("combination_pool"), racePda.toBuffer(), Buffer.from([BET_KINDS.indexOf(betKind)]), Buffer.from(selectionBytes)],
+        program.programId
+      )[0]
+    : null;
+
+  const referrerRewards = referrer
+    ? PublicKey.findProgramAddressSync(
+        [Buffer.from("reward_account"), referrer.toBuffer()],
+        program.programId
+      )[0]
+    : null;
+
   try {
     // Verify race exists and get race data
     const race = await program.account.race.fetch(racePda);
@@ -101,17 +139,32 @@ async function main() {
       process.exit(1);
     }
     
-    console.log("\nSelected horse:", race.horseNames[horseNumber - 1]);
-    console.log("Entry fee: 0.1 GOR");
+    console.log("\nSelected horses (speed / stamina / form):");
+    for (const h of selection) {
+      const { speed, stamina, form } = race.horseAttributes[h - 1];
+      console.log(`  ${h}. ${race.horseNames[h - 1]} - ${speed} / ${stamina} / ${form}`);
+    }
+    if (stake) {
+      console.log("Stake:", stake.toNumber() / anchor.web3.LAMPORTS_PER_SOL, "GOR");
+    } else {
+      console.log("Entry fee:", race.entryFee.toNumber() / anchor.web3.LAMPORTS_PER_SOL, "GOR");
+    }
     
+    // Token races settle through associated token accounts, native races pass none
+    const tokenAccounts = await raceTokenAccounts(provider.connection, race.mint, raceVaultPda, player.publicKey);
+
     // Join race
     const tx = await program.methods
-      .joinRace(horseNumber, referralCode)
+      .joinRace({ [betKind]: {} }, selectionBytes, referralCode, stake, referrer)
       .accounts({
         race: racePda,
         playerEntry: playerEntryPda,
         player: player.publicKey,
         raceVault: raceVaultPda,
+        combinationPool,
+        referrerRewards,
+        ...tokenAccounts,
+        platformVault: platformVaultPda,
         systemProgram: SystemProgram.programId,
       })
       .rpc();
@@ -128,8 +181,11 @@ async function main() {
     // Fetch player entry data
     const playerEntry = await program.account.playerEntry.fetch(playerEntryPda);
     console.log("\nYour Entry:");
-    console.log("Horse:", playerEntry.horseNumber, "-", updatedRace.horseNames[playerEntry.horseNumber - 1]);
-    console.log("Entry amount:", playerEntry.entryAmount.toNumber() / anchor.web3.LAMPORTS_PER_SOL, "GOR");
+    for (const bet of playerEntry.bets) {
+      const horses = bet.selection.filter(h => h > 0).map(h => `${h} - ${updatedRace.horseNames[h - 1]}`);
+      console.log(`${Object.keys(bet.betKind)[0]}:`, horses.join(", "), "|", bet.stake.toNumber() / anchor.web3.LAMPORTS_PER_SOL, "GOR");
+    }
+    console.log("Total staked:", playerEntry.entryAmount.toNumber() / anchor.web3.LAMPORTS_PER_SOL, "GOR");
     
     const currentTime = Math.floor(Date.now() / 1000);
     const raceEndTime = updatedRace.startTime.toNumber() + updatedRace.waitTime.toNumber();
@@ -148,6 +204,47 @@ async function main() {
   }
 }
 
+const BET_KINDS = ["win", "show", "exacta", "quinella", "trifecta"];
+const SELECTION_LENGTHS = { win: 1, show: 1, exacta: 2, quinella: 2, trifecta: 3 };
+
+function parseBet(arg: string): { betKind: string; selection: number[] } {
+  const [kind, horses] = arg.includes(":") ? arg.split(":") : ["win", arg];
+  const betKind = kind.toLowerCase();
+  const selection = horses.split(",").map(h => parseInt(h));
+
+  if (!BET_KINDS.includes(betKind) || selection.length !== SELECTION_LENGTHS[betKind] || selection.some(isNaN)) {
+    console.error("Invalid bet:", arg);
+    console.error("Bets are a horse number or <win|show|exacta|quinella|trifecta>:<horse>[,<horse>...]");
+    process.exit(1);
+  }
+
+  // Quinella pairs are stored in ascending order so backers of the same pair share a pool
+  if (betKind === "quinella") {
+    selection.sort((a, b) => a - b);
+  }
+
+  return { betKind, selection };
+}
+
+async function raceTokenAccounts(
+  connection: anchor.web3.Connection,
+  mint: PublicKey | null,
+  raceVault: PublicKey,
+  owner: PublicKey
+) {
+  if (!mint) {
+    return { mint: null, playerTokenAccount: null, raceVaultTokenAccount: null, tokenProgram: null };
+  }
+
+  const tokenProgram = (await connection.getAccountInfo(mint)).owner;
+  return {
+    mint,
+    playerTokenAccount: getAssociatedTokenAddressSync(mint, owner, false, tokenProgram),
+    raceVaultTokenAccount: getAssociatedTokenAddressSync(mint, raceVault, true, tokenProgram),
+    tokenProgram,
+  };
+}
+
 function decodeReferralCode(referralCode: string): number | null {
   // Decode referral code back to race_id
   if (referralCode.length !== 8) {
@@ -0,0 +1,90 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { GorRace } from "../target/types/gor_race";
+import { PublicKey, SystemProgram, SYSVAR_CLOCK_PUBKEY } from "@solana/web3.js";
+import * as dotenv from "dotenv";
+
+// Load environment variables from .env file
+dotenv.config();
+
+async function main() {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.GorRace as Program<GorRace>;
+  const player = provider.wallet as anchor.Wallet;
+
+  // Get the new username from command line
+  const newUsername = process.argv[2];
+
+  if (!newUsername) {
+    console.error("Usage: npm run rename-profile <NEW_USERNAME>");
+    console.error("Example: npm run rename-profile HorseWhisperer");
+    process.exit(1);
+  }
+
+  if (newUsername.length > 32 || !/^[a-zA-Z0-9_.-]+$/.test(newUsername)) {
+    console.error("❌ Username must be 1-32 letters, numbers, underscores (_), dashes (-) or dots (.)");
+    process.exit(1);
+  }
+
+  // Derive PDAs
+  const [playerProfilePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("player_profile"), player.publicKey.toBuffer()],
+    program.programId
+  );
+
+  try {
+    const profile = await program.account.playerProfile.fetchNullable(playerProfilePda);
+    if (!profile) {
+      console.error("❌ No profile found for this wallet. Create one with: npm run create-profile <USERNAME>");
+      process.exit(1);
+    }
+
+    console.log("Renaming profile...");
+    console.log("Player:", player.publicKey.toString());
+    console.log("Current username:", profile.username);
+    console.log("New username:", newUsername);
+
+    // Names are registered by their lowercase form
+    const [oldUsernamePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("username"), Buffer.from(profile.username.toLowerCase())],
+      program.programId
+    );
+    const [newUsernamePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("username"), Buffer.from(newUsername.toLowerCase())],
+      program.programId
+    );
+
+    const existingUsername = await program.account.username.fetchNullable(newUsernamePda);
+    if (existingUsername && !existingUsername.player.equals(player.publicKey)) {
+      console.error(`❌ Username "${newUsername}" is already taken`);
+      process.exit(1);
+    }
+
+    const tx = await program.methods
+      .renameProfile(newUsername)
+      .accounts({
+        playerProfile: playerProfilePda,
+        oldUsernameAccount: oldUsernamePda,
+        newUsernameAccount: newUsernamePda,
+        player: player.publicKey,
+        systemProgram: SystemProgram.programId,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+
+    console.log("✅ Profile renamed successfully!");
+    console.log("📋 Transaction signature:", tx);
+  } catch (error) {
+    console.error("❌ Error renaming profile:", error);
+    process.exit(1);
+  }
+}
+
+main()
+  .then(() => process.exit(0))
+  .catch((error) => {
+    console.error(error);
+    process.exit(1);
+  });
@@ -90,6 +90,9 @@ async function main() {
       return;
     }
 
+    // Winners count the race when they claim, update-stats is for everyone else
+    const claimed = Object.keys(playerEntry.claimStatus)[0] === 'claimed';
+
     // Check if player profile exists
     let playerProfile;
     try {
@@ -105,11 +108,16 @@ async function main() {
     console.log("🥈 2nd place: Horse", race.winningHorses[1], "-", race.horseNames[race.winningHorses[1] - 1]);
     console.log("🥉 3rd place: Horse", race.winningHorses[2], "-", race.horseNames[race.winningHorses[2] - 1]);
     
-    console.log("\n🐎 Your Horse:");
-    console.log("You selected: Horse", playerEntry.horseNumber, "-", race.horseNames[playerEntry.horseNumber - 1]);
+    console.log("\n🐎 Your Horses:");
+    for (const bet of playerEntry.bets) {
+      console.log(Object.keys(bet.betKind)[0] + ":", "Horse", bet.selection[0], "-", race.horseNames[bet.selection[0] - 1]);
+    }
 
-    // Show what will be updated
-    const wonPosition = race.winningHorses.findIndex(h => h === playerEntry.horseNumber);
+    // Show what will be updated, using the best finish across the player's bets
+    const positions = playerEntry.bets
+      .map(bet => race.winningHorses.findIndex(h => h === bet.selection[0]))
+      .filter(position => position !== -1);
+    const wonPosition = positions.length > 0 ? Math.min(...positions) : -1;
     console.log("\n📊 Stats Update:");
     console.log("Race participation: +1");
     
@@ -126,6 +134,17 @@ async function main() {
       console.log("No prize won this race");
     }
 
+    // Unclaimed entries are checked for prizes on chain, exotic bets against
+    // the winning combination's pool
+    const combinationPools = claimed ? [] : [...new Set(playerEntry.bets
+      .map(bet => Object.keys(bet.betKind)[0])
+      .filter(kind => COMBINATION_BET_KINDS.includes(kind)))]
+      .map(kind => ({
+        pubkey: winningCombinationPool(program.programId, racePda, kind, race.winningHorses),
+        isWritable: false,
+        isSigner: false,
+      }));
+
     // Update stats
     const tx = await program.methods
       .updateStats()
@@ -136,6 +155,7 @@ async function main() {
         player: player.publicKey,
         clock: SYSVAR_CLOCK_PUBKEY,
       })
+      .remainingAccounts(combinationPools)
       .rpc();
 
     console.log("\n✅ Stats updated successfully!");
@@ -165,6 +185,9 @@ async function main() {
   } catch (error) {
     if (error.message && error.message.includes("AccountNotFound")) {
       console.error("❌ Race, player entry, or profile not found.");
+    } else if (error.message && error.message.includes("PrizeNotClaimed")) {
+      console.error("❌ You won a prize in this race, claim it to update your stats:");
+      console.error("   npm run claim-prize", input);
     } else {
       console.error("❌ Error updating stats:", error);
     }
@@ -172,6 +195,29 @@ async function main() {
   }
 }
 
+const BET_KINDS = ["win", "show", "exacta", "quinella", "trifecta"];
+const COMBINATION_BET_KINDS = ["exacta", "quinella", "trifecta"];
+
+function winningCombinationPool(
+  programId: PublicKey,
+  race: PublicKey,
+  betKind: string,
+  winningHorses: number[]
+) {
+  const [first, second, third] = winningHorses;
+  const selection = {
+    exacta: [first, second, 0],
+    quinella: [Math.min(first, second), Math.max(first, second), 0],
+    trifecta: [first, second, third],
+  }[betKind];
+
+  const [combinationPoolPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("combination_pool"), race.toBuffer(), Buffer.from([BET_KINDS.indexOf(betKind)]), Buffer.from(selection)],
+    programId
+  );
+  return combinationPoolPda;
+}
+
 function decodeReferralCode(referralCode: string): number | null {
   // Decode referral code back to race_id
   if (referralCode.length !== 8) {
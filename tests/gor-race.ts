@@ -1,8 +1,22 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { GorRace } from "../target/types/gor_race";
-import { PublicKey, Keypair, SystemProgram, SYSVAR_CLOCK_PUBKEY } from "@solana/web3.js";
+import { PublicKey, Keypair, SystemProgram, SYSVAR_CLOCK_PUBKEY, SYSVAR_SLOT_HASHES_PUBKEY } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccount,
+  createInitializeMintInstruction,
+  createInitializeNonTransferableMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMintLen,
+  mintTo,
+} from "@solana/spl-token";
 import { assert } from "chai";
+import * as crypto from "crypto";
 import * as dotenv from "dotenv";
 import bs58 from "bs58";
 
@@ -21,6 +35,7 @@ describe("gor-race", () => {
   let player1: Keypair;
   let player2: Keypair;
   let player3: Keypair;
+  let creator: Keypair;
 
   // PDAs
   let platformVaultPda: PublicKey;
@@ -29,12 +44,16 @@ describe("gor-race", () => {
   let player1ProfilePda: PublicKey;
   let player2ProfilePda: PublicKey;
   let player3ProfilePda: PublicKey;
+  let creatorRewardsPda: PublicKey;
+  let referrerRewardsPda: PublicKey;
 
   // Test data
   let raceId: number;
   let referralCode: string;
+  const raceSeed = crypto.randomBytes(32);
   const ENTRY_FEE = 100_000_000; // 0.1 GOR in lamports
   const PLATFORM_FEE_BPS = 500; // 5%
+  const CREATOR_BOND = 100_000_000; // 0.1 GOR held on every race
 
   before(async () => {
     // Use your local wallet as authority
@@ -72,22 +91,172 @@ describe("gor-race", () => {
       program.programId
     );
 
-    // Generate race ID and derive related PDAs with random component
-    raceId = Math.floor(Date.now() / 1000) + Math.floor(Math.random() * 10000);
-    [racePda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("race"), new anchor.BN(raceId).toArrayLike(Buffer, "le", 8)],
+    // Creators may not bet on their own races, so a separate wallet creates and reveals them
+    creator = Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: authority.publicKey,
+          toPubkey: creator.publicKey,
+          lamports: 2_000_000_000,
+        })
+      )
+    );
+    [creatorRewardsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_account"), creator.publicKey.toBuffer()],
       program.programId
     );
 
-    [raceVaultPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("race_vault"), racePda.toBuffer()],
+    // Player 1 refers player 3 to the test race
+    [referrerRewardsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_account"), player1.publicKey.toBuffer()],
       program.programId
     );
 
-    // Generate referral code for testing
-    referralCode = generateReferralCode(raceId);
   });
 
+  // Events the program emitted in a confirmed transaction, decoded from its logs
+  async function emittedEvents(signature: string) {
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return tx.meta.logMessages
+      .filter(log => log.startsWith("Program data: "))
+      .map(log => program.coder.events.decode(log.slice("Program data: ".length)))
+      .filter(event => event !== null);
+  }
+
+  async function emittedEvent(signature: string, name: string) {
+    const event = (await emittedEvents(signature)).find(event => event.name === name);
+    assert.isDefined(event, `${name} should be emitted`);
+    return event.data as any;
+  }
+
+  // Player 1 creates a native race with the platform's next race id
+  async function createRace(config: { maxPlayers?: number; minPlayers?: number; mode?: object }, waitTime = 30) {
+    const id = (await program.account.platformVault.fetch(platformVaultPda)).nextRaceId;
+    const [race] = PublicKey.findProgramAddressSync(
+      [Buffer.from("race"), id.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [raceVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("race_vault"), race.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createRace(
+        new anchor.BN(waitTime),
+        Array.from(crypto.createHash("sha256").update(raceSeed).digest()),
+        config.mode ?? { fixed: {} },
+        { entryFee: null, maxPlayers: config.maxPlayers ?? null, minPlayers: config.minPlayers ?? null, prizeDistribution: null }
+      )
+      .accounts({
+        race,
+        creator: creator.publicKey,
+        raceVault,
+        platformVault: platformVaultPda,
+        mint: null,
+        raceVaultTokenAccount: null,
+        platformTokenAccount: null,
+        tokenProgram: null,
+        associatedTokenProgram: null,
+        creatorRewards: creatorRewardsPda,
+        systemProgram: SystemProgram.programId,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([creator])
+      .rpc();
+
+    return { race, raceVault, referralCode: generateReferralCode(id.toNumber()) };
+  }
+
+  async function joinRace(
+    race: PublicKey,
+    raceVault: PublicKey,
+    code: string,
+    player: Keypair,
+    horse: number,
+    amount: anchor.BN | null = null
+  ) {
+    const [playerEntry] = PublicKey.findProgramAddressSync(
+      [Buffer.from("player_entry"), race.toBuffer(), player.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .joinRace({ win: {} }, [horse, 0, 0], code, amount, null)
+      .accounts({
+        race,
+        playerEntry,
+        player: player.publicKey,
+        raceVault,
+        mint: null,
+        playerTokenAccount: null,
+        raceVaultTokenAccount: null,
+        tokenProgram: null,
+        combinationPool: null,
+        platformVault: platformVaultPda,
+        referrerRewards: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+
+    return playerEntry;
+  }
+
+  // Start or cancel a race, or finish it when the creator passes the seed
+  async function executeRace(race: PublicKey, raceVault: PublicKey, executor: Keypair, seed: Buffer | null = null) {
+    return await program.methods
+      .executeRace(seed ? Array.from(seed) : null)
+      .accounts({
+        race,
+        raceVault,
+        platformVault: platformVaultPda,
+        executor: executor.publicKey,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        mint: null,
+        raceVaultTokenAccount: null,
+        platformTokenAccount: null,
+        executorTokenAccount: null,
+        tokenProgram: null,
+        creatorRewards: creatorRewardsPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([executor])
+      .rpc();
+  }
+
+  async function claimPrize(race: PublicKey, raceVault: PublicKey, player: Keypair) {
+    const [playerEntry] = PublicKey.findProgramAddressSync(
+      [Buffer.from("player_entry"), race.toBuffer(), player.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .claimPrize()
+      .accounts({
+        race,
+        playerEntry,
+        player: player.publicKey,
+        raceVault,
+        platformVault: platformVaultPda,
+        mint: null,
+        playerTokenAccount: null,
+        raceVaultTokenAccount: null,
+        tokenProgram: null,
+        playerProfile: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+
+    return (await program.account.playerEntry.fetch(playerEntry)).prizeAmount;
+  }
+
   describe("Platform Initialization", () => {
     it("should initialize platform vault or skip if exists", async () => {
       try {
@@ -125,6 +294,7 @@ describe("gor-race", () => {
           .createProfile("SpeedDemon")
           .accounts({
             playerProfile: player1ProfilePda,
+            usernameAccount: usernamePda("SpeedDemon"),
             player: player1.publicKey,
             systemProgram: SystemProgram.programId,
             clock: SYSVAR_CLOCK_PUBKEY,
@@ -145,6 +315,7 @@ describe("gor-race", () => {
           .createProfile("CryptoJockey")
           .accounts({
             playerProfile: player2ProfilePda,
+            usernameAccount: usernamePda("CryptoJockey"),
             player: player2.publicKey,
             systemProgram: SystemProgram.programId,
             clock: SYSVAR_CLOCK_PUBKEY,
@@ -165,6 +336,7 @@ describe("gor-race", () => {
           .createProfile("HorseRacer123")
           .accounts({
             playerProfile: player3ProfilePda,
+            usernameAccount: usernamePda("HorseRacer123"),
             player: player3.publicKey,
             systemProgram: SystemProgram.programId,
             clock: SYSVAR_CLOCK_PUBKEY,
@@ -196,6 +368,69 @@ describe("gor-race", () => {
       assert.equal(profile3.username, "HorseRacer123");
     });
 
+    it("should not register a username taken in another case", async () => {
+      // Player 1 holds "SpeedDemon", so player 2 cannot rename to it either
+      try {
+        await program.methods
+          .renameProfile("speeddemon")
+          .accounts({
+            playerProfile: player2ProfilePda,
+            oldUsernameAccount: usernamePda("CryptoJockey"),
+            newUsernameAccount: usernamePda("speeddemon"),
+            player: player2.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([player2])
+          .rpc();
+        assert.fail("Should have thrown error for a taken username");
+      } catch (error) {
+        assert.include(error.toString(), "UsernameAlreadyTaken");
+      }
+
+      const registry = await program.account.username.fetch(usernamePda("SPEEDDEMON"));
+      assert.equal(registry.player.toString(), player1.publicKey.toString());
+      assert.equal(registry.profile.toString(), player1ProfilePda.toString());
+    });
+
+    it("should rename a profile and release the old username", async () => {
+      const renamed = "HorseRacer" + Math.floor(Math.random() * 1_000_000);
+
+      await program.methods
+        .renameProfile(renamed)
+        .accounts({
+          playerProfile: player3ProfilePda,
+          oldUsernameAccount: usernamePda("HorseRacer123"),
+          newUsernameAccount: usernamePda(renamed),
+          player: player3.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([player3])
+        .rpc();
+
+      let profile = await program.account.playerProfile.fetch(player3ProfilePda);
+      assert.equal(profile.username, renamed);
+      assert.isNull(await program.account.username.fetchNullable(usernamePda("HorseRacer123")));
+
+      // Rename back so later runs find the original name
+      await program.methods
+        .renameProfile("HorseRacer123")
+        .accounts({
+          playerProfile: player3ProfilePda,
+          oldUsernameAccount: usernamePda(renamed),
+          newUsernameAccount: usernamePda("HorseRacer123"),
+          player: player3.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([player3])
+        .rpc();
+
+      profile = await program.account.playerProfile.fetch(player3ProfilePda);
+      assert.equal(profile.username, "HorseRacer123");
+    });
+
     it("should fail to create profile with invalid username", async () => {
       const invalidPlayer = Keypair.generate();
       // TODO: Use a wallet with $GOR tokens instead of airdrop
@@ -212,6 +447,7 @@ describe("gor-race", () => {
           .createProfile("") // Empty username
           .accounts({
             playerProfile: invalidProfilePda,
+            usernameAccount: usernamePda(""),
             player: invalidPlayer.publicKey,
             systemProgram: SystemProgram.programId,
             clock: SYSVAR_CLOCK_PUBKEY,
@@ -230,26 +466,73 @@ describe("gor-race", () => {
     it("should create a new race with referral code", async () => {
       const waitTime = 30; // 30 seconds wait time
 
-      await program.methods
-        .createRace(new anchor.BN(raceId), new anchor.BN(waitTime))
+      // The race takes the platform's next race id
+      raceId = (await program.account.platformVault.fetch(platformVaultPda)).nextRaceId.toNumber();
+      [racePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("race"), new anchor.BN(raceId).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      [raceVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("race_vault"), racePda.toBuffer()],
+        program.programId
+      );
+      referralCode = generateReferralCode(raceId);
+
+      const tx = await program.methods
+        .createRace(
+          new anchor.BN(waitTime),
+          Array.from(crypto.createHash("sha256").update(raceSeed).digest()),
+          { fixed: {} },
+          { entryFee: null, maxPlayers: null, minPlayers: null, prizeDistribution: null }
+        )
         .accounts({
           race: racePda,
-          creator: player1.publicKey,
+          creator: creator.publicKey,
+          raceVault: raceVaultPda,
+          platformVault: platformVaultPda,
+          mint: null,
+          raceVaultTokenAccount: null,
+          platformTokenAccount: null,
+          tokenProgram: null,
+          associatedTokenProgram: null,
+          creatorRewards: creatorRewardsPda,
           systemProgram: SystemProgram.programId,
           clock: SYSVAR_CLOCK_PUBKEY,
         })
-        .signers([player1])
+        .signers([creator])
         .rpc();
 
       const race = await program.account.race.fetch(racePda);
       assert.equal(race.raceId.toString(), raceId.toString());
-      assert.equal(race.creator.toString(), player1.publicKey.toString());
+      assert.equal(race.creator.toString(), creator.publicKey.toString());
       assert.equal(race.waitTime.toString(), waitTime.toString());
       assert.equal(race.referralCode, referralCode);
       assert.equal(race.entryCount, 0);
       assert.equal(race.totalPool.toString(), "0");
       assert.equal(race.horseNames.length, 10);
       assert.equal(Object.keys(race.status)[0], "pending");
+
+      // The creator bond sits on the race account on top of its rent
+      assert.equal(race.creatorBond.toNumber(), CREATOR_BOND);
+      assert.isFalse(race.bondForfeited);
+      const raceAccount = await provider.connection.getAccountInfo(racePda);
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(raceAccount.data.length);
+      assert.equal(raceAccount.lamports, rent + CREATOR_BOND);
+
+      const created = await emittedEvent(tx, "RaceCreated");
+      assert.ok(created.race.equals(racePda));
+      assert.equal(created.raceId.toNumber(), raceId);
+      assert.equal(created.referralCode, referralCode);
+      assert.equal(created.creatorBond.toNumber(), CREATOR_BOND);
+
+      const platformVault = await program.account.platformVault.fetch(platformVaultPda);
+      assert.equal(platformVault.nextRaceId.toNumber(), raceId + 1);
+
+      const address = await program.methods
+        .getRaceAddress(new anchor.BN(raceId))
+        .accounts({ platformVault: platformVaultPda })
+        .view();
+      assert.equal(address.toString(), racePda.toString());
     });
   });
 
@@ -261,18 +544,29 @@ describe("gor-race", () => {
         program.programId
       );
 
-      await program.methods
-        .joinRace(3, referralCode)
+      const joinTx = await program.methods
+        .joinRace({ win: {} }, [3, 0, 0], referralCode, null, null)
         .accounts({
           race: racePda,
           playerEntry: player1EntryPda,
           player: player1.publicKey,
           raceVault: raceVaultPda,
+          mint: null,
+          playerTokenAccount: null,
+          raceVaultTokenAccount: null,
+          tokenProgram: null,
+          combinationPool: null,
+          platformVault: platformVaultPda,
+          referrerRewards: null,
           systemProgram: SystemProgram.programId,
         })
         .signers([player1])
         .rpc();
 
+      const joined = await emittedEvent(joinTx, "PlayerJoined");
+      assert.ok(joined.race.equals(racePda));
+      assert.ok(joined.player.equals(player1.publicKey));
+
       // Player 2 joins with horse 5
       const [player2EntryPda] = PublicKey.findProgramAddressSync(
         [Buffer.from("player_entry"), racePda.toBuffer(), player2.publicKey.toBuffer()],
@@ -280,12 +574,19 @@ describe("gor-race", () => {
       );
 
       await program.methods
-        .joinRace(5, referralCode)
+        .joinRace({ win: {} }, [5, 0, 0], referralCode, null, null)
         .accounts({
           race: racePda,
           playerEntry: player2EntryPda,
           player: player2.publicKey,
           raceVault: raceVaultPda,
+          mint: null,
+          playerTokenAccount: null,
+          raceVaultTokenAccount: null,
+          tokenProgram: null,
+          combinationPool: null,
+          platformVault: platformVaultPda,
+          referrerRewards: null,
           systemProgram: SystemProgram.programId,
         })
         .signers([player2])
@@ -298,12 +599,19 @@ describe("gor-race", () => {
       );
 
       await program.methods
-        .joinRace(7, referralCode)
+        .joinRace({ win: {} }, [7, 0, 0], referralCode, null, player1.publicKey)
         .accounts({
           race: racePda,
           playerEntry: player3EntryPda,
           player: player3.publicKey,
           raceVault: raceVaultPda,
+          mint: null,
+          playerTokenAccount: null,
+          raceVaultTokenAccount: null,
+          tokenProgram: null,
+          combinationPool: null,
+          platformVault: platformVaultPda,
+          referrerRewards: referrerRewardsPda,
           systemProgram: SystemProgram.programId,
         })
         .signers([player3])
@@ -316,15 +624,65 @@ describe("gor-race", () => {
 
       // Verify player entries
       const entry1 = await program.account.playerEntry.fetch(player1EntryPda);
-      assert.equal(entry1.horseNumber, 3);
+      assert.equal(entry1.bets[0].selection[0], 3);
       assert.equal(entry1.statsUpdated, false);
       assert.equal(Object.keys(entry1.claimStatus)[0], "unclaimed");
 
       const entry2 = await program.account.playerEntry.fetch(player2EntryPda);
-      assert.equal(entry2.horseNumber, 5);
+      assert.equal(entry2.bets[0].selection[0], 5);
 
       const entry3 = await program.account.playerEntry.fetch(player3EntryPda);
-      assert.equal(entry3.horseNumber, 7);
+      assert.equal(entry3.bets[0].selection[0], 7);
+      assert.equal(entry3.referrer.toString(), player1.publicKey.toString());
+      assert.equal(race.referredStake.toString(), ENTRY_FEE.toString());
+    });
+
+    it("should not let the creator or the operator bet on the race", async () => {
+      // Both can learn the result before it is revealed
+      const { operator } = await program.account.platformVault.fetch(platformVaultPda);
+      const revealers = [creator, authority].filter(
+        wallet => wallet === creator || wallet.publicKey.equals(operator)
+      );
+      for (const revealer of revealers) {
+        try {
+          await joinRace(racePda, raceVaultPda, referralCode, revealer, 4);
+          assert.fail("Should have rejected a bet from whoever can reveal the seed");
+        } catch (error) {
+          assert.include(error.toString(), "RevealerCannotBet");
+        }
+      }
+    });
+
+    it("should not let a player back the same horse twice in a fixed race", async () => {
+      const [player1EntryPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("player_entry"), racePda.toBuffer(), player1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .joinRace({ win: {} }, [3, 0, 0], referralCode, null, null)
+          .accounts({
+            race: racePda,
+            playerEntry: player1EntryPda,
+            player: player1.publicKey,
+            raceVault: raceVaultPda,
+            mint: null,
+            playerTokenAccount: null,
+            raceVaultTokenAccount: null,
+            tokenProgram: null,
+            combinationPool: null,
+            platformVault: platformVaultPda,
+            referrerRewards: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([player1])
+          .rpc();
+        
+        assert.fail("Should have thrown error for a repeated fixed race bet");
+      } catch (error) {
+        assert.include(error.toString(), "AlreadyJoined");
+      }
     });
 
     it("should fail to join with invalid referral code", async () => {
@@ -340,12 +698,19 @@ describe("gor-race", () => {
 
       try {
         await program.methods
-          .joinRace(1, "INVALID1") // Invalid referral code
+          .joinRace({ win: {} }, [1, 0, 0], "INVALID1", null, null) // Invalid referral code
           .accounts({
             race: racePda,
             playerEntry: newPlayerEntryPda,
             player: newPlayer.publicKey,
             raceVault: raceVaultPda,
+            mint: null,
+            playerTokenAccount: null,
+            raceVaultTokenAccount: null,
+            tokenProgram: null,
+            combinationPool: null,
+            platformVault: platformVaultPda,
+            referrerRewards: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([newPlayer])
@@ -358,53 +723,386 @@ describe("gor-race", () => {
     });
   });
 
+  describe("Crank Rewards", () => {
+    it("should update the crank reward", async () => {
+      await program.methods
+        .updateCrankReward(10, new anchor.BN(50_000_000))
+        .accounts({
+          platformVault: platformVaultPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const platformVault = await program.account.platformVault.fetch(platformVaultPda);
+      assert.equal(platformVault.crankRewardBps, 10);
+      assert.equal(platformVault.maxCrankReward.toNumber(), 50_000_000);
+
+      try {
+        await program.methods
+          .updateCrankReward(500, new anchor.BN(0))
+          .accounts({
+            platformVault: platformVaultPda,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidCrankReward");
+      }
+    });
+  });
+
   describe("Race Execution", () => {
     it("should execute race in two phases", async () => {
       // Wait for wait time to expire (30 seconds + buffer)
       await new Promise(resolve => setTimeout(resolve, 3000));
 
-      // Phase 1: Start race simulation
-      await program.methods
-        .executeRace()
+      // Every execute_race that moves the race on pays its executor a share of the pool
+      const { crankRewardBps, maxCrankReward } = await program.account.platformVault.fetch(platformVaultPda);
+      const totalPool = (await program.account.race.fetch(racePda)).totalPool.toNumber();
+      const crankReward = Math.min(Math.floor((totalPool * crankRewardBps) / 10000), maxCrankReward.toNumber());
+      assert.isAbove(crankReward, 0);
+
+      // Phase 1: Start race simulation, the provider wallet pays the transaction fee
+      const starterBalanceBefore = await provider.connection.getBalance(player2.publicKey);
+      const startTx = await program.methods
+        .executeRace(null)
         .accounts({
           race: racePda,
           raceVault: raceVaultPda,
           platformVault: platformVaultPda,
-          recentBlockhashes: anchor.web3.SYSVAR_RECENT_BLOCKHASHES_PUBKEY,
+          executor: player2.publicKey,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
           clock: SYSVAR_CLOCK_PUBKEY,
+          mint: null,
+          raceVaultTokenAccount: null,
+          platformTokenAccount: null,
+          executorTokenAccount: null,
+          tokenProgram: null,
+          creatorRewards: creatorRewardsPda,
           systemProgram: SystemProgram.programId,
         })
+        .signers([player2])
         .rpc();
 
       let race = await program.account.race.fetch(racePda);
       assert.equal(Object.keys(race.status)[0], "racing");
       assert.isNotNull(race.raceStartTime);
+      assert.equal(race.crankRewards.toNumber(), crankReward);
+      assert.equal(await provider.connection.getBalance(player2.publicKey), starterBalanceBefore + crankReward);
+
+      const started = await emittedEvent(startTx, "RaceStarted");
+      assert.ok(started.race.equals(racePda));
+      assert.equal(started.entryCount, 3);
+      const startReward = await emittedEvent(startTx, "CrankRewardPaid");
+      assert.ok(startReward.executor.equals(player2.publicKey));
+      assert.equal(startReward.amount.toNumber(), crankReward);
+
+      // A call that does not move the race on fails and pays nothing
+      const idleBalanceBefore = await provider.connection.getBalance(player3.publicKey);
+      try {
+        await program.methods
+          .executeRace(null)
+          .accounts({
+            race: racePda,
+            raceVault: raceVaultPda,
+            platformVault: platformVaultPda,
+            executor: player3.publicKey,
+            slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+            clock: SYSVAR_CLOCK_PUBKEY,
+            mint: null,
+            raceVaultTokenAccount: null,
+            platformTokenAccount: null,
+            executorTokenAccount: null,
+            tokenProgram: null,
+            creatorRewards: creatorRewardsPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([player3])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "RaceNotReady");
+      }
+      assert.equal(await provider.connection.getBalance(player3.publicKey), idleBalanceBefore);
+      assert.equal((await program.account.race.fetch(racePda)).crankRewards.toNumber(), crankReward);
 
       // Wait for race simulation to complete (60 seconds + buffer)
       // In testing, we'll use a shorter wait to speed up tests
       await new Promise(resolve => setTimeout(resolve, 2000));
 
-      // Phase 2: Complete race and determine winners
-      await program.methods
-        .executeRace()
+      // Phase 2: Creator reveals the seed to complete the race and determine winners
+      const revealerBalanceBefore = await provider.connection.getBalance(creator.publicKey);
+      const finishTx = await program.methods
+        .executeRace(Array.from(raceSeed))
         .accounts({
           race: racePda,
           raceVault: raceVaultPda,
           platformVault: platformVaultPda,
-          recentBlockhashes: anchor.web3.SYSVAR_RECENT_BLOCKHASHES_PUBKEY,
+          executor: creator.publicKey,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
           clock: SYSVAR_CLOCK_PUBKEY,
+          mint: null,
+          raceVaultTokenAccount: null,
+          platformTokenAccount: null,
+          executorTokenAccount: null,
+          tokenProgram: null,
+          creatorRewards: creatorRewardsPda,
           systemProgram: SystemProgram.programId,
         })
+        .signers([creator])
         .rpc();
 
       race = await program.account.race.fetch(racePda);
       assert.equal(Object.keys(race.status)[0], "completed");
       assert.equal(race.winningHorses.length, 3);
       assert.isNotNull(race.endTime);
+      assert.equal(race.crankRewards.toNumber(), 2 * crankReward);
+      assert.equal(await provider.connection.getBalance(creator.publicKey), revealerBalanceBefore + crankReward);
+
+      const completed = await emittedEvent(finishTx, "RaceCompleted");
+      assert.ok(completed.race.equals(racePda));
+      assert.deepEqual(Array.from(completed.winningHorses), Array.from(race.winningHorses));
       
       // Verify platform fee calculation
       const expectedPlatformFee = (ENTRY_FEE * 3 * PLATFORM_FEE_BPS) / 10000;
       assert.equal(race.platformFee.toString(), expectedPlatformFee.toString());
+      assert.equal(race.feesCollected, true);
+    });
+  });
+
+  describe("Referral Settlement", () => {
+    it("should credit a referrer once the race completes, before the entry closes", async () => {
+      const [player3EntryPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("player_entry"), racePda.toBuffer(), player3.publicKey.toBuffer()],
+        program.programId
+      );
+      const race = await program.account.race.fetch(racePda);
+      const entry = await program.account.playerEntry.fetch(player3EntryPda);
+      const owedBefore = (await program.account.rewardAccount.fetch(referrerRewardsPda)).owed;
+
+      const tx = await program.methods
+        .settleReferral()
+        .accounts({
+          race: racePda,
+          playerEntry: player3EntryPda,
+          referrerRewards: referrerRewardsPda,
+        })
+        .signers([player2])
+        .rpc();
+
+      // Pro rata to the entry's share of the referred stake
+      const expectedReward = race.referralRewards.mul(entry.entryAmount).div(race.referredStake);
+      const owedAfter = (await program.account.rewardAccount.fetch(referrerRewardsPda)).owed;
+      assert.equal(owedAfter.sub(owedBefore).toString(), expectedReward.toString());
+      assert.equal(
+        (await program.account.race.fetch(racePda)).referralRewardsPaid.toString(),
+        race.referralRewardsPaid.add(expectedReward).toString()
+      );
+      assert.isTrue((await program.account.playerEntry.fetch(player3EntryPda)).referralSettled);
+
+      const settled = await emittedEvent(tx, "ReferralSettled");
+      assert.ok(settled.referrer.equals(player1.publicKey));
+      assert.equal(settled.referrerReward.toString(), expectedReward.toString());
+
+      // A referrer is credited once, and entries without one have nothing to settle
+      for (const player of [player3, player2]) {
+        const [playerEntryPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("player_entry"), racePda.toBuffer(), player.publicKey.toBuffer()],
+          program.programId
+        );
+        try {
+          await program.methods
+            .settleReferral()
+            .accounts({
+              race: racePda,
+              playerEntry: playerEntryPda,
+              referrerRewards: referrerRewardsPda,
+            })
+            .signers([player2])
+            .rpc();
+          assert.fail("Should have nothing left to settle");
+        } catch (error) {
+          assert.include(error.toString(), "NoReferralToSettle");
+        }
+      }
+    });
+  });
+
+  describe("Player Limits", () => {
+    it("should start a full race before its wait time is over", async () => {
+      const { race, raceVault, referralCode: code } = await createRace({ maxPlayers: 1 }, 180);
+      await joinRace(race, raceVault, code, player2, 4);
+
+      await executeRace(race, raceVault, player3);
+
+      const started = await program.account.race.fetch(race);
+      assert.equal(Object.keys(started.status)[0], "racing");
+      assert.isBelow(started.raceStartTime.toNumber(), started.startTime.toNumber() + started.waitTime.toNumber());
+    });
+
+    it("should not start a race short of its minimum players before the wait time is over", async () => {
+      const { race, raceVault, referralCode: code } = await createRace({ maxPlayers: 3, minPlayers: 2 }, 180);
+      await joinRace(race, raceVault, code, player2, 4);
+
+      try {
+        await executeRace(race, raceVault, player3);
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "RaceNotReady");
+      }
+      const pending = await program.account.race.fetch(race);
+      assert.equal(Object.keys(pending.status)[0], "pending");
+      assert.equal(pending.minPlayers, 2);
+    });
+
+    it("should fail to create a race whose minimum exceeds its player cap", async () => {
+      try {
+        await createRace({ maxPlayers: 2, minPlayers: 3 });
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidMinPlayers");
+      }
+
+      try {
+        await createRace({ minPlayers: 0 });
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidMinPlayers");
+      }
+    });
+  });
+
+  describe("Cancelled Races", () => {
+    it("should refund entries net of the crank reward", async () => {
+      // One player short of the minimum, the race is cancelled once the wait time is over
+      const { race, raceVault, referralCode: code } = await createRace({ minPlayers: 2 });
+      const playerEntry = await joinRace(race, raceVault, code, player2, 1);
+      await new Promise(resolve => setTimeout(resolve, 31000));
+
+      const executorBalanceBefore = await provider.connection.getBalance(player3.publicKey);
+      const cancelTx = await executeRace(race, raceVault, player3);
+      const cancelledEvent = await emittedEvent(cancelTx, "RaceCancelled");
+      assert.deepEqual(cancelledEvent.reason, { underfilled: {} });
+
+      // Cancelled below the creator's minimum, even though the platform minimum of one was met
+      const cancelled = await program.account.race.fetch(race);
+      assert.equal(cancelled.entryCount, 1);
+      assert.equal(cancelled.minPlayers, 2);
+      const { crankRewardBps, maxCrankReward } = await program.account.platformVault.fetch(platformVaultPda);
+      const totalPool = cancelled.totalPool.toNumber();
+      const crankReward = Math.min(Math.floor((totalPool * crankRewardBps) / 10000), maxCrankReward.toNumber());
+      assert.equal(Object.keys(cancelled.status)[0], "cancelled");
+      assert.equal(cancelled.crankRewards.toNumber(), crankReward);
+      assert.equal(await provider.connection.getBalance(player3.publicKey), executorBalanceBefore + crankReward);
+
+      // The only entry bears the whole crank reward
+      const refundBalanceBefore = await provider.connection.getBalance(player2.publicKey);
+      await program.methods
+        .claimRefund()
+        .accounts({
+          race,
+          playerEntry,
+          player: player2.publicKey,
+          raceVault,
+          mint: null,
+          playerTokenAccount: null,
+          raceVaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([player2])
+        .rpc();
+
+      assert.equal(await provider.connection.getBalance(player2.publicKey), refundBalanceBefore + totalPool - crankReward);
+      const entry = await program.account.playerEntry.fetch(playerEntry);
+      assert.equal(Object.keys(entry.claimStatus)[0], "refunded");
+    });
+
+    it("should refund every entry of uneven stakes after a crank reward", async () => {
+      // Stakes that do not divide the crank reward evenly leave rounding dust behind
+      const { race, raceVault, referralCode: code } = await createRace({ minPlayers: 3, mode: { pariMutuel: {} } });
+      const entries = [
+        { player: player2, entry: await joinRace(race, raceVault, code, player2, 1, new anchor.BN(100_000_000)) },
+        { player: player3, entry: await joinRace(race, raceVault, code, player3, 2, new anchor.BN(233_333_333)) },
+      ];
+      await new Promise(resolve => setTimeout(resolve, 31000));
+      await executeRace(race, raceVault, player1);
+
+      const cancelled = await program.account.race.fetch(race);
+      assert.equal(Object.keys(cancelled.status)[0], "cancelled");
+      const totalPool = cancelled.totalPool.toNumber();
+      const crankRewards = cancelled.crankRewards.toNumber();
+
+      // Both refunds go through, the last one included
+      let refunded = 0;
+      for (const { player, entry } of entries) {
+        const { entryAmount } = await program.account.playerEntry.fetch(entry);
+        const balanceBefore = await provider.connection.getBalance(player.publicKey);
+        await program.methods
+          .claimRefund()
+          .accounts({
+            race,
+            playerEntry: entry,
+            player: player.publicKey,
+            raceVault,
+            mint: null,
+            playerTokenAccount: null,
+            raceVaultTokenAccount: null,
+            tokenProgram: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([player])
+          .rpc();
+
+        const refund = entryAmount.mul(cancelled.totalPool.sub(cancelled.crankRewards)).div(cancelled.totalPool).toNumber();
+        assert.equal(await provider.connection.getBalance(player.publicKey), balanceBefore + refund);
+        refunded += refund;
+      }
+
+      const vaultRent = await provider.connection.getMinimumBalanceForRentExemption(0);
+      assert.equal(await provider.connection.getBalance(raceVault), vaultRent + totalPool - crankRewards - refunded);
+    });
+
+    it("should pay the creator bond to the entries when the seed is never revealed", async () => {
+      // A full race starts at once, then nobody reveals within the race and reveal windows
+      const { race, raceVault, referralCode: code } = await createRace({ maxPlayers: 1 }, 180);
+      const playerEntry = await joinRace(race, raceVault, code, player2, 1);
+      await executeRace(race, raceVault, player3);
+      await new Promise(resolve => setTimeout(resolve, 151000));
+
+      const cancelTx = await executeRace(race, raceVault, player3);
+      const cancelledEvent = await emittedEvent(cancelTx, "RaceCancelled");
+      assert.deepEqual(cancelledEvent.reason, { seedNotRevealed: {} });
+
+      const cancelled = await program.account.race.fetch(race);
+      assert.isTrue(cancelled.bondForfeited);
+
+      // The only entry takes the whole bond on top of its refund
+      const refundBalanceBefore = await provider.connection.getBalance(player2.publicKey);
+      const refundTx = await program.methods
+        .claimRefund()
+        .accounts({
+          race,
+          playerEntry,
+          player: player2.publicKey,
+          raceVault,
+          mint: null,
+          playerTokenAccount: null,
+          raceVaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([player2])
+        .rpc();
+
+      const refund = cancelled.totalPool.toNumber() - cancelled.crankRewards.toNumber();
+      assert.equal(await provider.connection.getBalance(player2.publicKey), refundBalanceBefore + refund + CREATOR_BOND);
+      const refunded = await emittedEvent(refundTx, "RefundClaimed");
+      assert.equal(refunded.refundAmount.toNumber(), refund);
+      assert.equal(refunded.bondShare.toNumber(), CREATOR_BOND);
     });
   });
 
@@ -423,13 +1121,18 @@ describe("gor-race", () => {
         );
 
         const playerEntry = await program.account.playerEntry.fetch(playerEntryPda);
-        const wonPosition = winningHorses.findIndex(h => h === playerEntry.horseNumber);
+        const wonPosition = winningHorses.findIndex(h => h === playerEntry.bets[0].selection[0]);
 
         if (wonPosition !== -1) {
-          // Player won, try to claim prize
+          // Player won, try to claim prize and count the race on their profile
+          const [playerProfilePda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("player_profile"), player.publicKey.toBuffer()],
+            program.programId
+          );
+          const profileBefore = await program.account.playerProfile.fetch(playerProfilePda);
           const balanceBefore = await provider.connection.getBalance(player.publicKey);
 
-          await program.methods
+          const claimTx = await program.methods
             .claimPrize()
             .accounts({
               race: racePda,
@@ -437,6 +1140,11 @@ describe("gor-race", () => {
               player: player.publicKey,
               raceVault: raceVaultPda,
               platformVault: platformVaultPda,
+              mint: null,
+              playerTokenAccount: null,
+              raceVaultTokenAccount: null,
+              tokenProgram: null,
+              playerProfile: playerProfilePda,
               systemProgram: SystemProgram.programId,
             })
             .signers([player])
@@ -448,10 +1156,52 @@ describe("gor-race", () => {
           assert(balanceAfter > balanceBefore, "Winner should receive prize");
           assert.equal(Object.keys(updatedEntry.claimStatus)[0], "claimed");
           assert(updatedEntry.prizeAmount.gt(new anchor.BN(0)), "Prize amount should be > 0");
+
+          // Prizes come out of the pool left after the platform fee and crank rewards
+          const prizePool = race.totalPool.sub(race.platformFee).sub(race.crankRewards);
+          const expectedPrize = prizePool.muln(race.prizeDistribution[wonPosition]).divn(10000);
+          assert.equal(updatedEntry.prizeAmount.toString(), expectedPrize.toString());
+
+          const claimed = await emittedEvent(claimTx, "PrizeClaimed");
+          assert.ok(claimed.player.equals(player.publicKey));
+          assert.equal(claimed.prizeAmount.toString(), updatedEntry.prizeAmount.toString());
+
+          // The claim settles the player's stats with the final prize
+          const profileAfter = await program.account.playerProfile.fetch(playerProfilePda);
+          assert.equal(updatedEntry.statsUpdated, true);
+          assert.equal(profileAfter.totalRaces, profileBefore.totalRaces + 1);
+          assert.equal(
+            profileAfter.totalEarnings.toString(),
+            profileBefore.totalEarnings.add(updatedEntry.prizeAmount).toString()
+          );
         }
       }
     });
 
+    it("should pay every winner of uneven stakes out of a rent-exempt vault", async () => {
+      // Both entries back horse 1, so whatever wins they split the pool 1:2 with rounding dust
+      const { race, raceVault, referralCode: code } = await createRace({ maxPlayers: 2, mode: { pariMutuel: {} } });
+      await joinRace(race, raceVault, code, player2, 1, new anchor.BN(100_000_000));
+      await joinRace(race, raceVault, code, player3, 1, new anchor.BN(200_000_000));
+      await executeRace(race, raceVault, player3);
+      await new Promise(resolve => setTimeout(resolve, 61000));
+      await executeRace(race, raceVault, creator, raceSeed);
+
+      const completed = await program.account.race.fetch(race);
+      assert.equal(Object.keys(completed.status)[0], "completed");
+
+      // The last claim leaves the dust on top of the vault's rent reserve instead of failing
+      const first = await claimPrize(race, raceVault, player2);
+      const second = await claimPrize(race, raceVault, player3);
+      const prizePool = completed.totalPool.sub(completed.platformFee).sub(completed.crankRewards);
+      assert(first.add(second).lte(prizePool), "Payouts should never exceed the prize pool");
+      assert(second.gte(first.muln(2)), "Payouts should follow the stakes");
+
+      const vaultRent = await provider.connection.getMinimumBalanceForRentExemption(0);
+      const vaultBalance = await provider.connection.getBalance(raceVault);
+      assert.equal(vaultBalance, vaultRent + prizePool.sub(first).sub(second).toNumber());
+    });
+
     it("should prevent double claiming", async () => {
       const race = await program.account.race.fetch(racePda);
       const winningHorses = race.winningHorses;
@@ -464,7 +1214,7 @@ describe("gor-race", () => {
         );
 
         const playerEntry = await program.account.playerEntry.fetch(playerEntryPda);
-        const wonPosition = winningHorses.findIndex(h => h === playerEntry.horseNumber);
+        const wonPosition = winningHorses.findIndex(h => h === playerEntry.bets[0].selection[0]);
 
         if (wonPosition !== -1 && Object.keys(playerEntry.claimStatus)[0] === "claimed") {
           try {
@@ -476,6 +1226,11 @@ describe("gor-race", () => {
                 player: player.publicKey,
                 raceVault: raceVaultPda,
                 platformVault: platformVaultPda,
+                mint: null,
+                playerTokenAccount: null,
+                raceVaultTokenAccount: null,
+                tokenProgram: null,
+                playerProfile: null,
                 systemProgram: SystemProgram.programId,
               })
               .signers([player])
@@ -491,11 +1246,234 @@ describe("gor-race", () => {
     });
   });
 
-  describe("Statistics Update", () => {
-    it("should update player statistics after race completion", async () => {
-      const race = await program.account.race.fetch(racePda);
+  describe("Token Races", () => {
+    const TRANSFER_FEE_BPS = 100; // 1% withheld on every transfer of the test mint
+    let revenueSplit: [number, number];
+
+    const setRevenueSplit = (creatorShareBps: number, referrerShareBps: number) =>
+      program.methods
+        .updateRevenueSplit(creatorShareBps, referrerShareBps)
+        .accounts({ platformVault: platformVaultPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+    // Rewards are paid in GOR only, token races need the revenue split off
+    before(async () => {
+      const { creatorShareBps, referrerShareBps } = await program.account.platformVault.fetch(platformVaultPda);
+      revenueSplit = [creatorShareBps, referrerShareBps];
+      await setRevenueSplit(0, 0);
+    });
+
+    after(async () => {
+      await setRevenueSplit(...revenueSplit);
+    });
+
+    // Token-2022 mint with the given extensions initialized, authority mints it
+    async function createMint2022(extensions: ExtensionType[], initExtensions: (mint: PublicKey) => anchor.web3.TransactionInstruction[]) {
+      const mint = Keypair.generate();
+      const space = getMintLen(extensions);
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          SystemProgram.createAccount({
+            fromPubkey: authority.publicKey,
+            newAccountPubkey: mint.publicKey,
+            space,
+            lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+            programId: TOKEN_2022_PROGRAM_ID,
+          }),
+          ...initExtensions(mint.publicKey),
+          createInitializeMintInstruction(mint.publicKey, 9, authority.publicKey, null, TOKEN_2022_PROGRAM_ID)
+        ),
+        [mint]
+      );
+      return mint.publicKey;
+    }
+
+    function createTokenRace(mint: PublicKey, race: PublicKey, raceVault: PublicKey) {
+      return program.methods
+        .createRace(
+          new anchor.BN(30),
+          Array.from(crypto.createHash("sha256").update(raceSeed).digest()),
+          { pariMutuel: {} },
+          { entryFee: null, maxPlayers: 2, minPlayers: null, prizeDistribution: null }
+        )
+        .accounts({
+          race,
+          creator: creator.publicKey,
+          raceVault,
+          platformVault: platformVaultPda,
+          mint,
+          raceVaultTokenAccount: getAssociatedTokenAddressSync(mint, raceVault, true, TOKEN_2022_PROGRAM_ID),
+          platformTokenAccount: getAssociatedTokenAddressSync(mint, platformVaultPda, true, TOKEN_2022_PROGRAM_ID),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          creatorRewards: creatorRewardsPda,
+          systemProgram: SystemProgram.programId,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([creator]);
+    }
+
+    async function nextRace() {
+      const id = (await program.account.platformVault.fetch(platformVaultPda)).nextRaceId;
+      const [race] = PublicKey.findProgramAddressSync(
+        [Buffer.from("race"), id.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [raceVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("race_vault"), race.toBuffer()],
+        program.programId
+      );
+      return { id, race, raceVault };
+    }
+
+    it("should run a race in a transfer-fee mint from join to claim", async () => {
+      const mint = await createMint2022([ExtensionType.TransferFeeConfig], mint => [
+        createInitializeTransferFeeConfigInstruction(
+          mint,
+          authority.publicKey,
+          authority.publicKey,
+          TRANSFER_FEE_BPS,
+          BigInt(1_000_000_000_000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+      ]);
+      const tokenAccounts = new Map<string, PublicKey>();
+      for (const wallet of [player1, player2, creator]) {
+        const account = await createAssociatedTokenAccount(
+          provider.connection, authority, mint, wallet.publicKey, undefined, TOKEN_2022_PROGRAM_ID
+        );
+        await mintTo(provider.connection, authority, mint, account, authority, 1_000_000_000, [], undefined, TOKEN_2022_PROGRAM_ID);
+        tokenAccounts.set(wallet.publicKey.toString(), account);
+      }
+      const tokenAccount = (wallet: Keypair) => tokenAccounts.get(wallet.publicKey.toString());
+      const balance = async (account: PublicKey) =>
+        new anchor.BN((await getAccount(provider.connection, account, "confirmed", TOKEN_2022_PROGRAM_ID)).amount.toString());
+      const transferFee = (amount: anchor.BN) =>
+        amount.muln(TRANSFER_FEE_BPS).addn(9999).divn(10000);
+
+      const { id, race, raceVault } = await nextRace();
+      await createTokenRace(mint, race, raceVault).rpc();
+      const raceVaultTokenAccount = getAssociatedTokenAddressSync(mint, raceVault, true, TOKEN_2022_PROGRAM_ID);
+      const tokenRaceAccounts = {
+        race,
+        raceVault,
+        platformVault: platformVaultPda,
+        mint,
+        raceVaultTokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      };
+
+      // Stakes are recorded from what reached the vault, net of the mint's transfer fee.
+      // Both back horse 1 in a pari-mutuel race, so whatever wins they split the pool.
+      const entryFee = (await program.account.race.fetch(race)).entryFee;
+      const code = generateReferralCode(id.toNumber());
+      for (const player of [player1, player2]) {
+        const [playerEntry] = PublicKey.findProgramAddressSync(
+          [Buffer.from("player_entry"), race.toBuffer(), player.publicKey.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .joinRace({ win: {} }, [1, 0, 0], code, null, null)
+          .accounts({
+            ...tokenRaceAccounts,
+            playerEntry,
+            player: player.publicKey,
+            playerTokenAccount: tokenAccount(player),
+            combinationPool: null,
+            referrerRewards: null,
+          })
+          .signers([player])
+          .rpc();
+        const entry = await program.account.playerEntry.fetch(playerEntry);
+        assert.equal(entry.entryAmount.toString(), entryFee.sub(transferFee(entryFee)).toString());
+      }
+      let state = await program.account.race.fetch(race);
+      assert.equal((await balance(raceVaultTokenAccount)).toString(), state.totalPool.toString());
 
-      // Update stats for all players
+      // The full race starts at once, then the creator reveals the seed once it has run
+      const execute = (executor: Keypair, seed: Buffer | null) =>
+        program.methods
+          .executeRace(seed ? Array.from(seed) : null)
+          .accounts({
+            ...tokenRaceAccounts,
+            executor: executor.publicKey,
+            slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+            clock: SYSVAR_CLOCK_PUBKEY,
+            platformTokenAccount: getAssociatedTokenAddressSync(mint, platformVaultPda, true, TOKEN_2022_PROGRAM_ID),
+            executorTokenAccount: tokenAccount(executor),
+            creatorRewards: creatorRewardsPda,
+          })
+          .signers([executor])
+          .rpc();
+      await execute(player2, null);
+      await new Promise(resolve => setTimeout(resolve, 61000));
+      await execute(creator, raceSeed);
+
+      state = await program.account.race.fetch(race);
+      assert.equal(Object.keys(state.status)[0], "completed");
+      const prizePool = state.totalPool.sub(state.platformFee).sub(state.crankRewards);
+      assert.equal((await balance(raceVaultTokenAccount)).toString(), prizePool.toString());
+
+      // Each player is paid their half less the fee the mint withholds on the way out
+      for (const player of [player1, player2]) {
+        const before = await balance(tokenAccount(player));
+        const [playerEntry] = PublicKey.findProgramAddressSync(
+          [Buffer.from("player_entry"), race.toBuffer(), player.publicKey.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .claimPrize()
+          .accounts({
+            ...tokenRaceAccounts,
+            playerEntry,
+            player: player.publicKey,
+            playerTokenAccount: tokenAccount(player),
+            playerProfile: null,
+          })
+          .signers([player])
+          .rpc();
+
+        const prize = (await program.account.playerEntry.fetch(playerEntry)).prizeAmount;
+        assert.equal(prize.toString(), prizePool.divn(2).toString());
+        assert.equal((await balance(tokenAccount(player))).sub(before).toString(), prize.sub(transferFee(prize)).toString());
+      }
+    });
+
+    it("should not create a token race while the platform shares its fee", async () => {
+      const mint = await createMint2022([], () => []);
+      const { race, raceVault } = await nextRace();
+
+      await setRevenueSplit(2000, 0);
+      try {
+        await createTokenRace(mint, race, raceVault).rpc();
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "TokenRaceRewardsUnsupported");
+      } finally {
+        await setRevenueSplit(0, 0);
+      }
+    });
+
+    it("should not create a race in a non-transferable mint", async () => {
+      const mint = await createMint2022([ExtensionType.NonTransferable], mint => [
+        createInitializeNonTransferableMintInstruction(mint, TOKEN_2022_PROGRAM_ID),
+      ]);
+      const { race, raceVault } = await nextRace();
+
+      try {
+        await createTokenRace(mint, race, raceVault).rpc();
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "UnsupportedMintExtension");
+      }
+    });
+  });
+
+  describe("Statistics Update", () => {
+    it("should count the race for players who won nothing", async () => {
+      // Update stats for every player whose claim did not already count the race
       for (const player of [player1, player2, player3]) {
         const [playerEntryPda] = PublicKey.findProgramAddressSync(
           [Buffer.from("player_entry"), racePda.toBuffer(), player.publicKey.toBuffer()],
@@ -507,6 +1485,12 @@ describe("gor-race", () => {
           program.programId
         );
 
+        const entry = await program.account.playerEntry.fetch(playerEntryPda);
+        if (entry.statsUpdated) {
+          assert.equal(Object.keys(entry.claimStatus)[0], "claimed");
+          continue;
+        }
+
         const profileBefore = await program.account.playerProfile.fetch(playerProfilePda);
 
         await program.methods
@@ -518,28 +1502,16 @@ describe("gor-race", () => {
             player: player.publicKey,
             clock: SYSVAR_CLOCK_PUBKEY,
           })
+          .signers([player])
           .rpc();
 
         const profileAfter = await program.account.playerProfile.fetch(playerProfilePda);
         const updatedEntry = await program.account.playerEntry.fetch(playerEntryPda);
 
-        // Verify stats were updated
+        // Participation counts, earnings do not change
         assert.equal(profileAfter.totalRaces, profileBefore.totalRaces + 1);
+        assert.equal(profileAfter.totalEarnings.toString(), profileBefore.totalEarnings.toString());
         assert.equal(updatedEntry.statsUpdated, true);
-
-        // Check if player won and stats reflect that
-        const playerEntry = await program.account.playerEntry.fetch(playerEntryPda);
-        const wonPosition = race.winningHorses.findIndex(h => h === playerEntry.horseNumber);
-
-        if (wonPosition !== -1) {
-          assert(profileAfter.totalPodiums > profileBefore.totalPodiums, "Podium count should increase");
-          if (wonPosition === 0) {
-            assert(profileAfter.totalWins > profileBefore.totalWins, "Win count should increase for 1st place");
-          }
-          if (playerEntry.prizeAmount.gt(new anchor.BN(0))) {
-            assert(profileAfter.totalEarnings.gt(profileBefore.totalEarnings), "Earnings should increase");
-          }
-        }
       }
     });
 
@@ -559,6 +1531,7 @@ describe("gor-race", () => {
           player: player1.publicKey,
           clock: SYSVAR_CLOCK_PUBKEY,
         })
+        .signers([player1])
         .rpc();
 
       // Should complete without error but not change stats
@@ -571,7 +1544,7 @@ describe("gor-race", () => {
     it("should update platform fee", async () => {
       const newFeeBps = 750; // 7.5%
       
-      await program.methods
+      const tx = await program.methods
         .updatePlatformFee(newFeeBps)
         .accounts({
           platformVault: platformVaultPda,
@@ -582,6 +1555,54 @@ describe("gor-race", () => {
 
       const platformVault = await program.account.platformVault.fetch(platformVaultPda);
       assert.equal(platformVault.platformFeeBps, newFeeBps);
+
+      const feeUpdated = await emittedEvent(tx, "FeeUpdated");
+      assert.equal(feeUpdated.platformFeeBps, newFeeBps);
+      assert.ok(feeUpdated.authority.equals(authority.publicKey));
+    });
+
+    it("should reconcile platform fees with the vault balance", async () => {
+      const report = await program.methods
+        .reconcileFees()
+        .accounts({
+          platformVault: platformVaultPda,
+        })
+        .view();
+
+      const platformVault = await program.account.platformVault.fetch(platformVaultPda);
+      assert.equal(report.totalFeesCollected.toString(), platformVault.totalFeesCollected.toString());
+      // Only one side of the ledger can be off
+      assert.isTrue(report.surplus.isZero() || report.shortfall.isZero());
+      console.log("Fees held:", report.feesHeld.toString(), "surplus:", report.surplus.toString(), "shortfall:", report.shortfall.toString());
+    });
+
+    it("should update the creator and referrer revenue split", async () => {
+      await program.methods
+        .updateRevenueSplit(2000, 1000)
+        .accounts({
+          platformVault: platformVaultPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const platformVault = await program.account.platformVault.fetch(platformVaultPda);
+      assert.equal(platformVault.creatorShareBps, 2000);
+      assert.equal(platformVault.referrerShareBps, 1000);
+
+      try {
+        await program.methods
+          .updateRevenueSplit(8000, 3000)
+          .accounts({
+            platformVault: platformVaultPda,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "InvalidRevenueSplit");
+      }
     });
 
     it("should withdraw platform fees", async () => {
@@ -592,12 +1613,159 @@ describe("gor-race", () => {
         .accounts({
           platformVault: platformVaultPda,
           authority: authority.publicKey,
+          destination: null,
+          mint: null,
+          platformTokenAccount: null,
+          destinationTokenAccount: null,
+          tokenProgram: null,
           systemProgram: SystemProgram.programId,
         })
         .signers([authority])
         .rpc();
 
-      // Test passes if no error is thrown
+      const platformVault = await program.account.platformVault.fetch(platformVaultPda);
+      assert(platformVault.totalFeesWithdrawn.gte(withdrawAmount), "Withdrawal should be recorded");
+    });
+
+    it("should not withdraw the platform vault's rent reserve", async () => {
+      const balance = await provider.connection.getBalance(platformVaultPda);
+
+      try {
+        await program.methods
+          .withdrawPlatformFees(new anchor.BN(balance))
+          .accounts({
+            platformVault: platformVaultPda,
+            authority: authority.publicKey,
+            destination: null,
+            mint: null,
+            platformTokenAccount: null,
+            destinationTokenAccount: null,
+            tokenProgram: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+        assert.fail("Should have thrown an error");
+      } catch (error) {
+        assert.include(error.toString(), "InsufficientFunds");
+      }
+    });
+
+    it("should pause and resume race creation", async () => {
+      const PAUSE_CREATE_RACE = 1 << 0;
+      const pausedRaceId = (await program.account.platformVault.fetch(platformVaultPda)).nextRaceId;
+      const [pausedRacePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("race"), pausedRaceId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [pausedRaceVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("race_vault"), pausedRacePda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .setPause(PAUSE_CREATE_RACE)
+        .accounts({
+          platformVault: platformVaultPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      try {
+        await program.methods
+          .createRace(
+            new anchor.BN(30),
+            Array.from(crypto.createHash("sha256").update(raceSeed).digest()),
+            { fixed: {} },
+            { entryFee: null, maxPlayers: null, minPlayers: null, prizeDistribution: null }
+          )
+          .accounts({
+            race: pausedRacePda,
+            creator: creator.publicKey,
+            raceVault: pausedRaceVaultPda,
+            platformVault: platformVaultPda,
+            mint: null,
+            raceVaultTokenAccount: null,
+            platformTokenAccount: null,
+            tokenProgram: null,
+            associatedTokenProgram: null,
+            creatorRewards: creatorRewardsPda,
+            systemProgram: SystemProgram.programId,
+            clock: SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([creator])
+          .rpc();
+
+        assert.fail("Should have thrown error while race creation is paused");
+      } catch (error) {
+        assert.include(error.toString(), "Paused");
+      } finally {
+        await program.methods
+          .setPause(0)
+          .accounts({
+            platformVault: platformVaultPda,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+      }
+
+      const platformVault = await program.account.platformVault.fetch(platformVaultPda);
+      assert.equal(platformVault.paused, 0);
+    });
+
+    it("should propose, cancel and accept an authority transfer", async () => {
+      const newAuthority = Keypair.generate();
+
+      await program.methods
+        .proposeAuthority(newAuthority.publicKey)
+        .accounts({
+          platformVault: platformVaultPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      let platformVault = await program.account.platformVault.fetch(platformVaultPda);
+      assert.equal(platformVault.pendingAuthority.toString(), newAuthority.publicKey.toString());
+
+      await program.methods
+        .cancelAuthorityTransfer()
+        .accounts({
+          platformVault: platformVaultPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      platformVault = await program.account.platformVault.fetch(platformVaultPda);
+      assert.isNull(platformVault.pendingAuthority);
+
+      // Hand authority over and straight back so later tests keep working
+      for (const [current, next] of [[authority, newAuthority], [newAuthority, authority]]) {
+        await program.methods
+          .proposeAuthority(next.publicKey)
+          .accounts({
+            platformVault: platformVaultPda,
+            authority: current.publicKey,
+          })
+          .signers([current])
+          .rpc();
+
+        await program.methods
+          .acceptAuthority()
+          .accounts({
+            platformVault: platformVaultPda,
+            newAuthority: next.publicKey,
+          })
+          .signers([next])
+          .rpc();
+
+        platformVault = await program.account.platformVault.fetch(platformVaultPda);
+        assert.equal(platformVault.authority.toString(), next.publicKey.toString());
+        assert.isNull(platformVault.pendingAuthority);
+      }
     });
   });
 
@@ -616,12 +1784,19 @@ describe("gor-race", () => {
         );
 
         await program.methods
-          .joinRace(11, "TESTCODE") // Invalid horse number (> 10)
+          .joinRace({ win: {} }, [11, 0, 0], "TESTCODE", null, null) // Invalid horse number (> 10)
           .accounts({
             race: newRacePda,
             playerEntry: playerEntryPda,
             player: player1.publicKey,
             raceVault: PublicKey.default,
+            mint: null,
+            playerTokenAccount: null,
+            raceVaultTokenAccount: null,
+            tokenProgram: null,
+            combinationPool: null,
+            platformVault: platformVaultPda,
+            referrerRewards: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([player1])
@@ -639,6 +1814,7 @@ describe("gor-race", () => {
           .createProfile("DuplicateUser")
           .accounts({
             playerProfile: player1ProfilePda,
+            usernameAccount: usernamePda("DuplicateUser"),
             player: player1.publicKey,
             systemProgram: SystemProgram.programId,
             clock: SYSVAR_CLOCK_PUBKEY,
@@ -655,6 +1831,14 @@ describe("gor-race", () => {
   });
 });
 
+// Usernames are registered by their lowercase form
+function usernamePda(username: string): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("username"), Buffer.from(username.toLowerCase())],
+    anchor.workspace.GorRace.programId
+  )[0];
+}
+
 // Helper function to generate referral code from race ID
 function generateReferralCode(raceId: number): string {
   const chars = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";